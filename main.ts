@@ -22,6 +22,7 @@ import {
 
 interface HelloWorldPluginSettings {
 	mySetting: string;
+	autoClusterCount: boolean;
 }
 
 export default class HelloWorldPlugin extends Plugin {
@@ -243,5 +244,19 @@ class SampleSettingTab extends PluginSettingTab {
 						await this.plugin.saveSettings();
 					})
 			);
+
+		new Setting(containerEl)
+			.setName("Automatic cluster count")
+			.setDesc(
+				"Pick the number of vector clusters by silhouette score instead of from the note count. Slower on large vaults."
+			)
+			.addToggle((toggle) =>
+				toggle
+					.setValue(this.plugin.settings.autoClusterCount)
+					.onChange(async (value) => {
+						this.plugin.settings.autoClusterCount = value;
+						await this.plugin.saveSettings();
+					})
+			);
 	}
 }
@@ -2,6 +2,8 @@
 //!
 //! This module constructs sparse adjacency matrices from note links,
 //! where M[i][j] = number of forward links from note i to note j.
+//! [`GraphOptions`] controls how links become edge weights and which
+//! Laplacian is built.
 
 use crate::PluginError;
 use serde::{Deserialize, Serialize};
@@ -19,6 +21,46 @@ pub struct NoteLink {
     pub to_id: usize,
 }
 
+/// Which graph Laplacian to build.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LaplacianKind {
+    /// L = D - A.
+    #[default]
+    Combinatorial,
+    /// L = I - D^-1/2 A D^-1/2.
+    SymmetricNormalized,
+    /// L = I - D^-1 A.
+    RandomWalk,
+}
+
+/// Options controlling how links are turned into a matrix.
+///
+/// The defaults reproduce the plain directed link-count matrix.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GraphOptions {
+    /// Keep link direction; when false the adjacency is symmetrised (A + Aᵀ).
+    pub directed: bool,
+    /// Laplacian built by `build_laplacian`.
+    pub laplacian: LaplacianKind,
+    /// Keep links from a note to itself.
+    pub self_loops: bool,
+    /// Replace link counts with 1 for every connected pair.
+    pub binarize: bool,
+}
+
+impl Default for GraphOptions {
+    fn default() -> Self {
+        Self {
+            directed: true,
+            laplacian: LaplacianKind::Combinatorial,
+            self_loops: true,
+            binarize: false,
+        }
+    }
+}
+
 /// Builds adjacency matrices from note links.
 ///
 /// The matrix M[i][j] represents the number of forward links from note i to note j.
@@ -28,6 +70,8 @@ pub struct AdjacencyMatrixBuilder {
     num_notes: usize,
     /// Map from note paths to indices.
     note_id_map: HashMap<String, usize>,
+    /// How links become edge weights.
+    options: GraphOptions,
 }
 
 impl AdjacencyMatrixBuilder {
@@ -40,6 +84,19 @@ impl AdjacencyMatrixBuilder {
     /// A new builder configured for the given notes
     #[must_use]
     pub fn new(note_paths: Vec<String>) -> Self {
+        Self::with_options(note_paths, GraphOptions::default())
+    }
+
+    /// Create a new adjacency matrix builder with custom graph options.
+    ///
+    /// # Arguments
+    /// * `note_paths` - List of note paths in the vault
+    /// * `options` - Direction, Laplacian flavour, self-loop and weighting options
+    ///
+    /// # Returns
+    /// A new builder configured for the given notes
+    #[must_use]
+    pub fn with_options(note_paths: Vec<String>, options: GraphOptions) -> Self {
         let num_notes = note_paths.len();
         let note_id_map = note_paths
             .into_iter()
@@ -47,11 +104,21 @@ impl AdjacencyMatrixBuilder {
             .map(|(i, path)| (path, i))
             .collect();
 
-        Self { num_notes, note_id_map }
+        Self { num_notes, note_id_map, options }
+    }
+
+    /// Get the graph options used by this builder.
+    #[must_use]
+    pub const fn options(&self) -> &GraphOptions {
+        &self.options
     }
 
     /// Build the sparse adjacency matrix from a list of links.
     ///
+    /// Applies the builder's [`GraphOptions`]: self-loops are dropped if
+    /// disabled, undirected graphs are symmetrised (A + Aᵀ), and binarised
+    /// graphs get weight 1 for every non-zero entry.
+    ///
     /// # Arguments
     /// * `links` - List of note links
     ///
@@ -79,10 +146,20 @@ impl AdjacencyMatrixBuilder {
 
         // Build sparse matrix from link counts
         for ((from, to), count) in link_counts {
+            if from == to && !self.options.self_loops {
+                continue;
+            }
             triplets.add_triplet(from, to, count as f64);
+            if !self.options.directed {
+                triplets.add_triplet(to, from, count as f64);
+            }
         }
 
-        Ok(triplets.to_csr())
+        let mut matrix = triplets.to_csr();
+        if self.options.binarize {
+            matrix.map_inplace(|_| 1.0);
+        }
+        Ok(matrix)
     }
 
     /// Convert adjacency matrix to vector representation.
@@ -127,11 +204,29 @@ impl AdjacencyMatrixBuilder {
         self.note_id_map.get(path).copied()
     }
 
+    /// Get note paths ordered by index, the inverse of [`Self::get_note_index`].
+    ///
+    /// # Returns
+    /// One path per note index; indices shadowed by a duplicate path are empty
+    #[must_use]
+    pub fn note_paths(&self) -> Vec<String> {
+        let mut paths = vec![String::new(); self.num_notes];
+        for (path, &index) in &self.note_id_map {
+            paths[index].clone_from(path);
+        }
+        paths
+    }
+
     /// Build the graph Laplacian matrix from a list of links.
     ///
-    /// The Laplacian matrix L = D - A, where:
-    /// - D is the degree matrix (diagonal, D[i][i] = out-degree of node i)
-    /// - A is the adjacency matrix
+    /// The flavour follows [`GraphOptions::laplacian`], where:
+    /// - D is the degree matrix (diagonal, D[i][i] = out-degree of node i,
+    ///   which is the total degree for undirected graphs)
+    /// - A is the adjacency matrix produced by [`Self::build`]
+    ///
+    /// Normalized variants leave rows of notes without links at zero. They are
+    /// meant for undirected graphs; on directed graphs they normalize by
+    /// out-degree only.
     ///
     /// # Arguments
     /// * `links` - List of note links
@@ -145,22 +240,40 @@ impl AdjacencyMatrixBuilder {
         // First build the adjacency matrix
         let adjacency = self.build(links)?;
 
-        // Compute degree matrix (diagonal with out-degrees)
-        let mut degree_triplets = TriMat::new((self.num_notes, self.num_notes));
-        for i in 0..self.num_notes {
-            let row_sum: f64 = adjacency
-                .outer_view(i)
-                .map_or(0.0, |row| row.iter().map(|(_, &val)| val).sum());
-            if row_sum > 0.0 {
-                degree_triplets.add_triplet(i, i, row_sum);
+        // Degrees are row sums (out-degrees)
+        let degrees: Vec<f64> = adjacency
+            .outer_iterator()
+            .map(|row| row.iter().map(|(_, &val)| val).sum())
+            .collect();
+
+        // D^-1/2 for the symmetric normalized Laplacian
+        let inverse_sqrt: Vec<f64> = degrees
+            .iter()
+            .map(|&d| if d > 0.0 { d.sqrt().recip() } else { 0.0 })
+            .collect();
+
+        let mut triplets = TriMat::new((self.num_notes, self.num_notes));
+        for (i, &degree) in degrees.iter().enumerate() {
+            if degree > 0.0 {
+                let diagonal = match self.options.laplacian {
+                    LaplacianKind::Combinatorial => degree,
+                    LaplacianKind::SymmetricNormalized | LaplacianKind::RandomWalk => 1.0,
+                };
+                triplets.add_triplet(i, i, diagonal);
+            }
+        }
+        for (i, row) in adjacency.outer_iterator().enumerate() {
+            for (j, &val) in row.iter() {
+                let weight = match self.options.laplacian {
+                    LaplacianKind::Combinatorial => val,
+                    LaplacianKind::SymmetricNormalized => val * inverse_sqrt[i] * inverse_sqrt[j],
+                    LaplacianKind::RandomWalk => val / degrees[i],
+                };
+                triplets.add_triplet(i, j, -weight);
             }
         }
-        let degree = degree_triplets.to_csr();
-
-        // Compute L = D - A
-        let laplacian = &degree - &adjacency;
 
-        Ok(laplacian)
+        Ok(triplets.to_csr())
     }
 }
 
@@ -180,6 +293,15 @@ mod tests {
         assert_eq!(builder.get_note_index("note3.md"), Some(2));
     }
 
+    #[test]
+    fn test_note_paths_inverts_index() {
+        let builder = AdjacencyMatrixBuilder::new(vec!["a.md".to_string(), "b.md".to_string()]);
+        let paths = builder.note_paths();
+
+        assert_eq!(paths, vec!["a.md", "b.md"]);
+        assert_eq!(builder.get_note_index(&paths[1]), Some(1));
+    }
+
     #[test]
     fn test_adjacency_matrix_simple() {
         let note_paths =
@@ -302,4 +424,86 @@ mod tests {
         assert_eq!(vectors[0], vec![1.0, -1.0]);
         assert_eq!(vectors[1], vec![0.0, 0.0]);
     }
+
+    fn options_builder(options: GraphOptions) -> AdjacencyMatrixBuilder {
+        let note_paths = (1..=3).map(|i| format!("note{i}.md")).collect();
+        AdjacencyMatrixBuilder::with_options(note_paths, options)
+    }
+
+    fn options_links() -> Vec<NoteLink> {
+        vec![
+            NoteLink { from_id: 0, to_id: 0 },
+            NoteLink { from_id: 0, to_id: 1 },
+            NoteLink { from_id: 0, to_id: 1 },
+            NoteLink { from_id: 1, to_id: 2 },
+        ]
+    }
+
+    #[test]
+    fn test_undirected_symmetrises_links() {
+        let options = GraphOptions { directed: false, ..GraphOptions::default() };
+        let builder = options_builder(options);
+        let matrix = builder
+            .build(options_links())
+            .expect("Failed to build matrix");
+        let vectors = builder.matrix_to_vectors(&matrix);
+
+        // A + Aᵀ doubles the self-loop
+        assert_eq!(vectors[0], vec![2.0, 2.0, 0.0]);
+        assert_eq!(vectors[1], vec![2.0, 0.0, 1.0]);
+        assert_eq!(vectors[2], vec![0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_drop_self_loops_and_binarize() {
+        let options = GraphOptions { self_loops: false, binarize: true, ..GraphOptions::default() };
+        let builder = options_builder(options);
+        let matrix = builder
+            .build(options_links())
+            .expect("Failed to build matrix");
+        let vectors = builder.matrix_to_vectors(&matrix);
+
+        assert_eq!(vectors[0], vec![0.0, 1.0, 0.0]);
+        assert_eq!(vectors[1], vec![0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_symmetric_normalized_laplacian() {
+        let options = GraphOptions {
+            directed: false,
+            laplacian: LaplacianKind::SymmetricNormalized,
+            self_loops: false,
+            binarize: true,
+        };
+        let builder = options_builder(options);
+        let matrix = builder
+            .build_laplacian(options_links())
+            .expect("Failed to build Laplacian");
+        let vectors = builder.matrix_to_vectors(&matrix);
+
+        // Path graph 0 - 1 - 2 with degrees 1, 2, 1
+        let off = -1.0 / 2.0_f64.sqrt();
+        assert_eq!(vectors[0], vec![1.0, off, 0.0]);
+        assert_eq!(vectors[1], vec![off, 1.0, off]);
+        assert_eq!(vectors[2], vec![0.0, off, 1.0]);
+    }
+
+    #[test]
+    fn test_random_walk_laplacian_rows_sum_to_zero() {
+        let options = GraphOptions {
+            directed: false,
+            laplacian: LaplacianKind::RandomWalk,
+            ..GraphOptions::default()
+        };
+        let builder = options_builder(options);
+        let matrix = builder
+            .build_laplacian(options_links())
+            .expect("Failed to build Laplacian");
+        let vectors = builder.matrix_to_vectors(&matrix);
+
+        assert_eq!(vectors[1], vec![-2.0 / 3.0, 1.0, -1.0 / 3.0]);
+        for row in &vectors {
+            assert!(row.iter().sum::<f64>().abs() < 1e-12);
+        }
+    }
 }
@@ -0,0 +1,204 @@
+//! Layout alignment between successive embeddings.
+//!
+//! Recomputing an embedding can rotate or mirror the whole scene even when
+//! little has changed. Orthogonal Procrustes finds the rotation (or
+//! reflection) plus translation that best maps the new layout onto the
+//! previous one, using the notes present in both.
+
+use crate::PluginError;
+use nalgebra::DMatrix;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A note's position in an embedding.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NotePosition {
+    /// Note file path or unique ID.
+    pub id: String,
+    /// Coordinates in the embedding.
+    pub coordinates: Vec<f64>,
+}
+
+/// Rotate and translate a layout to best match a previous one.
+///
+/// Finds the orthogonal transform minimising the squared distance between
+/// shared notes. Reflections are allowed, so a mirrored layout is flipped
+/// back. Distances between notes are preserved.
+///
+/// # Arguments
+/// * `current` - The new layout
+/// * `previous` - The layout to match
+///
+/// # Returns
+/// The aligned positions, in the same order as `current`
+///
+/// # Errors
+/// Returns error if dimensions differ, naming the input and index of the
+/// offending position, or if fewer than two notes are shared
+pub fn procrustes_align(
+    current: &[NotePosition],
+    previous: &[NotePosition],
+) -> Result<Vec<NotePosition>, PluginError> {
+    let dim = current.first().map_or(0, |p| p.coordinates.len());
+    for (input, positions) in [("current", current), ("previous", previous)] {
+        for (i, position) in positions.iter().enumerate() {
+            if position.coordinates.len() != dim {
+                return Err(PluginError::ValidationError {
+                    field: format!("{input}[{i}].coordinates"),
+                    value: position.id.clone(),
+                    reason: format!(
+                        "Expected {dim} dimensions, got {}",
+                        position.coordinates.len()
+                    ),
+                });
+            }
+        }
+    }
+
+    let previous_by_id: HashMap<&str, &[f64]> = previous
+        .iter()
+        .map(|p| (p.id.as_str(), p.coordinates.as_slice()))
+        .collect();
+    let shared: Vec<(&[f64], &[f64])> = current
+        .iter()
+        .filter_map(|p| {
+            previous_by_id
+                .get(p.id.as_str())
+                .map(|&target| (p.coordinates.as_slice(), target))
+        })
+        .collect();
+
+    if shared.len() < 2 {
+        return Err(PluginError::InsufficientData { required: 2, provided: shared.len() });
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let count = shared.len() as f64;
+    let source_mean: Vec<f64> = (0..dim)
+        .map(|d| shared.iter().map(|(s, _)| s[d]).sum::<f64>() / count)
+        .collect();
+    let target_mean: Vec<f64> = (0..dim)
+        .map(|d| shared.iter().map(|(_, t)| t[d]).sum::<f64>() / count)
+        .collect();
+
+    // Cross-covariance M = Σ (x - μx)ᵀ (y - μy); the optimal map is R = U Vᵀ
+    let mut covariance = DMatrix::<f64>::zeros(dim, dim);
+    for (source, target) in &shared {
+        for i in 0..dim {
+            for j in 0..dim {
+                covariance[(i, j)] += (source[i] - source_mean[i]) * (target[j] - target_mean[j]);
+            }
+        }
+    }
+    let svd = covariance.svd(true, true);
+    let (Some(u), Some(v_t)) = (svd.u, svd.v_t) else {
+        return Err(PluginError::DimensionalityReductionError {
+            method: "Procrustes".to_string(),
+            reason: "SVD of the cross-covariance failed".to_string(),
+        });
+    };
+    let rotation = u * v_t;
+
+    Ok(current
+        .iter()
+        .map(|p| NotePosition {
+            id: p.id.clone(),
+            coordinates: (0..dim)
+                .map(|j| {
+                    target_mean[j]
+                        + (0..dim)
+                            .map(|i| (p.coordinates[i] - source_mean[i]) * rotation[(i, j)])
+                            .sum::<f64>()
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(id: &str, coordinates: Vec<f64>) -> NotePosition {
+        NotePosition { id: id.to_string(), coordinates }
+    }
+
+    fn previous_layout() -> Vec<NotePosition> {
+        vec![
+            position("a", vec![0.0, 0.0]),
+            position("b", vec![2.0, 0.0]),
+            position("c", vec![0.0, 1.0]),
+            position("d", vec![3.0, 3.0]),
+        ]
+    }
+
+    #[test]
+    fn test_undoes_rotation_and_translation() {
+        let previous = previous_layout();
+        // Rotate by 90 degrees, shift, and drop a note
+        let current: Vec<NotePosition> = previous[..3]
+            .iter()
+            .map(|p| {
+                let [x, y] = [p.coordinates[0], p.coordinates[1]];
+                position(&p.id, vec![-y + 5.0, x - 1.0])
+            })
+            .chain([position("new", vec![4.0, 1.0])])
+            .collect();
+
+        let aligned = procrustes_align(&current, &previous).expect("Alignment failed");
+
+        for (got, expected) in aligned.iter().zip(&previous[..3]) {
+            assert_eq!(got.id, expected.id);
+            for (g, e) in got.coordinates.iter().zip(&expected.coordinates) {
+                assert!((g - e).abs() < 1e-9);
+            }
+        }
+        // The new note gets the same inverse transform: (u, v) -> (v + 1, 5 - u)
+        assert!((aligned[3].coordinates[0] - 2.0).abs() < 1e-9);
+        assert!((aligned[3].coordinates[1] - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_undoes_mirroring() {
+        let previous = previous_layout();
+        let current: Vec<NotePosition> = previous
+            .iter()
+            .map(|p| position(&p.id, vec![-p.coordinates[0], p.coordinates[1]]))
+            .collect();
+
+        let aligned = procrustes_align(&current, &previous).expect("Alignment failed");
+
+        for (got, expected) in aligned.iter().zip(&previous) {
+            for (g, e) in got.coordinates.iter().zip(&expected.coordinates) {
+                assert!((g - e).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dimension_mismatch_names_input_and_index() {
+        let mut previous = previous_layout();
+        previous[2].coordinates = vec![0.0, 1.0, 2.0];
+
+        let result = procrustes_align(&previous_layout(), &previous);
+
+        match result {
+            Err(PluginError::ValidationError { field, value, reason }) => {
+                assert_eq!(field, "previous[2].coordinates");
+                assert_eq!(value, "c");
+                assert_eq!(reason, "Expected 2 dimensions, got 3");
+            },
+            _ => panic!("Expected ValidationError"),
+        }
+    }
+
+    #[test]
+    fn test_requires_shared_notes() {
+        let previous = previous_layout();
+        let current = vec![position("a", vec![1.0, 1.0]), position("zzz", vec![0.0, 0.0])];
+
+        let result = procrustes_align(&current, &previous);
+
+        assert!(matches!(result, Err(PluginError::InsufficientData { required: 2, provided: 1 })));
+    }
+}
@@ -0,0 +1,218 @@
+//! Minimal little-endian binary encoding helpers.
+//!
+//! Used for compact on-disk formats (indices, fitted models) that the plugin
+//! stores in the vault's plugin data folder.
+
+use crate::PluginError;
+
+/// Append-only little-endian byte writer.
+#[derive(Debug, Default)]
+pub struct ByteWriter {
+    /// Encoded bytes so far.
+    buf: Vec<u8>,
+}
+
+impl ByteWriter {
+    /// Create a writer that starts with a format magic and version byte.
+    #[must_use]
+    pub fn with_header(magic: [u8; 4], version: u8) -> Self {
+        let mut writer = Self::default();
+        writer.buf.extend_from_slice(&magic);
+        writer.write_u8(version);
+        writer
+    }
+
+    /// Write a single byte.
+    pub fn write_u8(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    /// Write a boolean as one byte.
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_u8(u8::from(value));
+    }
+
+    /// Write a 32-bit unsigned integer.
+    pub fn write_u32(&mut self, value: u32) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Write a 64-bit unsigned integer.
+    pub fn write_u64(&mut self, value: u64) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Write a length or index, stored as 32 bits.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn write_len(&mut self, value: usize) {
+        self.write_u32(value as u32);
+    }
+
+    /// Write a 32-bit float.
+    pub fn write_f32(&mut self, value: f32) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Write a 64-bit float.
+    pub fn write_f64(&mut self, value: f64) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Write a length-prefixed UTF-8 string.
+    pub fn write_str(&mut self, value: &str) {
+        self.write_len(value.len());
+        self.buf.extend_from_slice(value.as_bytes());
+    }
+
+    /// Finish writing and return the encoded bytes.
+    #[must_use]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+/// Cursor over little-endian encoded bytes.
+#[derive(Debug)]
+pub struct ByteReader<'a> {
+    /// Source bytes.
+    bytes: &'a [u8],
+    /// Current read offset.
+    pos: usize,
+    /// Context reported in errors.
+    context: &'static str,
+}
+
+impl<'a> ByteReader<'a> {
+    /// Create a reader, checking the format magic and returning the version byte.
+    ///
+    /// # Errors
+    /// Returns error if the data is truncated or the magic does not match
+    pub fn with_header(
+        bytes: &'a [u8],
+        magic: [u8; 4],
+        context: &'static str,
+    ) -> Result<(Self, u8), PluginError> {
+        let mut reader = Self { bytes, pos: 0, context };
+        if reader.take(4)? != magic.as_slice() {
+            return Err(reader.error("unrecognised format header"));
+        }
+        let version = reader.read_u8()?;
+        Ok((reader, version))
+    }
+
+    /// Build a serialization error carrying this reader's context.
+    #[must_use]
+    pub fn error(&self, message: &str) -> PluginError {
+        PluginError::SerializationError {
+            context: self.context.to_string(),
+            source: format!("{message} at byte {}", self.pos),
+        }
+    }
+
+    /// Number of bytes not yet consumed.
+    ///
+    /// Decoders use this to bound allocations sized by untrusted length fields.
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    /// Consume `len` bytes.
+    fn take(&mut self, len: usize) -> Result<&'a [u8], PluginError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len());
+        let Some(end) = end else {
+            return Err(self.error("unexpected end of data"));
+        };
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    /// Consume exactly `N` bytes as an array.
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], PluginError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    /// Read a single byte.
+    ///
+    /// # Errors
+    /// Returns error if the data is truncated
+    pub fn read_u8(&mut self) -> Result<u8, PluginError> {
+        Ok(self.take_array::<1>()?[0])
+    }
+
+    /// Read a boolean byte.
+    ///
+    /// # Errors
+    /// Returns error if the data is truncated
+    pub fn read_bool(&mut self) -> Result<bool, PluginError> {
+        Ok(self.read_u8()? != 0)
+    }
+
+    /// Read a 32-bit unsigned integer.
+    ///
+    /// # Errors
+    /// Returns error if the data is truncated
+    pub fn read_u32(&mut self) -> Result<u32, PluginError> {
+        Ok(u32::from_le_bytes(self.take_array()?))
+    }
+
+    /// Read a 64-bit unsigned integer.
+    ///
+    /// # Errors
+    /// Returns error if the data is truncated
+    pub fn read_u64(&mut self) -> Result<u64, PluginError> {
+        Ok(u64::from_le_bytes(self.take_array()?))
+    }
+
+    /// Read a length or index written by [`ByteWriter::write_len`].
+    ///
+    /// # Errors
+    /// Returns error if the data is truncated
+    pub fn read_len(&mut self) -> Result<usize, PluginError> {
+        Ok(self.read_u32()? as usize)
+    }
+
+    /// Read a 32-bit float.
+    ///
+    /// # Errors
+    /// Returns error if the data is truncated
+    pub fn read_f32(&mut self) -> Result<f32, PluginError> {
+        Ok(f32::from_le_bytes(self.take_array()?))
+    }
+
+    /// Read a 64-bit float.
+    ///
+    /// # Errors
+    /// Returns error if the data is truncated
+    pub fn read_f64(&mut self) -> Result<f64, PluginError> {
+        Ok(f64::from_le_bytes(self.take_array()?))
+    }
+
+    /// Read a length-prefixed UTF-8 string.
+    ///
+    /// # Errors
+    /// Returns error if the data is truncated or not valid UTF-8
+    pub fn read_str(&mut self) -> Result<String, PluginError> {
+        let len = self.read_len()?;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| self.error("invalid UTF-8 string"))
+    }
+
+    /// Check that every byte has been consumed.
+    ///
+    /// # Errors
+    /// Returns error if trailing bytes remain
+    pub fn finish(&self) -> Result<(), PluginError> {
+        if self.pos == self.bytes.len() {
+            Ok(())
+        } else {
+            Err(self.error("trailing data"))
+        }
+    }
+}
@@ -0,0 +1,719 @@
+//! Node importance scores over the note link graph.
+//!
+//! All algorithms work directly on the sparse CSR adjacency matrix produced by
+//! `AdjacencyMatrixBuilder::build`, where entry (i, j) is the number of links
+//! from note i to note j.
+
+use crate::PluginError;
+use crate::rng::SeededRng;
+use serde::{Deserialize, Serialize};
+use sprs::CsMat;
+use std::collections::VecDeque;
+
+/// Settings for PageRank power iteration.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PageRankConfig {
+    /// Probability of following a link rather than teleporting (typically 0.85).
+    pub damping: f64,
+    /// Stop once the L1 change between iterations falls below this.
+    pub tolerance: f64,
+    /// Upper bound on power iterations.
+    pub max_iterations: usize,
+}
+
+impl Default for PageRankConfig {
+    fn default() -> Self {
+        Self { damping: 0.85, tolerance: 1e-9, max_iterations: 200 }
+    }
+}
+
+/// Scores produced by an iterative ranking algorithm.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PageRankResult {
+    /// Score per note, summing to 1.
+    pub scores: Vec<f64>,
+    /// Number of power iterations performed.
+    pub iterations: usize,
+    /// Whether the tolerance was reached before `max_iterations`.
+    pub converged: bool,
+}
+
+/// Compute PageRank over a link graph.
+///
+/// Links are weighted by their count. Notes without outgoing links
+/// (dangling nodes) spread their score uniformly over every note.
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix
+/// * `config` - Damping factor and stopping criteria
+///
+/// # Returns
+/// Scores summing to 1, plus convergence information
+///
+/// # Errors
+/// Returns error if the matrix is not square or the damping factor is outside [0, 1)
+pub fn pagerank(
+    adjacency: &CsMat<f64>,
+    config: &PageRankConfig,
+) -> Result<PageRankResult, PluginError> {
+    let n = validate_graph(adjacency, config)?;
+    #[allow(clippy::cast_precision_loss)]
+    let teleport = vec![1.0 / n.max(1) as f64; n];
+    Ok(power_iteration(adjacency, &teleport, config))
+}
+
+/// Compute personalized PageRank relative to a set of seed notes.
+///
+/// Teleports (and dangling-node mass) return to the seeds only, so each
+/// score measures how reachable a note is from the seeds.
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix
+/// * `seeds` - Indices of the seed notes
+/// * `config` - Damping factor and stopping criteria
+///
+/// # Returns
+/// Relevance scores summing to 1, plus convergence information
+///
+/// # Errors
+/// Returns error if the matrix is not square, the damping factor is outside
+/// [0, 1), or the seeds are empty or out of range
+pub fn personalized_pagerank(
+    adjacency: &CsMat<f64>,
+    seeds: &[usize],
+    config: &PageRankConfig,
+) -> Result<PageRankResult, PluginError> {
+    let n = validate_graph(adjacency, config)?;
+    if seeds.is_empty() {
+        return Err(PluginError::ValidationError {
+            field: "seeds".to_string(),
+            value: "[]".to_string(),
+            reason: "At least one seed note is required".to_string(),
+        });
+    }
+    if let Some(&bad) = seeds.iter().find(|&&s| s >= n) {
+        return Err(PluginError::ValidationError {
+            field: "seeds".to_string(),
+            value: bad.to_string(),
+            reason: format!("Seed index must be below {n}"),
+        });
+    }
+
+    let mut teleport = vec![0.0; n];
+    for &seed in seeds {
+        teleport[seed] += 1.0;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let seed_count = seeds.len() as f64;
+    for weight in &mut teleport {
+        *weight /= seed_count;
+    }
+
+    Ok(power_iteration(adjacency, &teleport, config))
+}
+
+/// Check the matrix and config, returning the node count.
+fn validate_graph(adjacency: &CsMat<f64>, config: &PageRankConfig) -> Result<usize, PluginError> {
+    let (rows, cols) = adjacency.shape();
+    if rows != cols {
+        return Err(PluginError::ValidationError {
+            field: "adjacency".to_string(),
+            value: format!("{rows}x{cols}"),
+            reason: "Adjacency matrix must be square".to_string(),
+        });
+    }
+    if !(0.0..1.0).contains(&config.damping) {
+        return Err(PluginError::ValidationError {
+            field: "damping".to_string(),
+            value: config.damping.to_string(),
+            reason: "Damping factor must be in [0, 1)".to_string(),
+        });
+    }
+    Ok(rows)
+}
+
+/// Run PageRank power iteration with the given teleport distribution.
+fn power_iteration(
+    adjacency: &CsMat<f64>,
+    teleport: &[f64],
+    config: &PageRankConfig,
+) -> PageRankResult {
+    let n = teleport.len();
+    let csr = adjacency.to_csr();
+    let out_weight: Vec<f64> = (0..n)
+        .map(|i| {
+            csr.outer_view(i)
+                .map_or(0.0, |row| row.iter().map(|(_, &w)| w).sum())
+        })
+        .collect();
+
+    let mut scores = teleport.to_vec();
+    let mut iterations = 0;
+    let mut converged = false;
+    while iterations < config.max_iterations {
+        iterations += 1;
+
+        let mut next = vec![0.0; n];
+        let mut dangling = 0.0;
+        for (i, row) in csr.outer_iterator().enumerate() {
+            if out_weight[i] > 0.0 {
+                let share = config.damping * scores[i] / out_weight[i];
+                for (j, &w) in row.iter() {
+                    next[j] += share * w;
+                }
+            } else {
+                dangling += scores[i];
+            }
+        }
+
+        // Teleport and dangling mass both follow the teleport distribution
+        let redistributed = config.damping.mul_add(dangling, 1.0 - config.damping);
+        for (value, &t) in next.iter_mut().zip(teleport) {
+            *value += redistributed * t;
+        }
+
+        let change = l1_distance(&next, &scores);
+        scores = next;
+        if change < config.tolerance {
+            converged = true;
+            break;
+        }
+    }
+
+    PageRankResult { scores, iterations, converged }
+}
+
+/// Settings for HITS power iteration.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HitsConfig {
+    /// Stop once the L1 change of both score vectors falls below this.
+    pub tolerance: f64,
+    /// Upper bound on power iterations.
+    pub max_iterations: usize,
+}
+
+impl Default for HitsConfig {
+    fn default() -> Self {
+        Self { tolerance: 1e-9, max_iterations: 200 }
+    }
+}
+
+/// Hub and authority scores from HITS.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HitsResult {
+    /// Hub score per note (notes that link to good authorities), summing to 1.
+    pub hubs: Vec<f64>,
+    /// Authority score per note (notes linked from good hubs), summing to 1.
+    pub authorities: Vec<f64>,
+    /// Number of power iterations performed.
+    pub iterations: usize,
+    /// Whether the tolerance was reached before `max_iterations`.
+    pub converged: bool,
+    /// L1 change of the scores in the final iteration.
+    pub final_change: f64,
+}
+
+/// Compute HITS hub and authority scores over a link graph.
+///
+/// Alternates a = Aᵀh and h = Aa until both vectors stop changing. Links are
+/// weighted by their count. A graph without links yields all-zero scores.
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix
+/// * `config` - Stopping criteria
+///
+/// # Returns
+/// Hub and authority scores, each summing to 1, plus convergence diagnostics
+///
+/// # Errors
+/// Returns error if the matrix is not square
+pub fn hits(adjacency: &CsMat<f64>, config: &HitsConfig) -> Result<HitsResult, PluginError> {
+    let (n, cols) = adjacency.shape();
+    if n != cols {
+        return Err(PluginError::ValidationError {
+            field: "adjacency".to_string(),
+            value: format!("{n}x{cols}"),
+            reason: "Adjacency matrix must be square".to_string(),
+        });
+    }
+
+    let csr = adjacency.to_csr();
+    #[allow(clippy::cast_precision_loss)]
+    let mut hubs = vec![1.0 / n.max(1) as f64; n];
+    let mut authorities = vec![0.0; n];
+    let mut iterations = 0;
+    let mut converged = false;
+    let mut final_change = 0.0;
+
+    while iterations < config.max_iterations {
+        iterations += 1;
+
+        let mut next_authorities = vec![0.0; n];
+        for (i, row) in csr.outer_iterator().enumerate() {
+            for (j, &w) in row.iter() {
+                next_authorities[j] += w * hubs[i];
+            }
+        }
+        normalize_l1(&mut next_authorities);
+
+        let mut next_hubs: Vec<f64> = csr
+            .outer_iterator()
+            .map(|row| row.iter().map(|(j, &w)| w * next_authorities[j]).sum())
+            .collect();
+        normalize_l1(&mut next_hubs);
+
+        final_change =
+            l1_distance(&next_hubs, &hubs) + l1_distance(&next_authorities, &authorities);
+        hubs = next_hubs;
+        authorities = next_authorities;
+        if final_change < config.tolerance {
+            converged = true;
+            break;
+        }
+    }
+
+    Ok(HitsResult { hubs, authorities, iterations, converged, final_change })
+}
+
+/// Settings for betweenness centrality.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BetweennessConfig {
+    /// Follow links only in their direction; otherwise treat them as undirected.
+    pub directed: bool,
+    /// Scale scores by the number of node pairs so they lie in [0, 1].
+    pub normalized: bool,
+    /// Number of source notes to sample; 0 uses every note (exact).
+    pub samples: usize,
+    /// Seed for choosing sampled sources.
+    pub seed: u64,
+}
+
+impl Default for BetweennessConfig {
+    fn default() -> Self {
+        Self { directed: false, normalized: true, samples: 0, seed: 42 }
+    }
+}
+
+/// Structurally critical notes of a link graph.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalNotes {
+    /// Betweenness centrality per note.
+    pub betweenness: Vec<f64>,
+    /// Notes whose removal disconnects their component, in increasing order.
+    pub articulation_points: Vec<usize>,
+    /// Links whose removal disconnects their component, as `[lower, higher]`
+    /// note index pairs in increasing order. Direction is ignored.
+    pub bridges: Vec<[usize; 2]>,
+}
+
+/// Compute betweenness centrality with Brandes' algorithm.
+///
+/// Every shortest path (in hops) between a pair of notes contributes to the
+/// notes strictly inside it, split evenly between equally short paths. With
+/// sampling, only some sources are expanded and the totals are scaled up,
+/// giving an unbiased estimate in a fraction of the time.
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix
+/// * `config` - Direction, normalisation and sampling settings
+///
+/// # Returns
+/// Betweenness per note
+///
+/// # Errors
+/// Returns error if the matrix is not square
+pub fn betweenness_centrality(
+    adjacency: &CsMat<f64>,
+    config: &BetweennessConfig,
+) -> Result<Vec<f64>, PluginError> {
+    let neighbors = neighbor_lists(adjacency, config.directed)?;
+    let n = neighbors.len();
+
+    let mut sources: Vec<usize> = (0..n).collect();
+    if config.samples > 0 && config.samples < n {
+        SeededRng::new(config.seed).shuffle(&mut sources);
+        sources.truncate(config.samples);
+        sources.sort_unstable();
+    }
+
+    let mut scores = vec![0.0; n];
+    let mut order: Vec<usize> = Vec::with_capacity(n);
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut paths = vec![0.0; n];
+    let mut distance = vec![usize::MAX; n];
+    let mut dependency = vec![0.0; n];
+    let mut queue = VecDeque::with_capacity(n);
+    for &source in &sources {
+        // Count shortest paths from the source breadth-first
+        for &node in &order {
+            predecessors[node].clear();
+            paths[node] = 0.0;
+            distance[node] = usize::MAX;
+            dependency[node] = 0.0;
+        }
+        order.clear();
+        paths[source] = 1.0;
+        distance[source] = 0;
+        queue.push_back(source);
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for &next in &neighbors[node] {
+                if distance[next] == usize::MAX {
+                    distance[next] = distance[node] + 1;
+                    queue.push_back(next);
+                }
+                if distance[next] == distance[node] + 1 {
+                    paths[next] += paths[node];
+                    predecessors[next].push(node);
+                }
+            }
+        }
+
+        // Accumulate dependencies from the farthest notes back
+        for &node in order.iter().rev() {
+            for &previous in &predecessors[node] {
+                dependency[previous] += paths[previous] / paths[node] * (1.0 + dependency[node]);
+            }
+            if node != source {
+                scores[node] += dependency[node];
+            }
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut scale = n as f64 / sources.len().max(1) as f64;
+    if config.normalized {
+        if n > 2 {
+            #[allow(clippy::cast_precision_loss)]
+            let pairs = ((n - 1) * (n - 2)) as f64;
+            scale /= pairs;
+        }
+    } else if !config.directed {
+        // Each undirected path is found from both of its ends
+        scale /= 2.0;
+    }
+    for score in &mut scores {
+        *score *= scale;
+    }
+    Ok(scores)
+}
+
+/// Find articulation points and bridges of the undirected link graph.
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix; direction and self-loops are ignored
+///
+/// # Returns
+/// Articulation points and bridges, each in increasing order
+///
+/// # Errors
+/// Returns error if the matrix is not square
+pub fn articulation_points_and_bridges(
+    adjacency: &CsMat<f64>,
+) -> Result<(Vec<usize>, Vec<[usize; 2]>), PluginError> {
+    let neighbors = neighbor_lists(adjacency, false)?;
+    let n = neighbors.len();
+
+    let mut discovery = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut is_articulation = vec![false; n];
+    let mut bridges = Vec::new();
+    let mut counter = 0;
+    for root in 0..n {
+        if discovery[root] != usize::MAX {
+            continue;
+        }
+        discovery[root] = counter;
+        low[root] = counter;
+        counter += 1;
+        let mut root_children = 0;
+        // Explicit stack of (node, parent, next neighbour position)
+        let mut calls = vec![(root, usize::MAX, 0)];
+        while let Some(frame) = calls.last_mut() {
+            let (node, parent) = (frame.0, frame.1);
+            if let Some(&next) = neighbors[node].get(frame.2) {
+                frame.2 += 1;
+                if discovery[next] == usize::MAX {
+                    discovery[next] = counter;
+                    low[next] = counter;
+                    counter += 1;
+                    if node == root {
+                        root_children += 1;
+                    }
+                    calls.push((next, node, 0));
+                } else if next != parent {
+                    low[node] = low[node].min(discovery[next]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if parent != usize::MAX {
+                low[parent] = low[parent].min(low[node]);
+                let parent_discovery = discovery[parent];
+                if low[node] > parent_discovery {
+                    bridges.push([parent.min(node), parent.max(node)]);
+                }
+                if parent != root && low[node] >= parent_discovery {
+                    is_articulation[parent] = true;
+                }
+            }
+        }
+        is_articulation[root] = root_children > 1;
+    }
+
+    bridges.sort_unstable();
+    let articulation_points = (0..n).filter(|&node| is_articulation[node]).collect();
+    Ok((articulation_points, bridges))
+}
+
+/// Find the notes and links that hold the link graph together.
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix
+/// * `config` - Betweenness settings
+///
+/// # Returns
+/// Betweenness per note, articulation points and bridges
+///
+/// # Errors
+/// Returns error if the matrix is not square
+pub fn critical_notes(
+    adjacency: &CsMat<f64>,
+    config: &BetweennessConfig,
+) -> Result<CriticalNotes, PluginError> {
+    let betweenness = betweenness_centrality(adjacency, config)?;
+    let (articulation_points, bridges) = articulation_points_and_bridges(adjacency)?;
+    Ok(CriticalNotes { betweenness, articulation_points, bridges })
+}
+
+/// Distinct neighbours of each node, excluding self-loops.
+fn neighbor_lists(adjacency: &CsMat<f64>, directed: bool) -> Result<Vec<Vec<usize>>, PluginError> {
+    let (n, cols) = adjacency.shape();
+    if n != cols {
+        return Err(PluginError::ValidationError {
+            field: "adjacency".to_string(),
+            value: format!("{n}x{cols}"),
+            reason: "Adjacency matrix must be square".to_string(),
+        });
+    }
+
+    let csr = adjacency.to_csr();
+    let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (i, row) in csr.outer_iterator().enumerate() {
+        for (j, &w) in row.iter() {
+            if i != j && w != 0.0 {
+                neighbors[i].push(j);
+                if !directed {
+                    neighbors[j].push(i);
+                }
+            }
+        }
+    }
+    for list in &mut neighbors {
+        list.sort_unstable();
+        list.dedup();
+    }
+    Ok(neighbors)
+}
+
+/// Scale non-negative scores to sum to 1 (left as zeros if they sum to 0).
+fn normalize_l1(values: &mut [f64]) {
+    let total: f64 = values.iter().sum();
+    if total > 0.0 {
+        for value in values {
+            *value /= total;
+        }
+    }
+}
+
+/// L1 distance between two score vectors.
+fn l1_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::graph;
+
+    #[test]
+    fn test_pagerank_cycle_is_uniform() {
+        let result = pagerank(&graph(3, &[(0, 1), (1, 2), (2, 0)]), &PageRankConfig::default())
+            .expect("PageRank failed");
+
+        assert!(result.converged);
+        for score in &result.scores {
+            assert!((score - 1.0 / 3.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_pagerank_hub_ranks_highest() {
+        // Everyone links to note 0; note 0 is dangling
+        let result =
+            pagerank(&graph(4, &[(1, 0), (2, 0), (3, 0), (1, 2)]), &PageRankConfig::default())
+                .expect("PageRank failed");
+
+        assert!((result.scores.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        let best = (0..4).max_by(|&a, &b| result.scores[a].total_cmp(&result.scores[b]));
+        assert_eq!(best, Some(0));
+        assert!(result.scores[2] > result.scores[3]);
+    }
+
+    #[test]
+    fn test_pagerank_known_values() {
+        // Two-node graph 0 -> 1 where note 1 is dangling
+        let result =
+            pagerank(&graph(2, &[(0, 1)]), &PageRankConfig::default()).expect("PageRank failed");
+
+        // Solving r0 = 0.075 + 0.425 r1 with r0 + r1 = 1 gives r0 = 1 / 2.85
+        let r0 = 1.0 / 2.85;
+        assert!((result.scores[0] - r0).abs() < 1e-9);
+        assert!((result.scores[1] - (1.0 - r0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_personalized_pagerank_favors_seed_neighborhood() {
+        // Two triangles joined by a single edge
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)];
+        let result = personalized_pagerank(&graph(6, &edges), &[0], &PageRankConfig::default())
+            .expect("Personalized PageRank failed");
+
+        assert!(result.scores[1] > result.scores[4]);
+        assert!(result.scores[2] > result.scores[5]);
+    }
+
+    #[test]
+    fn test_personalized_pagerank_rejects_bad_seeds() {
+        let adjacency = graph(2, &[(0, 1)]);
+        let config = PageRankConfig::default();
+
+        assert!(personalized_pagerank(&adjacency, &[], &config).is_err());
+        assert!(matches!(
+            personalized_pagerank(&adjacency, &[5], &config),
+            Err(PluginError::ValidationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_pagerank_rejects_bad_damping() {
+        let config = PageRankConfig { damping: 1.0, ..PageRankConfig::default() };
+        let result = pagerank(&graph(2, &[(0, 1)]), &config);
+
+        assert!(matches!(result, Err(PluginError::ValidationError { .. })));
+    }
+
+    #[test]
+    fn test_hits_separates_hubs_and_authorities() {
+        // Index notes 0 and 1 link to concept notes 2, 3 and 4
+        let edges = [(0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (4, 2)];
+        let result = hits(&graph(5, &edges), &HitsConfig::default()).expect("HITS failed");
+
+        assert!(result.converged);
+        assert!(result.final_change < 1e-9);
+        assert!(result.hubs[0] > result.hubs[2]);
+        assert!(result.hubs[0] > result.hubs[4]);
+        assert!(result.authorities[2] > result.authorities[0]);
+        assert!(result.authorities[2] > result.authorities[4]);
+        assert!((result.hubs.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!((result.authorities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_hits_without_links_is_zero() {
+        let result = hits(&graph(3, &[]), &HitsConfig::default()).expect("HITS failed");
+
+        assert!(result.converged);
+        assert!(
+            result
+                .hubs
+                .iter()
+                .chain(&result.authorities)
+                .all(|&x| x == 0.0)
+        );
+    }
+
+    #[test]
+    fn test_hits_reports_non_convergence() {
+        let config = HitsConfig { tolerance: 0.0, max_iterations: 3 };
+        let result = hits(&graph(3, &[(0, 1), (1, 2), (0, 2)]), &config).expect("HITS failed");
+
+        assert_eq!(result.iterations, 3);
+        assert!(!result.converged);
+    }
+
+    #[test]
+    fn test_betweenness_of_path() {
+        let adjacency = graph(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+        let raw = BetweennessConfig { normalized: false, ..BetweennessConfig::default() };
+        let scores = betweenness_centrality(&adjacency, &raw).expect("Betweenness failed");
+
+        let expected = [0.0, 3.0, 4.0, 3.0, 0.0];
+        for (score, want) in scores.iter().zip(expected) {
+            assert!((score - want).abs() < 1e-12, "{scores:?}");
+        }
+
+        let normalized = betweenness_centrality(&adjacency, &BetweennessConfig::default())
+            .expect("Betweenness failed");
+        assert!((normalized[2] - 4.0 / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_betweenness_splits_equal_paths() {
+        // Directed diamond 0 -> {1, 2} -> 3: each middle note carries half the 0-3 path
+        let config = BetweennessConfig { directed: true, normalized: false, ..Default::default() };
+        let scores = betweenness_centrality(&graph(4, &[(0, 1), (0, 2), (1, 3), (2, 3)]), &config)
+            .expect("Betweenness failed");
+
+        assert_eq!(scores, vec![0.0, 0.5, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_sampled_betweenness_finds_bridge_note() {
+        // Two 6-cliques joined through note 12
+        let mut edges = Vec::new();
+        for offset in [0, 6] {
+            for a in offset..offset + 6 {
+                for b in a + 1..offset + 6 {
+                    edges.push((a, b));
+                }
+            }
+        }
+        edges.extend([(5, 12), (12, 6)]);
+        let config = BetweennessConfig { samples: 6, seed: 3, ..BetweennessConfig::default() };
+        let scores =
+            betweenness_centrality(&graph(13, &edges), &config).expect("Betweenness failed");
+
+        let best = (0..13).max_by(|&a, &b| scores[a].total_cmp(&scores[b]));
+        assert_eq!(best, Some(12));
+    }
+
+    #[test]
+    fn test_articulation_points_and_bridges() {
+        // Triangle 0-1-2, a pendant 3 on 2, and a separate linked pair 4 <-> 5
+        let adjacency = graph(6, &[(0, 1), (1, 2), (2, 0), (3, 2), (4, 5), (5, 4)]);
+        let (points, bridges) =
+            articulation_points_and_bridges(&adjacency).expect("Cut detection failed");
+
+        assert_eq!(points, vec![2]);
+        assert_eq!(bridges, vec![[2, 3], [4, 5]]);
+    }
+
+    #[test]
+    fn test_path_interior_notes_are_articulation_points() {
+        let result =
+            critical_notes(&graph(4, &[(0, 1), (1, 2), (2, 3)]), &BetweennessConfig::default())
+                .expect("Analysis failed");
+
+        assert_eq!(result.articulation_points, vec![1, 2]);
+        assert_eq!(result.bridges, vec![[0, 1], [1, 2], [2, 3]]);
+        assert!(result.betweenness[1] > result.betweenness[0]);
+    }
+}
@@ -0,0 +1,378 @@
+//! Choosing the number of k-means clusters.
+//!
+//! Sweeps a range of cluster counts and scores each clustering with the
+//! silhouette coefficient, inertia and the Davies-Bouldin index. The count
+//! with the best silhouette is recommended; the inertia curve's knee is
+//! reported alongside it for elbow plots.
+
+use crate::rng::SeededRng;
+use crate::{
+    DistanceMetric, KMeansConfig, PluginError, compute_means, kmeans, non_negative_distance,
+};
+use serde::{Deserialize, Serialize};
+
+/// Settings for the cluster count sweep.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ClusterCountConfig {
+    /// Smallest cluster count to try (at least 2).
+    pub min_k: usize,
+    /// Largest cluster count to try; capped at one less than the number of vectors.
+    pub max_k: usize,
+    /// Maximum number of vectors used for the silhouette score, which is
+    /// quadratic in the number of vectors.
+    pub silhouette_sample: usize,
+    /// k-means settings used for every cluster count.
+    pub kmeans: KMeansConfig,
+}
+
+impl Default for ClusterCountConfig {
+    fn default() -> Self {
+        Self { min_k: 2, max_k: 10, silhouette_sample: 1000, kmeans: KMeansConfig::default() }
+    }
+}
+
+/// Quality scores of a clustering with a given cluster count.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClusterCountScore {
+    /// Number of clusters.
+    pub k: usize,
+    /// Mean silhouette coefficient in `[-1, 1]`; higher is better.
+    pub silhouette: f64,
+    /// k-means inertia; always falls as k grows.
+    pub inertia: f64,
+    /// Davies-Bouldin index; lower is better.
+    pub davies_bouldin: f64,
+}
+
+/// Result of a cluster count sweep.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClusterCountReport {
+    /// Scores for each cluster count tried, in increasing k.
+    pub scores: Vec<ClusterCountScore>,
+    /// Cluster count with the highest silhouette, preferring fewer clusters on ties.
+    pub recommended_k: usize,
+    /// Knee of the inertia curve, if at least three counts were tried.
+    pub elbow_k: Option<usize>,
+    /// Cluster assignment for each vector with the recommended count.
+    pub assignments: Vec<usize>,
+}
+
+/// Sweep cluster counts and recommend one.
+///
+/// # Arguments
+/// * `vectors` - Input vectors to cluster
+/// * `config` - Range of counts, silhouette sample size and k-means settings
+/// * `metric` - Distance metric used for clustering and scoring
+///
+/// # Returns
+/// Scores per cluster count, the recommendation and its assignments
+///
+/// # Errors
+/// Returns error if the range is invalid, there are too few vectors for it,
+/// or vectors have mismatched dimensions
+pub fn choose_cluster_count(
+    vectors: &[Vec<f64>],
+    config: &ClusterCountConfig,
+    metric: &dyn DistanceMetric,
+) -> Result<ClusterCountReport, PluginError> {
+    if config.min_k < 2 || config.min_k > config.max_k {
+        return Err(PluginError::ValidationError {
+            field: "minK".to_string(),
+            value: config.min_k.to_string(),
+            reason: "Cluster count range must start at 2 or more and not exceed maxK".to_string(),
+        });
+    }
+    // The silhouette needs at least one cluster with two members
+    let max_k = config.max_k.min(vectors.len().saturating_sub(1));
+    if max_k < config.min_k {
+        return Err(PluginError::InsufficientData {
+            required: config.min_k + 1,
+            provided: vectors.len(),
+        });
+    }
+
+    let sample = silhouette_sample(vectors.len(), config.silhouette_sample, config.kmeans.seed);
+    let sample_distances = pairwise_distances(vectors, &sample, metric)?;
+
+    let mut scores = Vec::with_capacity(max_k - config.min_k + 1);
+    let mut best: Option<(f64, Vec<usize>)> = None;
+    for k in config.min_k..=max_k {
+        let result = kmeans(vectors, k, &config.kmeans, metric)?;
+        let sampled: Vec<usize> = sample.iter().map(|&i| result.assignments[i]).collect();
+        let silhouette = silhouette_from_distances(&sample_distances, &sampled, k);
+        let score = ClusterCountScore {
+            k,
+            silhouette,
+            inertia: result.inertia,
+            davies_bouldin: davies_bouldin_index(vectors, &result.assignments, k, metric)?,
+        };
+        if best.as_ref().is_none_or(|(top, _)| silhouette > *top) {
+            best = Some((silhouette, result.assignments));
+        }
+        scores.push(score);
+    }
+
+    let Some((top, assignments)) = best else {
+        return Err(PluginError::InsufficientData { required: config.min_k + 1, provided: 0 });
+    };
+    let recommended_k = scores
+        .iter()
+        .find(|score| score.silhouette.total_cmp(&top).is_eq())
+        .map_or(config.min_k, |score| score.k);
+    let elbow_k = elbow(&scores);
+    Ok(ClusterCountReport { scores, recommended_k, elbow_k, assignments })
+}
+
+/// Mean silhouette coefficient of a clustering.
+///
+/// Points alone in their cluster score 0.
+///
+/// # Arguments
+/// * `vectors` - Clustered vectors
+/// * `assignments` - Cluster id per vector
+/// * `metric` - Distance metric
+///
+/// # Returns
+/// The mean silhouette in `[-1, 1]`, or 0 with fewer than two clusters
+///
+/// # Errors
+/// Returns error if vectors have mismatched dimensions
+pub fn silhouette_score(
+    vectors: &[Vec<f64>],
+    assignments: &[usize],
+    metric: &dyn DistanceMetric,
+) -> Result<f64, PluginError> {
+    let all: Vec<usize> = (0..vectors.len()).collect();
+    let distances = pairwise_distances(vectors, &all, metric)?;
+    let k = assignments.iter().max().map_or(0, |&max| max + 1);
+    Ok(silhouette_from_distances(&distances, assignments, k))
+}
+
+/// Davies-Bouldin index of a clustering: the mean, over clusters, of the worst
+/// ratio of combined spread to centroid separation.
+///
+/// # Arguments
+/// * `vectors` - Clustered vectors
+/// * `assignments` - Cluster id per vector, in `0..k`
+/// * `k` - Number of clusters
+/// * `metric` - Distance metric
+///
+/// # Returns
+/// The index, 0 for perfectly separated clusters; lower is better
+///
+/// # Errors
+/// Returns error if vectors have mismatched dimensions
+pub fn davies_bouldin_index(
+    vectors: &[Vec<f64>],
+    assignments: &[usize],
+    k: usize,
+    metric: &dyn DistanceMetric,
+) -> Result<f64, PluginError> {
+    let centroids = compute_means(vectors, assignments, k);
+    let mut spread = vec![0.0; k];
+    let mut counts = vec![0_u32; k];
+    for (vec, &cluster) in vectors.iter().zip(assignments) {
+        spread[cluster] += non_negative_distance(metric, vec, &centroids[cluster])?;
+        counts[cluster] += 1;
+    }
+    for (s, &count) in spread.iter_mut().zip(&counts) {
+        if count > 0 {
+            *s /= f64::from(count);
+        }
+    }
+
+    let present: Vec<usize> = (0..k).filter(|&c| counts[c] > 0).collect();
+    if present.len() < 2 {
+        return Ok(0.0);
+    }
+    let mut total = 0.0;
+    for &a in &present {
+        let mut worst: f64 = 0.0;
+        for &b in &present {
+            let separation = metric.distance(&centroids[a], &centroids[b])?;
+            // Coincident centroids are skipped rather than dividing by zero
+            if b != a && separation > 0.0 {
+                worst = worst.max((spread[a] + spread[b]) / separation);
+            }
+        }
+        total += worst;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let mean = total / present.len() as f64;
+    Ok(mean)
+}
+
+/// Indices of the vectors used for the silhouette, in increasing order.
+fn silhouette_sample(n: usize, max_size: usize, seed: u64) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..n).collect();
+    if n > max_size.max(2) {
+        SeededRng::new(seed).shuffle(&mut indices);
+        indices.truncate(max_size.max(2));
+        indices.sort_unstable();
+    }
+    indices
+}
+
+/// Dense distance matrix between the selected vectors.
+fn pairwise_distances(
+    vectors: &[Vec<f64>],
+    selected: &[usize],
+    metric: &dyn DistanceMetric,
+) -> Result<Vec<Vec<f64>>, PluginError> {
+    let m = selected.len();
+    let mut distances = vec![vec![0.0; m]; m];
+    for a in 0..m {
+        for b in a + 1..m {
+            let dist = non_negative_distance(metric, &vectors[selected[a]], &vectors[selected[b]])?;
+            distances[a][b] = dist;
+            distances[b][a] = dist;
+        }
+    }
+    Ok(distances)
+}
+
+/// Mean silhouette from a precomputed distance matrix.
+fn silhouette_from_distances(distances: &[Vec<f64>], assignments: &[usize], k: usize) -> f64 {
+    if distances.is_empty() {
+        return 0.0;
+    }
+    let mut sizes = vec![0_u32; k];
+    for &cluster in assignments {
+        sizes[cluster] += 1;
+    }
+
+    let mut total = 0.0;
+    let mut sums = vec![0.0; k];
+    for (row, &own) in distances.iter().zip(assignments) {
+        if sizes[own] < 2 {
+            continue;
+        }
+        sums.fill(0.0);
+        for (&dist, &cluster) in row.iter().zip(assignments) {
+            sums[cluster] += dist;
+        }
+        let cohesion = sums[own] / f64::from(sizes[own] - 1);
+        let separation = (0..k)
+            .filter(|&c| c != own && sizes[c] > 0)
+            .map(|c| sums[c] / f64::from(sizes[c]))
+            .fold(f64::INFINITY, f64::min);
+        let scale = cohesion.max(separation);
+        if separation.is_finite() && scale > 0.0 {
+            total += (separation - cohesion) / scale;
+        }
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let mean = total / distances.len() as f64;
+    mean
+}
+
+/// Knee of the inertia curve: the count farthest below the straight line
+/// joining the first and last points, with both axes scaled to `[0, 1]`.
+fn elbow(scores: &[ClusterCountScore]) -> Option<usize> {
+    let (first, last) = (scores.first()?, scores.last()?);
+    let drop = first.inertia - last.inertia;
+    if scores.len() < 3 || drop <= 0.0 {
+        return None;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let span = (last.k - first.k) as f64;
+    scores
+        .iter()
+        .map(|score| {
+            #[allow(clippy::cast_precision_loss)]
+            let x = (score.k - first.k) as f64 / span;
+            let y = (first.inertia - score.inertia) / drop;
+            (y - x, score.k)
+        })
+        .filter(|&(gap, _)| gap > 0.0)
+        .max_by(|a, b| a.0.total_cmp(&b.0).then(b.1.cmp(&a.1)))
+        .map(|(_, k)| k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Euclidean;
+
+    /// `blobs` tight clusters of eight points spread along the diagonal.
+    fn blobs(blobs: usize) -> Vec<Vec<f64>> {
+        let mut rng = SeededRng::new(3);
+        let mut vectors = Vec::new();
+        for b in 0..blobs {
+            #[allow(clippy::cast_precision_loss)]
+            let center = 10.0 * b as f64;
+            for _ in 0..8 {
+                vectors.push(vec![
+                    0.2f64.mul_add(rng.next_normal(), center),
+                    0.2f64.mul_add(rng.next_normal(), -center),
+                ]);
+            }
+        }
+        vectors
+    }
+
+    #[test]
+    fn test_recommends_true_cluster_count() {
+        for true_k in [2, 3, 5] {
+            let report =
+                choose_cluster_count(&blobs(true_k), &ClusterCountConfig::default(), &Euclidean)
+                    .expect("Sweep failed");
+
+            assert_eq!(report.recommended_k, true_k);
+            assert_eq!(report.scores.first().map(|s| s.k), Some(2));
+            let best = &report.scores[true_k - 2];
+            assert!(best.silhouette > 0.9, "{}", best.silhouette);
+            assert!(
+                report
+                    .scores
+                    .iter()
+                    .all(|s| s.davies_bouldin >= best.davies_bouldin),
+                "k = {true_k}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_elbow_finds_knee() {
+        let report = choose_cluster_count(&blobs(4), &ClusterCountConfig::default(), &Euclidean)
+            .expect("Sweep failed");
+        assert_eq!(report.elbow_k, Some(4));
+    }
+
+    #[test]
+    fn test_silhouette_score_matches_hand_computation() {
+        // Clusters {0, 1} and {3}: point 0 has a = 1, b = 3; point 1 has a = 1, b = 2
+        let vectors = vec![vec![0.0], vec![1.0], vec![3.0]];
+        let score = silhouette_score(&vectors, &[0, 0, 1], &Euclidean).expect("Score failed");
+
+        let expected = (2.0 / 3.0 + 1.0 / 2.0) / 3.0;
+        assert!((score - expected).abs() < 1e-12, "{score}");
+    }
+
+    #[test]
+    fn test_davies_bouldin_matches_hand_computation() {
+        // Spreads 1 and 0.5, centroids 10 apart
+        let vectors = vec![vec![-1.0], vec![1.0], vec![9.5], vec![10.5]];
+        let index =
+            davies_bouldin_index(&vectors, &[0, 0, 1, 1], 2, &Euclidean).expect("Index failed");
+        assert!((index - 0.15).abs() < 1e-12, "{index}");
+    }
+
+    #[test]
+    fn test_range_is_capped_by_vector_count() {
+        let vectors = vec![vec![0.0], vec![1.0], vec![5.0], vec![6.0]];
+        let report = choose_cluster_count(&vectors, &ClusterCountConfig::default(), &Euclidean)
+            .expect("Sweep failed");
+        assert_eq!(report.scores.iter().map(|s| s.k).collect::<Vec<_>>(), vec![2, 3]);
+
+        assert!(
+            choose_cluster_count(&vectors[..2], &ClusterCountConfig::default(), &Euclidean)
+                .is_err()
+        );
+        let inverted = ClusterCountConfig { min_k: 5, max_k: 3, ..ClusterCountConfig::default() };
+        assert!(choose_cluster_count(&vectors, &inverted, &Euclidean).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+//! Naming clusters after their most distinctive terms.
+//!
+//! Uses class-based TF-IDF (c-TF-IDF, as in `BERTopic`): all notes in a cluster
+//! are treated as one document, and a term scores highly when it is frequent in
+//! that cluster but rare across the vault as a whole.
+
+use crate::PluginError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Common English words and web fragments that make poor cluster names, separated by spaces.
+const ENGLISH_STOPWORDS: &str = "\
+    a about above after again against all also am an and any are as at be because been before \
+    being below between both but by can could did do does doing down during each even few for \
+    from further get got had has have having he her here hers herself him himself his how i if \
+    in into is it its itself just let like made make many may me might more most much must my \
+    myself no nor not now of off on once one only or other our ours ourselves out over own \
+    same she should so some such than that the their theirs them themselves then there these \
+    they this those through to too under until up us use used using very was we were what when \
+    where which while who whom why will with would you your yours yourself yourselves com http \
+    https md www";
+
+/// Settings for cluster labelling.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ClusterLabelConfig {
+    /// Keywords to report per cluster.
+    pub top_keywords: usize,
+    /// Leading keywords joined to form the label.
+    pub label_terms: usize,
+    /// Minimum number of notes a term must appear in to be considered.
+    pub min_document_frequency: usize,
+    /// Shortest term to keep, in characters.
+    pub min_term_length: usize,
+    /// Whether to drop the built-in English stopwords.
+    pub default_stopwords: bool,
+    /// Extra terms to drop, matched case-insensitively.
+    pub stopwords: Vec<String>,
+}
+
+impl Default for ClusterLabelConfig {
+    fn default() -> Self {
+        Self {
+            top_keywords: 8,
+            label_terms: 3,
+            min_document_frequency: 2,
+            min_term_length: 3,
+            default_stopwords: true,
+            stopwords: Vec::new(),
+        }
+    }
+}
+
+/// A term and its c-TF-IDF score within a cluster.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClusterKeyword {
+    /// Lowercased term.
+    pub term: String,
+    /// c-TF-IDF weight; higher is more distinctive.
+    pub score: f64,
+}
+
+/// Name and keywords of one cluster.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClusterLabel {
+    /// Cluster id from the assignments.
+    pub cluster: usize,
+    /// Number of notes in the cluster.
+    pub size: usize,
+    /// Leading keywords joined with `", "`, or `"Cluster <id>"` without keywords.
+    pub label: String,
+    /// Most distinctive terms, best first.
+    pub keywords: Vec<ClusterKeyword>,
+}
+
+/// Label clusters with their most distinctive terms.
+///
+/// # Arguments
+/// * `assignments` - Cluster id per note; negative ids such as [`NOISE_LABEL`](crate::NOISE_LABEL)
+///   are skipped
+/// * `texts` - Title or text of each note
+/// * `config` - Keyword counts, stopwords and document frequency threshold
+///
+/// # Returns
+/// One label per cluster, in increasing cluster id
+///
+/// # Errors
+/// Returns error if `texts` and `assignments` differ in length
+pub fn label_clusters(
+    assignments: &[i64],
+    texts: &[String],
+    config: &ClusterLabelConfig,
+) -> Result<Vec<ClusterLabel>, PluginError> {
+    if texts.len() != assignments.len() {
+        return Err(PluginError::ValidationError {
+            field: "texts".to_string(),
+            value: texts.len().to_string(),
+            reason: format!("Expected one text per assignment ({})", assignments.len()),
+        });
+    }
+
+    let mut stopwords: HashSet<String> = config
+        .stopwords
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect();
+    if config.default_stopwords {
+        stopwords.extend(
+            ENGLISH_STOPWORDS
+                .split_whitespace()
+                .map(ToString::to_string),
+        );
+    }
+    let documents: Vec<Vec<String>> = texts
+        .iter()
+        .map(|text| tokenize(text, config.min_term_length, &stopwords))
+        .collect();
+
+    let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+    for tokens in &documents {
+        let distinct: HashSet<&str> = tokens.iter().map(String::as_str).collect();
+        for term in distinct {
+            *document_frequency.entry(term).or_default() += 1;
+        }
+    }
+
+    // Term counts per cluster, over terms that pass the document frequency threshold
+    let mut clusters: BTreeMap<usize, (usize, HashMap<&str, usize>)> = BTreeMap::new();
+    for (tokens, &assignment) in documents.iter().zip(assignments) {
+        let Ok(cluster) = usize::try_from(assignment) else {
+            continue;
+        };
+        let (size, counts) = clusters.entry(cluster).or_default();
+        *size += 1;
+        for term in tokens {
+            if document_frequency[term.as_str()] >= config.min_document_frequency {
+                *counts.entry(term.as_str()).or_default() += 1;
+            }
+        }
+    }
+
+    let mut total_frequency: HashMap<&str, usize> = HashMap::new();
+    let mut total_words = 0;
+    for (_, counts) in clusters.values() {
+        for (&term, &count) in counts {
+            *total_frequency.entry(term).or_default() += count;
+            total_words += count;
+        }
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let average_words = total_words as f64 / clusters.len().max(1) as f64;
+
+    Ok(clusters
+        .into_iter()
+        .map(|(cluster, (size, counts))| {
+            let cluster_words: usize = counts.values().sum();
+            let mut keywords: Vec<ClusterKeyword> = counts
+                .into_iter()
+                .map(|(term, count)| {
+                    #[allow(clippy::cast_precision_loss)]
+                    let tf = count as f64 / cluster_words as f64;
+                    #[allow(clippy::cast_precision_loss)]
+                    let idf = (average_words / total_frequency[term] as f64).ln_1p();
+                    ClusterKeyword { term: term.to_string(), score: tf * idf }
+                })
+                .collect();
+            keywords.sort_by(|a, b| {
+                b.score
+                    .total_cmp(&a.score)
+                    .then_with(|| a.term.cmp(&b.term))
+            });
+            keywords.truncate(config.top_keywords);
+
+            let label = if keywords.is_empty() {
+                format!("Cluster {cluster}")
+            } else {
+                keywords
+                    .iter()
+                    .take(config.label_terms.max(1))
+                    .map(|keyword| keyword.term.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            ClusterLabel { cluster, size, label, keywords }
+        })
+        .collect())
+}
+
+/// Lowercase alphanumeric terms of a text, without stopwords, short terms or numbers.
+fn tokenize(text: &str, min_length: usize, stopwords: &HashSet<String>) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= min_length)
+        .map(str::to_lowercase)
+        .filter(|word| !word.chars().all(char::is_numeric) && !stopwords.contains(word))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(items: &[&str]) -> Vec<String> {
+        items.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_distinctive_terms_win() {
+        let notes = texts(&[
+            "Rust ownership and the borrow checker",
+            "Rust lifetimes and the borrow checker explained",
+            "Sourdough bread starter notes",
+            "Bread baking with a sourdough starter",
+        ]);
+        let labels = label_clusters(&[0, 0, 1, 1], &notes, &ClusterLabelConfig::default())
+            .expect("Labelling failed");
+
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[0].size, 2);
+        let rust_terms: Vec<&str> = labels[0].keywords.iter().map(|k| k.term.as_str()).collect();
+        assert_eq!(rust_terms, vec!["borrow", "checker", "rust"]);
+        assert_eq!(labels[1].label, "bread, sourdough, starter");
+    }
+
+    #[test]
+    fn test_shared_terms_rank_below_distinctive_ones() {
+        let notes = texts(&[
+            "project planning roadmap",
+            "project planning milestones",
+            "project budget roadmap",
+            "project budget milestones",
+        ]);
+        let config =
+            ClusterLabelConfig { min_document_frequency: 1, ..ClusterLabelConfig::default() };
+        let labels = label_clusters(&[0, 0, 1, 1], &notes, &config).expect("Labelling failed");
+
+        assert_eq!(labels[0].keywords[0].term, "planning");
+        assert_eq!(labels[1].keywords[0].term, "budget");
+        let last = labels[0].keywords.iter().position(|k| k.term == "project");
+        assert!(last > Some(0));
+    }
+
+    #[test]
+    fn test_noise_and_stopwords_are_skipped() {
+        let notes = texts(&["alpha beta", "alpha beta", "gamma gamma"]);
+        let config = ClusterLabelConfig {
+            min_document_frequency: 1,
+            stopwords: vec!["Beta".to_string()],
+            ..ClusterLabelConfig::default()
+        };
+        let labels = label_clusters(&[3, 3, -1], &notes, &config).expect("Labelling failed");
+
+        assert_eq!(labels.len(), 1);
+        assert_eq!((labels[0].cluster, labels[0].label.as_str()), (3, "alpha"));
+    }
+
+    #[test]
+    fn test_cluster_without_terms_gets_a_fallback_label() {
+        let labels =
+            label_clusters(&[0, 1], &texts(&["unique", "words"]), &ClusterLabelConfig::default())
+                .expect("Labelling failed");
+
+        assert_eq!(labels[0].label, "Cluster 0");
+        assert!(labels[1].keywords.is_empty());
+        assert!(label_clusters(&[0], &texts(&["a", "b"]), &ClusterLabelConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let stopwords = HashSet::from(["the".to_string()]);
+        assert_eq!(
+            tokenize("The [[Café]] visit: 2024-05-01, ok?", 3, &stopwords),
+            vec!["café", "visit"]
+        );
+    }
+}
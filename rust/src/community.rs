@@ -0,0 +1,507 @@
+//! Modularity-based community detection on the note link graph.
+//!
+//! Links are treated as undirected (A + Aᵀ) and weighted by their count.
+//! Louvain alternates greedy local moves with aggregation of communities into
+//! single nodes; Leiden adds a refinement step before aggregation that only
+//! merges well-connected nodes, which guarantees connected communities.
+
+use crate::PluginError;
+use crate::rng::SeededRng;
+use serde::{Deserialize, Serialize};
+use sprs::CsMat;
+use std::collections::BTreeMap;
+
+/// Smallest modularity gain treated as an improvement.
+const GAIN_EPSILON: f64 = 1e-12;
+
+/// Community detection algorithm.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CommunityAlgorithm {
+    /// Local moving and aggregation.
+    Louvain,
+    /// Louvain with the Leiden refinement step.
+    Leiden,
+}
+
+impl CommunityAlgorithm {
+    /// Parse an algorithm name (`"louvain"` or `"leiden"`).
+    ///
+    /// # Errors
+    /// Returns error if the name is unknown
+    pub fn from_name(name: &str) -> Result<Self, PluginError> {
+        match name.to_lowercase().as_str() {
+            "louvain" => Ok(Self::Louvain),
+            "leiden" => Ok(Self::Leiden),
+            _ => Err(PluginError::ValidationError {
+                field: "algorithm".to_string(),
+                value: name.to_string(),
+                reason: "Unknown community detection algorithm".to_string(),
+            }),
+        }
+    }
+}
+
+/// Settings for community detection.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommunityConfig {
+    /// Resolution γ; higher values give more, smaller communities.
+    pub resolution: f64,
+    /// Which algorithm to run.
+    pub algorithm: CommunityAlgorithm,
+    /// Seed for the order in which nodes are visited.
+    pub seed: u64,
+}
+
+impl Default for CommunityConfig {
+    fn default() -> Self {
+        Self { resolution: 1.0, algorithm: CommunityAlgorithm::Leiden, seed: 42 }
+    }
+}
+
+/// Result of community detection.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommunityResult {
+    /// Community id per note, numbered from 0 in order of first appearance.
+    pub assignments: Vec<usize>,
+    /// Number of distinct communities.
+    pub community_count: usize,
+    /// Modularity of the partition at the configured resolution.
+    pub modularity: f64,
+    /// Number of aggregation levels performed.
+    pub levels: usize,
+}
+
+/// Detect communities in a link graph by maximising modularity.
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix
+/// * `config` - Resolution and algorithm
+///
+/// # Returns
+/// One community id per note plus the modularity of the partition
+///
+/// # Errors
+/// Returns error if the matrix is not square or the resolution is not positive
+pub fn detect_communities(
+    adjacency: &CsMat<f64>,
+    config: &CommunityConfig,
+) -> Result<CommunityResult, PluginError> {
+    validate(adjacency, config.resolution)?;
+    let original = WeightedGraph::from_adjacency(adjacency);
+    let n = original.len();
+    let resolution = config.resolution;
+
+    // membership maps each note to its node in the current (aggregated) graph
+    let mut membership: Vec<usize> = (0..n).collect();
+    let mut graph = original.clone();
+    let mut partition: Vec<usize> = (0..n).collect();
+    let mut levels = 0;
+    let mut rng = SeededRng::new(config.seed);
+
+    if original.total_weight > 0.0 {
+        loop {
+            move_nodes(&graph, &mut partition, resolution, &mut rng);
+            let community_count = renumber(&mut partition);
+            if community_count == graph.len() {
+                break;
+            }
+
+            let mut refined = match config.algorithm {
+                CommunityAlgorithm::Louvain => partition.clone(),
+                CommunityAlgorithm::Leiden => refine(&graph, &partition, resolution),
+            };
+            let refined_count = renumber(&mut refined);
+            if refined_count == graph.len() {
+                break;
+            }
+
+            // Aggregate refined communities; each starts in its parent community
+            let mut next_partition = vec![0; refined_count];
+            for (node, &r) in refined.iter().enumerate() {
+                next_partition[r] = partition[node];
+            }
+            for node in &mut membership {
+                *node = refined[*node];
+            }
+            graph = graph.aggregate(&refined, refined_count);
+            partition = next_partition;
+            levels += 1;
+        }
+    }
+
+    let mut assignments: Vec<usize> = membership.iter().map(|&node| partition[node]).collect();
+    let community_count = renumber(&mut assignments);
+    let modularity = original.modularity(&assignments, resolution);
+
+    Ok(CommunityResult { assignments, community_count, modularity, levels })
+}
+
+/// Compute the modularity of a partition of a link graph.
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix
+/// * `assignments` - Community id per note
+/// * `resolution` - Resolution γ (1.0 for standard modularity)
+///
+/// # Returns
+/// Modularity Q, in [-1/2, 1] at resolution 1; 0 for a graph without links
+///
+/// # Errors
+/// Returns error if the matrix is not square, the resolution is not positive,
+/// or the assignment count differs from the note count
+pub fn modularity(
+    adjacency: &CsMat<f64>,
+    assignments: &[usize],
+    resolution: f64,
+) -> Result<f64, PluginError> {
+    validate(adjacency, resolution)?;
+    let graph = WeightedGraph::from_adjacency(adjacency);
+    if assignments.len() != graph.len() {
+        return Err(PluginError::ValidationError {
+            field: "assignments".to_string(),
+            value: assignments.len().to_string(),
+            reason: format!("Expected one community per note ({})", graph.len()),
+        });
+    }
+    Ok(graph.modularity(assignments, resolution))
+}
+
+/// Check the matrix shape and resolution.
+fn validate(adjacency: &CsMat<f64>, resolution: f64) -> Result<(), PluginError> {
+    let (rows, cols) = adjacency.shape();
+    if rows != cols {
+        return Err(PluginError::ValidationError {
+            field: "adjacency".to_string(),
+            value: format!("{rows}x{cols}"),
+            reason: "Adjacency matrix must be square".to_string(),
+        });
+    }
+    if !(resolution.is_finite() && resolution > 0.0) {
+        return Err(PluginError::ValidationError {
+            field: "resolution".to_string(),
+            value: resolution.to_string(),
+            reason: "Resolution must be a positive number".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Symmetric weighted graph W with self-loops kept on the diagonal.
+#[derive(Debug, Clone)]
+struct WeightedGraph {
+    /// Neighbours and edge weights per node, including self-loops.
+    neighbors: Vec<Vec<(usize, f64)>>,
+    /// Weighted degree of each node (row sums of W).
+    degrees: Vec<f64>,
+    /// Total weight 2m (sum of all degrees).
+    total_weight: f64,
+}
+
+impl WeightedGraph {
+    /// Build W = A + Aᵀ from a directed adjacency matrix.
+    fn from_adjacency(adjacency: &CsMat<f64>) -> Self {
+        let n = adjacency.rows();
+        let mut rows: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); n];
+        for (i, row) in adjacency.outer_iterator().enumerate() {
+            for (j, &w) in row.iter() {
+                let (from, to) = if adjacency.is_csr() { (i, j) } else { (j, i) };
+                *rows[from].entry(to).or_insert(0.0) += w;
+                *rows[to].entry(from).or_insert(0.0) += w;
+            }
+        }
+        Self::from_rows(rows)
+    }
+
+    /// Build from per-node weight maps.
+    fn from_rows(rows: Vec<BTreeMap<usize, f64>>) -> Self {
+        let neighbors: Vec<Vec<(usize, f64)>> = rows
+            .into_iter()
+            .map(|row| row.into_iter().filter(|&(_, w)| w != 0.0).collect())
+            .collect();
+        let degrees: Vec<f64> = neighbors
+            .iter()
+            .map(|row| row.iter().map(|&(_, w)| w).sum())
+            .collect();
+        let total_weight = degrees.iter().sum();
+        Self { neighbors, degrees, total_weight }
+    }
+
+    /// Number of nodes.
+    const fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    /// Collapse each group of nodes into a single node.
+    fn aggregate(&self, groups: &[usize], group_count: usize) -> Self {
+        let mut rows: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); group_count];
+        for (i, row) in self.neighbors.iter().enumerate() {
+            for &(j, w) in row {
+                *rows[groups[i]].entry(groups[j]).or_insert(0.0) += w;
+            }
+        }
+        Self::from_rows(rows)
+    }
+
+    /// Modularity: the sum over communities of `in / 2m − γ (tot / 2m)²`.
+    fn modularity(&self, assignments: &[usize], resolution: f64) -> f64 {
+        if self.total_weight <= 0.0 {
+            return 0.0;
+        }
+        let count = assignments.iter().max().map_or(0, |&c| c + 1);
+        let mut internal = vec![0.0; count];
+        let mut totals = vec![0.0; count];
+        for (i, row) in self.neighbors.iter().enumerate() {
+            totals[assignments[i]] += self.degrees[i];
+            internal[assignments[i]] += row
+                .iter()
+                .filter(|&&(j, _)| assignments[j] == assignments[i])
+                .map(|&(_, w)| w)
+                .sum::<f64>();
+        }
+        internal
+            .iter()
+            .zip(&totals)
+            .map(|(&inside, &total)| {
+                let share = total / self.total_weight;
+                (resolution * share).mul_add(-share, inside / self.total_weight)
+            })
+            .sum()
+    }
+}
+
+/// Renumber community ids to 0.. in order of first appearance.
+///
+/// Returns the number of distinct communities.
+fn renumber(assignments: &mut [usize]) -> usize {
+    let mut mapping: Vec<Option<usize>> = vec![None; assignments.len()];
+    let mut next = 0;
+    for community in assignments.iter_mut() {
+        *community = *mapping[*community].get_or_insert_with(|| {
+            next += 1;
+            next - 1
+        });
+    }
+    next
+}
+
+/// Greedily move nodes between communities until no move improves modularity.
+fn move_nodes(
+    graph: &WeightedGraph,
+    partition: &mut [usize],
+    resolution: f64,
+    rng: &mut SeededRng,
+) {
+    let n = graph.len();
+    let mut totals = vec![0.0; n];
+    for (node, &community) in partition.iter().enumerate() {
+        totals[community] += graph.degrees[node];
+    }
+
+    let mut links_to = vec![0.0; n];
+    let mut touched: Vec<usize> = Vec::new();
+    let mut order: Vec<usize> = (0..n).collect();
+    rng.shuffle(&mut order);
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for &node in &order {
+            let current = partition[node];
+            let degree = graph.degrees[node];
+            totals[current] -= degree;
+
+            touched.push(current);
+            for &(neighbor, w) in &graph.neighbors[node] {
+                if neighbor != node {
+                    let community = partition[neighbor];
+                    if links_to[community] == 0.0 {
+                        touched.push(community);
+                    }
+                    links_to[community] += w;
+                }
+            }
+
+            // Gain of joining community c: k_i,c − γ Σ_c k_i / 2m
+            let scale = resolution * degree / graph.total_weight;
+            let gain = |community: usize| scale.mul_add(-totals[community], links_to[community]);
+            let mut best = current;
+            let mut best_gain = gain(current);
+            for &community in &touched {
+                let candidate = gain(community);
+                if candidate > best_gain + GAIN_EPSILON {
+                    best = community;
+                    best_gain = candidate;
+                }
+            }
+
+            for &community in &touched {
+                links_to[community] = 0.0;
+            }
+            touched.clear();
+
+            totals[best] += degree;
+            if best != current {
+                partition[node] = best;
+                improved = true;
+            }
+        }
+    }
+}
+
+/// Leiden refinement: split each community into well-connected subcommunities.
+///
+/// Every node starts alone. A singleton node that is well connected to its
+/// community merges into the well-connected subcommunity of the same
+/// community that gives the largest positive modularity gain.
+fn refine(graph: &WeightedGraph, partition: &[usize], resolution: f64) -> Vec<usize> {
+    let n = graph.len();
+    let scale = resolution / graph.total_weight;
+    let mut community_totals = vec![0.0; n];
+    for (node, &community) in partition.iter().enumerate() {
+        community_totals[community] += graph.degrees[node];
+    }
+
+    let mut refined: Vec<usize> = (0..n).collect();
+    let mut totals = graph.degrees.clone();
+    let mut sizes = vec![1_usize; n];
+    // Weight from each subcommunity to the rest of its parent community
+    let mut external: Vec<f64> = (0..n)
+        .map(|node| {
+            graph.neighbors[node]
+                .iter()
+                .filter(|&&(j, _)| j != node && partition[j] == partition[node])
+                .map(|&(_, w)| w)
+                .sum()
+        })
+        .collect();
+
+    let mut links_to = vec![0.0; n];
+    let mut touched: Vec<usize> = Vec::new();
+    for node in 0..n {
+        let own = refined[node];
+        if sizes[own] > 1 {
+            continue;
+        }
+        let degree = graph.degrees[node];
+        let parent_total = community_totals[partition[node]];
+        if external[own] < scale * degree * (parent_total - degree) {
+            continue;
+        }
+
+        for &(neighbor, w) in &graph.neighbors[node] {
+            if neighbor != node && partition[neighbor] == partition[node] {
+                let sub = refined[neighbor];
+                if links_to[sub] == 0.0 {
+                    touched.push(sub);
+                }
+                links_to[sub] += w;
+            }
+        }
+
+        let mut best = None;
+        let mut best_gain = GAIN_EPSILON;
+        for &sub in &touched {
+            let well_connected =
+                external[sub] >= scale * totals[sub] * (parent_total - totals[sub]);
+            let gain = (scale * degree).mul_add(-totals[sub], links_to[sub]);
+            if well_connected && gain > best_gain {
+                best = Some(sub);
+                best_gain = gain;
+            }
+        }
+
+        if let Some(sub) = best {
+            refined[node] = sub;
+            totals[sub] += degree;
+            sizes[sub] += 1;
+            sizes[own] = 0;
+            external[sub] = 2.0f64.mul_add(-links_to[sub], external[sub] + external[own]);
+        }
+
+        for &sub in &touched {
+            links_to[sub] = 0.0;
+        }
+        touched.clear();
+    }
+    refined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::graph;
+
+    /// Two 4-cliques joined by a single bridge (3, 4).
+    fn two_cliques() -> CsMat<f64> {
+        let mut edges = Vec::new();
+        for offset in [0, 4] {
+            for i in 0..4 {
+                for j in (i + 1)..4 {
+                    edges.push((offset + i, offset + j));
+                }
+            }
+        }
+        edges.push((3, 4));
+        graph(8, &edges)
+    }
+
+    #[test]
+    fn test_finds_two_cliques() {
+        for algorithm in [CommunityAlgorithm::Louvain, CommunityAlgorithm::Leiden] {
+            let config = CommunityConfig { algorithm, ..CommunityConfig::default() };
+            let result = detect_communities(&two_cliques(), &config).expect("Detection failed");
+
+            assert_eq!(result.assignments, vec![0, 0, 0, 0, 1, 1, 1, 1]);
+            assert_eq!(result.community_count, 2);
+            assert!((result.modularity - 11.0 / 26.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_modularity_of_two_cliques() {
+        // 13 edges: each community has 6 internal edges and total degree 13
+        let q =
+            modularity(&two_cliques(), &[0, 0, 0, 0, 1, 1, 1, 1], 1.0).expect("Modularity failed");
+        // Q = 2 × (12/26 − (13/26)²) = 11/26
+        assert!((q - 11.0 / 26.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_resolution_controls_granularity() {
+        let coarse = CommunityConfig { resolution: 0.01, ..CommunityConfig::default() };
+        let fine = CommunityConfig { resolution: 5.0, ..CommunityConfig::default() };
+
+        let few = detect_communities(&two_cliques(), &coarse).expect("Detection failed");
+        let many = detect_communities(&two_cliques(), &fine).expect("Detection failed");
+
+        assert_eq!(few.community_count, 1);
+        assert!(many.community_count > 2);
+    }
+
+    #[test]
+    fn test_isolated_notes_get_own_community() {
+        let result = detect_communities(&graph(4, &[(0, 1)]), &CommunityConfig::default())
+            .expect("Detection failed");
+
+        assert_eq!(result.assignments, vec![0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_graph_without_links() {
+        let result = detect_communities(&graph(3, &[]), &CommunityConfig::default())
+            .expect("Detection failed");
+
+        assert_eq!(result.assignments, vec![0, 1, 2]);
+        assert!(result.modularity.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_resolution() {
+        let config = CommunityConfig { resolution: 0.0, ..CommunityConfig::default() };
+        let result = detect_communities(&two_cliques(), &config);
+
+        assert!(matches!(result, Err(PluginError::ValidationError { .. })));
+    }
+}
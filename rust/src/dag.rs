@@ -0,0 +1,395 @@
+//! Cycle detection and topological ordering of the note link graph.
+//!
+//! Structured notes such as course outlines or dependency chains are meant to
+//! form a directed acyclic graph. This module reports whether they do, which
+//! notes take part in cycles, and an order that respects every link when one
+//! exists.
+
+use crate::{AdjacencyMatrixBuilder, NoteLink, PluginError, strongly_connected_components};
+use serde::{Deserialize, Serialize};
+use sprs::CsMat;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Settings for cycle enumeration.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DagConfig {
+    /// Longest cycle to enumerate, in links.
+    pub max_cycle_length: usize,
+    /// Maximum number of cycles to report.
+    pub max_cycles: usize,
+}
+
+impl Default for DagConfig {
+    fn default() -> Self {
+        Self { max_cycle_length: 6, max_cycles: 1000 }
+    }
+}
+
+/// Acyclicity report for a link graph, with notes given by path.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DagReport {
+    /// Whether the graph has no cycles (including self-links).
+    pub is_dag: bool,
+    /// Strongly connected components that contain a cycle, largest first.
+    pub cyclic_components: Vec<Vec<String>>,
+    /// Cycles up to the configured length, shortest first. Each cycle starts at
+    /// its lowest-index note and follows link direction; the closing link back
+    /// to the first note is implied.
+    pub cycles: Vec<Vec<String>>,
+    /// Whether more cycles exist within the length limit than were reported.
+    pub cycles_truncated: bool,
+    /// Notes ordered so that every link points forward, if the graph is acyclic.
+    pub topological_order: Option<Vec<String>>,
+}
+
+/// Analyse the cycle structure of a vault's link graph.
+///
+/// # Arguments
+/// * `builder` - Builder holding the vault's notes; its graph options apply
+/// * `links` - List of note links
+/// * `config` - Cycle enumeration settings
+///
+/// # Returns
+/// The report, with every note given by its path
+///
+/// # Errors
+/// Returns error if link indices are out of bounds
+pub fn analyze_dag(
+    builder: &AdjacencyMatrixBuilder,
+    links: Vec<NoteLink>,
+    config: &DagConfig,
+) -> Result<DagReport, PluginError> {
+    let adjacency = builder.build(links)?;
+    let paths = builder.note_paths();
+    let to_paths = |nodes: &[usize]| -> Vec<String> {
+        nodes.iter().map(|&node| paths[node].clone()).collect()
+    };
+
+    let successors = successor_lists(&adjacency);
+    let labels = strongly_connected_components(&adjacency);
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    for (node, &label) in labels.iter().enumerate() {
+        if label >= components.len() {
+            components.resize(label + 1, Vec::new());
+        }
+        components[label].push(node);
+    }
+    let cyclic_components: Vec<Vec<String>> = components
+        .iter()
+        .filter(|members| members.len() > 1 || successors[members[0]].contains(&members[0]))
+        .map(|members| to_paths(members))
+        .collect();
+
+    let (cycles, cycles_truncated) =
+        enumerate_cycles(&successors, config.max_cycle_length, config.max_cycles);
+    let topological_order = topological_order(&adjacency).map(|order| to_paths(&order));
+
+    Ok(DagReport {
+        is_dag: cyclic_components.is_empty(),
+        cyclic_components,
+        cycles: cycles.iter().map(|cycle| to_paths(cycle)).collect(),
+        cycles_truncated,
+        topological_order,
+    })
+}
+
+/// Order the nodes so that every edge points from an earlier to a later node.
+///
+/// Kahn's algorithm, taking the lowest available index first so the order is
+/// deterministic.
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix
+///
+/// # Returns
+/// The order, or `None` if the graph has a cycle or self-loop
+#[must_use]
+pub fn topological_order(adjacency: &CsMat<f64>) -> Option<Vec<usize>> {
+    let successors = successor_lists(adjacency);
+    let mut in_degree = vec![0_usize; successors.len()];
+    for &next in successors.iter().flatten() {
+        in_degree[next] += 1;
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..successors.len())
+        .filter(|&node| in_degree[node] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(successors.len());
+    while let Some(Reverse(node)) = ready.pop() {
+        order.push(node);
+        for &next in &successors[node] {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                ready.push(Reverse(next));
+            }
+        }
+    }
+    (order.len() == successors.len()).then_some(order)
+}
+
+/// Enumerate the elementary cycles of a directed graph up to a given length.
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix
+/// * `max_length` - Longest cycle to enumerate, in edges
+/// * `max_cycles` - Maximum number of cycles to return
+///
+/// # Returns
+/// Cycles as node sequences starting at their lowest index, shortest first
+/// and then by first node
+#[must_use]
+pub fn find_cycles(
+    adjacency: &CsMat<f64>,
+    max_length: usize,
+    max_cycles: usize,
+) -> Vec<Vec<usize>> {
+    enumerate_cycles(&successor_lists(adjacency), max_length, max_cycles).0
+}
+
+/// Distinct successors of each node, in increasing order.
+fn successor_lists(adjacency: &CsMat<f64>) -> Vec<Vec<usize>> {
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); adjacency.rows()];
+    for (i, row) in adjacency.outer_iterator().enumerate() {
+        for (j, &w) in row.iter() {
+            if w != 0.0 {
+                let (from, to) = if adjacency.is_csr() { (i, j) } else { (j, i) };
+                successors[from].push(to);
+            }
+        }
+    }
+    for list in &mut successors {
+        list.sort_unstable();
+        list.dedup();
+    }
+    successors
+}
+
+/// Enumerate cycles and report whether the limit cut the list short.
+fn enumerate_cycles(
+    successors: &[Vec<usize>],
+    max_length: usize,
+    max_cycles: usize,
+) -> (Vec<Vec<usize>>, bool) {
+    let n = successors.len();
+    // An elementary cycle visits each node at most once, so caller limits
+    // beyond the node count only inflate the per-length buckets
+    let max_length = max_length.min(n);
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (node, list) in successors.iter().enumerate() {
+        for &next in list {
+            predecessors[next].push(node);
+        }
+    }
+
+    let mut search = CycleSearch {
+        successors,
+        max_cycles,
+        max_length,
+        by_length: vec![Vec::new(); max_length + 1],
+        distance: vec![usize::MAX; n],
+        path: Vec::new(),
+        on_path: vec![false; n],
+    };
+    for start in 0..n {
+        if search.max_length == 0 {
+            break;
+        }
+        let reached = search.distances_to(start, &predecessors);
+        search.path.push(start);
+        search.on_path[start] = true;
+        search.extend(start);
+        search.path.clear();
+        search.on_path[start] = false;
+        for node in reached {
+            search.distance[node] = usize::MAX;
+        }
+    }
+
+    let mut cycles: Vec<Vec<usize>> = search.by_length.into_iter().flatten().collect();
+    let truncated = cycles.len() > max_cycles;
+    cycles.truncate(max_cycles);
+    (cycles, truncated)
+}
+
+/// State of a bounded depth-first cycle search.
+///
+/// Cycles are rooted at their lowest node, so the search from `start` only
+/// visits higher nodes and finds every cycle exactly once.
+struct CycleSearch<'a> {
+    /// Successors of each node.
+    successors: &'a [Vec<usize>],
+    /// Maximum number of cycles to report.
+    max_cycles: usize,
+    /// Longest cycle still worth finding; shrinks once shorter cycles fill the limit.
+    max_length: usize,
+    /// Cycles found so far, bucketed by length.
+    by_length: Vec<Vec<Vec<usize>>>,
+    /// Edges from each node back to the current start, if within reach.
+    distance: Vec<usize>,
+    /// Current path from the start.
+    path: Vec<usize>,
+    /// Whether each node is on the current path.
+    on_path: Vec<bool>,
+}
+
+impl CycleSearch<'_> {
+    /// Breadth-first search backwards from `start` over higher nodes.
+    ///
+    /// # Returns
+    /// The nodes whose distance was set
+    fn distances_to(&mut self, start: usize, predecessors: &[Vec<usize>]) -> Vec<usize> {
+        self.distance[start] = 0;
+        let mut reached = vec![start];
+        let mut head = 0;
+        while let Some(&node) = reached.get(head) {
+            head += 1;
+            let distance = self.distance[node];
+            if distance + 1 >= self.max_length {
+                continue;
+            }
+            for &previous in &predecessors[node] {
+                if previous > start && self.distance[previous] == usize::MAX {
+                    self.distance[previous] = distance + 1;
+                    reached.push(previous);
+                }
+            }
+        }
+        reached
+    }
+
+    /// Extend the current path from `start`, recording cycles that close there.
+    fn extend(&mut self, start: usize) {
+        let successors = self.successors;
+        // Explicit call stack of (node, next successor position) keeps long
+        // cycles in large vaults from overflowing the wasm stack
+        let mut calls = vec![(start, 0)];
+        while let Some(&(node, position)) = calls.last() {
+            let length = self.path.len();
+            let next = successors[node]
+                .get(position)
+                .copied()
+                .filter(|_| length <= self.max_length);
+            let Some(next) = next else {
+                calls.pop();
+                if !calls.is_empty() {
+                    self.on_path[node] = false;
+                    self.path.pop();
+                }
+                continue;
+            };
+
+            let top = calls.len() - 1;
+            calls[top].1 += 1;
+            if next == start {
+                self.record(length);
+            } else if next > start
+                && !self.on_path[next]
+                && self.distance[next] != usize::MAX
+                && length + self.distance[next] <= self.max_length
+            {
+                self.path.push(next);
+                self.on_path[next] = true;
+                calls.push((next, 0));
+            }
+        }
+    }
+
+    /// Store the current path as a cycle of `length` edges.
+    fn record(&mut self, length: usize) {
+        self.by_length[length].push(self.path.clone());
+        // Once shorter cycles alone exceed the limit, longer ones can never be reported
+        let mut total = 0;
+        for shorter in 1..=self.max_length {
+            total += self.by_length[shorter].len();
+            if total > self.max_cycles {
+                self.max_length = shorter;
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::graph;
+
+    #[test]
+    fn test_topological_order_of_dag() {
+        // 3 -> 1 -> 0, 3 -> 2 -> 0
+        let adjacency = graph(4, &[(3, 1), (1, 0), (3, 2), (2, 0)]);
+        assert_eq!(topological_order(&adjacency), Some(vec![3, 1, 2, 0]));
+    }
+
+    #[test]
+    fn test_topological_order_rejects_cycles() {
+        assert_eq!(topological_order(&graph(3, &[(0, 1), (1, 2), (2, 0)])), None);
+        assert_eq!(topological_order(&graph(2, &[(0, 1), (1, 1)])), None);
+    }
+
+    #[test]
+    fn test_find_cycles_shortest_first() {
+        // Self-loop on 4, 2-cycle 0 <-> 1, triangle 0 -> 1 -> 2 -> 0, 4-cycle 0 -> 3 -> ... -> 0
+        let adjacency = graph(6, &[(4, 4), (0, 1), (1, 0), (1, 2), (2, 0), (0, 3), (3, 5), (5, 2)]);
+        let cycles = find_cycles(&adjacency, 10, 100);
+
+        assert_eq!(cycles, vec![vec![4], vec![0, 1], vec![0, 1, 2], vec![0, 3, 5, 2]]);
+        assert_eq!(find_cycles(&adjacency, 3, 100).len(), 3);
+        assert_eq!(find_cycles(&adjacency, usize::MAX, 100), cycles);
+    }
+
+    #[test]
+    fn test_find_cycles_counts_each_cycle_once() {
+        // Complete directed graph on 4 nodes: 6 two-cycles, 8 triangles, 6 four-cycles
+        let edges: Vec<(usize, usize)> = (0..4)
+            .flat_map(|a| (0..4).filter(move |&b| b != a).map(move |b| (a, b)))
+            .collect();
+        let cycles = find_cycles(&graph(4, &edges), 4, 100);
+
+        let count = |length| cycles.iter().filter(|c| c.len() == length).count();
+        assert_eq!((count(2), count(3), count(4)), (6, 8, 6));
+    }
+
+    #[test]
+    fn test_cycle_limit_keeps_shortest() {
+        let edges: Vec<(usize, usize)> = (0..4)
+            .flat_map(|a| (0..4).filter(move |&b| b != a).map(move |b| (a, b)))
+            .collect();
+        let (cycles, truncated) = enumerate_cycles(&successor_lists(&graph(4, &edges)), 4, 7);
+
+        assert!(truncated);
+        assert_eq!(cycles.len(), 7);
+        assert!(cycles[..6].iter().all(|c| c.len() == 2));
+        assert_eq!(cycles[6].len(), 3);
+    }
+
+    #[test]
+    fn test_find_cycles_on_long_ring() {
+        let n = 200_000;
+        let edges: Vec<(usize, usize)> = (0..n).map(|i| (i, (i + 1) % n)).collect();
+        let cycles = find_cycles(&graph(n, &edges), usize::MAX, 10);
+
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), n);
+        assert!(cycles[0].iter().enumerate().all(|(i, &node)| node == i));
+    }
+
+    #[test]
+    fn test_analyze_dag_reports_paths() {
+        let paths: Vec<String> = ["a.md", "b.md", "c.md"].map(String::from).to_vec();
+        let builder = AdjacencyMatrixBuilder::new(paths);
+        let links = vec![NoteLink { from_id: 0, to_id: 1 }, NoteLink { from_id: 1, to_id: 0 }];
+        let report =
+            analyze_dag(&builder, links, &DagConfig::default()).expect("Failed to analyze DAG");
+
+        assert!(!report.is_dag);
+        assert_eq!(report.cyclic_components, vec![vec!["a.md", "b.md"]]);
+        assert_eq!(report.cycles, vec![vec!["a.md", "b.md"]]);
+        assert!(!report.cycles_truncated);
+        assert_eq!(report.topological_order, None);
+    }
+}
@@ -0,0 +1,617 @@
+//! Density-based clustering: DBSCAN and HDBSCAN.
+//!
+//! Unlike k-means these need no cluster count and leave points in sparse
+//! regions unclustered, labelled [`NOISE_LABEL`]. HDBSCAN (Campello, Moulavi &
+//! Sander, 2013) builds a hierarchy over mutual reachability distances and keeps
+//! the most stable clusters, so it also copes with clusters of varying density.
+//!
+//! Both work on raw distances from any [`DistanceMetric`] and take time
+//! quadratic in the number of vectors, with memory linear in it.
+
+use crate::union_find::find_root;
+use crate::{DistanceMetric, PluginError, non_negative_distance};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Label given to points that belong to no cluster.
+pub const NOISE_LABEL: i64 = -1;
+
+/// Density-based clustering algorithm.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DensityAlgorithm {
+    /// Fixed-radius density clustering.
+    Dbscan,
+    /// Hierarchical density clustering with stability-based selection.
+    Hdbscan,
+}
+
+impl DensityAlgorithm {
+    /// Parse an algorithm name (`"dbscan"` or `"hdbscan"`).
+    ///
+    /// # Errors
+    /// Returns error if the name is unknown
+    pub fn from_name(name: &str) -> Result<Self, PluginError> {
+        match name.to_lowercase().as_str() {
+            "dbscan" => Ok(Self::Dbscan),
+            "hdbscan" => Ok(Self::Hdbscan),
+            _ => Err(PluginError::ValidationError {
+                field: "algorithm".to_string(),
+                value: name.to_string(),
+                reason: "Unknown density clustering algorithm".to_string(),
+            }),
+        }
+    }
+}
+
+/// Settings for DBSCAN.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DbscanConfig {
+    /// Neighbourhood radius.
+    pub eps: f64,
+    /// Points within `eps` (including the point itself) needed for a core point.
+    pub min_points: usize,
+}
+
+impl Default for DbscanConfig {
+    fn default() -> Self {
+        Self { eps: 0.5, min_points: 5 }
+    }
+}
+
+/// Settings for HDBSCAN.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HdbscanConfig {
+    /// Smallest group of points that counts as a cluster.
+    pub min_cluster_size: usize,
+    /// Neighbours (including the point itself) defining a point's core
+    /// distance; defaults to `min_cluster_size`. Larger values mark more noise.
+    pub min_samples: Option<usize>,
+    /// Whether all points may form one cluster; otherwise the root of the
+    /// hierarchy is never selected.
+    pub allow_single_cluster: bool,
+}
+
+impl Default for HdbscanConfig {
+    fn default() -> Self {
+        Self { min_cluster_size: 5, min_samples: None, allow_single_cluster: false }
+    }
+}
+
+/// Result of HDBSCAN clustering.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HdbscanResult {
+    /// Cluster label per vector, or [`NOISE_LABEL`].
+    pub labels: Vec<i64>,
+    /// Strength of each vector's membership in `[0, 1]`; 0 for noise.
+    pub probabilities: Vec<f64>,
+    /// Stability (persistence over density levels) of each cluster, by label.
+    pub stability: Vec<f64>,
+}
+
+/// Cluster vectors with DBSCAN.
+///
+/// # Arguments
+/// * `vectors` - Input vectors to cluster
+/// * `config` - Neighbourhood radius and core point threshold
+/// * `metric` - Distance metric
+///
+/// # Returns
+/// Cluster label per vector, numbered in order of discovery, or [`NOISE_LABEL`]
+///
+/// # Errors
+/// Returns error if the settings are invalid or vectors have mismatched dimensions
+pub fn dbscan(
+    vectors: &[Vec<f64>],
+    config: &DbscanConfig,
+    metric: &dyn DistanceMetric,
+) -> Result<Vec<i64>, PluginError> {
+    if !config.eps.is_finite() || config.eps < 0.0 {
+        return Err(PluginError::ValidationError {
+            field: "eps".to_string(),
+            value: config.eps.to_string(),
+            reason: "Radius must be a non-negative number".to_string(),
+        });
+    }
+    if config.min_points == 0 {
+        return Err(PluginError::ValidationError {
+            field: "minPoints".to_string(),
+            value: "0".to_string(),
+            reason: "Core points need at least one point".to_string(),
+        });
+    }
+    validate_dimensions(vectors)?;
+
+    let neighbours = |point: usize| -> Result<Vec<usize>, PluginError> {
+        let mut within = Vec::new();
+        for (other, vec) in vectors.iter().enumerate() {
+            if metric.distance(&vectors[point], vec)? <= config.eps {
+                within.push(other);
+            }
+        }
+        Ok(within)
+    };
+
+    let mut labels: Vec<Option<i64>> = vec![None; vectors.len()];
+    let mut next_label = 0;
+    for start in 0..vectors.len() {
+        if labels[start].is_some() {
+            continue;
+        }
+        let seeds = neighbours(start)?;
+        if seeds.len() < config.min_points {
+            // May still be claimed as a border point of a later cluster
+            labels[start] = Some(NOISE_LABEL);
+            continue;
+        }
+
+        labels[start] = Some(next_label);
+        let mut queue: VecDeque<usize> = seeds.into_iter().collect();
+        while let Some(point) = queue.pop_front() {
+            match labels[point] {
+                Some(NOISE_LABEL) => labels[point] = Some(next_label),
+                None => {
+                    labels[point] = Some(next_label);
+                    let reach = neighbours(point)?;
+                    if reach.len() >= config.min_points {
+                        queue.extend(
+                            reach
+                                .into_iter()
+                                .filter(|&p| labels[p].is_none() || labels[p] == Some(NOISE_LABEL)),
+                        );
+                    }
+                },
+                Some(_) => {},
+            }
+        }
+        next_label += 1;
+    }
+    Ok(labels
+        .into_iter()
+        .map(|label| label.unwrap_or(NOISE_LABEL))
+        .collect())
+}
+
+/// Cluster vectors with HDBSCAN, selecting clusters by excess of mass.
+///
+/// # Arguments
+/// * `vectors` - Input vectors to cluster
+/// * `config` - Minimum cluster size and density smoothing
+/// * `metric` - Distance metric
+///
+/// # Returns
+/// Labels, membership strengths and cluster stabilities
+///
+/// # Errors
+/// Returns error if the settings are invalid or vectors have mismatched dimensions
+pub fn hdbscan(
+    vectors: &[Vec<f64>],
+    config: &HdbscanConfig,
+    metric: &dyn DistanceMetric,
+) -> Result<HdbscanResult, PluginError> {
+    if config.min_cluster_size < 2 {
+        return Err(PluginError::ValidationError {
+            field: "minClusterSize".to_string(),
+            value: config.min_cluster_size.to_string(),
+            reason: "Clusters need at least two points".to_string(),
+        });
+    }
+    let min_samples = config.min_samples.unwrap_or(config.min_cluster_size);
+    if min_samples == 0 {
+        return Err(PluginError::ValidationError {
+            field: "minSamples".to_string(),
+            value: "0".to_string(),
+            reason: "Core distances need at least one neighbour".to_string(),
+        });
+    }
+    validate_dimensions(vectors)?;
+
+    let n = vectors.len();
+    if n < config.min_cluster_size {
+        return Ok(HdbscanResult {
+            labels: vec![NOISE_LABEL; n],
+            probabilities: vec![0.0; n],
+            stability: Vec::new(),
+        });
+    }
+
+    let core = core_distances(vectors, min_samples.min(n), metric)?;
+    let edges = mutual_reachability_tree(vectors, &core, metric)?;
+    let merges = single_linkage(n, edges);
+    let tree = CondensedTree::build(&merges, n, config.min_cluster_size);
+    Ok(tree.extract(config.allow_single_cluster))
+}
+
+/// Check that every vector has the same dimension.
+fn validate_dimensions(vectors: &[Vec<f64>]) -> Result<(), PluginError> {
+    let dim = vectors.first().map_or(0, Vec::len);
+    for (i, vec) in vectors.iter().enumerate() {
+        if vec.len() != dim {
+            return Err(PluginError::InvalidVectorDimensions {
+                expected: dim,
+                got: vec.len(),
+                vector_index: i,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Distance from each vector to its `min_samples`-th nearest vector, counting itself.
+fn core_distances(
+    vectors: &[Vec<f64>],
+    min_samples: usize,
+    metric: &dyn DistanceMetric,
+) -> Result<Vec<f64>, PluginError> {
+    let mut row = vec![0.0; vectors.len()];
+    let mut core = Vec::with_capacity(vectors.len());
+    for vec in vectors {
+        for (dist, other) in row.iter_mut().zip(vectors) {
+            *dist = non_negative_distance(metric, vec, other)?;
+        }
+        let (_, kth, _) = row.select_nth_unstable_by(min_samples - 1, f64::total_cmp);
+        core.push(*kth);
+    }
+    Ok(core)
+}
+
+/// Minimum spanning tree under mutual reachability distance (Prim's algorithm).
+///
+/// # Returns
+/// The tree's edges as `(a, b, distance)`
+fn mutual_reachability_tree(
+    vectors: &[Vec<f64>],
+    core: &[f64],
+    metric: &dyn DistanceMetric,
+) -> Result<Vec<(usize, usize, f64)>, PluginError> {
+    let n = vectors.len();
+    let mut in_tree = vec![false; n];
+    let mut best = vec![(f64::INFINITY, 0); n];
+    let mut edges = Vec::with_capacity(n.saturating_sub(1));
+    let mut current = 0;
+    in_tree[0] = true;
+    for _ in 1..n {
+        let mut next = None;
+        for other in 0..n {
+            if in_tree[other] {
+                continue;
+            }
+            let dist = non_negative_distance(metric, &vectors[current], &vectors[other])?;
+            let reach = dist.max(core[current]).max(core[other]);
+            if reach < best[other].0 {
+                best[other] = (reach, current);
+            }
+            if next.is_none_or(|chosen: usize| best[other].0 < best[chosen].0) {
+                next = Some(other);
+            }
+        }
+        let Some(chosen) = next else { break };
+        in_tree[chosen] = true;
+        edges.push((best[chosen].1, chosen, best[chosen].0));
+        current = chosen;
+    }
+    Ok(edges)
+}
+
+/// A merge in the single-linkage hierarchy. Nodes below `n` are points; merge
+/// `i` creates node `n + i`.
+#[derive(Debug, Clone, Copy)]
+struct Merge {
+    left: usize,
+    right: usize,
+    distance: f64,
+    size: usize,
+}
+
+/// Turn spanning tree edges into a single-linkage merge sequence.
+fn single_linkage(n: usize, mut edges: Vec<(usize, usize, f64)>) -> Vec<Merge> {
+    edges.sort_by(|a, b| a.2.total_cmp(&b.2));
+    let mut parent: Vec<usize> = (0..2 * n).collect();
+    let mut size = vec![1; 2 * n];
+    let mut merges = Vec::with_capacity(n.saturating_sub(1));
+    for (a, b, distance) in edges {
+        let (left, right) = (find_root(&mut parent, a), find_root(&mut parent, b));
+        let node = n + merges.len();
+        parent[left] = node;
+        parent[right] = node;
+        size[node] = size[left] + size[right];
+        merges.push(Merge { left, right, distance, size: size[node] });
+    }
+    merges
+}
+
+/// The single-linkage hierarchy condensed to clusters of at least the minimum size.
+///
+/// Cluster 0 is the root; children always have higher ids than their parents.
+struct CondensedTree {
+    /// Number of points.
+    n: usize,
+    /// Parent of each cluster (the root is its own parent).
+    parent: Vec<usize>,
+    /// Density (1 / distance) at which each cluster appears.
+    birth: Vec<f64>,
+    /// Density at which each point leaves the tree, and the cluster it leaves.
+    point_exit: Vec<(f64, usize)>,
+    /// Number of points in each cluster when it appears.
+    size: Vec<usize>,
+    /// Whether each cluster splits into child clusters.
+    has_children: Vec<bool>,
+}
+
+impl CondensedTree {
+    /// Condense a merge sequence, dropping splits that shed fewer than `min_size` points.
+    fn build(merges: &[Merge], n: usize, min_size: usize) -> Self {
+        let mut tree = Self {
+            n,
+            parent: vec![0],
+            birth: vec![0.0],
+            point_exit: vec![(0.0, 0); n],
+            size: vec![n],
+            has_children: vec![false],
+        };
+        let node_size = |node: usize| if node < n { 1 } else { merges[node - n].size };
+
+        let Some(root) = (n + merges.len()).checked_sub(1) else {
+            return tree;
+        };
+        let mut stack = vec![(root, 0)];
+        while let Some((node, cluster)) = stack.pop() {
+            if node < n {
+                // Unreachable for min_size >= 2, since single points are always shed
+                tree.point_exit[node] = (tree.birth[cluster], cluster);
+                continue;
+            }
+            let merge = merges[node - n];
+            let lambda = density(merge.distance);
+            let (left_big, right_big) =
+                (node_size(merge.left) >= min_size, node_size(merge.right) >= min_size);
+            match (left_big, right_big) {
+                (true, true) => {
+                    tree.has_children[cluster] = true;
+                    for child in [merge.left, merge.right] {
+                        let id = tree.parent.len();
+                        tree.parent.push(cluster);
+                        tree.birth.push(lambda);
+                        tree.size.push(node_size(child));
+                        tree.has_children.push(false);
+                        stack.push((child, id));
+                    }
+                },
+                (true, false) => {
+                    tree.shed(merges, merge.right, lambda, cluster);
+                    stack.push((merge.left, cluster));
+                },
+                (false, true) => {
+                    tree.shed(merges, merge.left, lambda, cluster);
+                    stack.push((merge.right, cluster));
+                },
+                (false, false) => {
+                    tree.shed(merges, merge.left, lambda, cluster);
+                    tree.shed(merges, merge.right, lambda, cluster);
+                },
+            }
+        }
+        tree
+    }
+
+    /// Record every point under `node` as leaving `cluster` at density `lambda`.
+    fn shed(&mut self, merges: &[Merge], node: usize, lambda: f64, cluster: usize) {
+        let mut stack = vec![node];
+        while let Some(node) = stack.pop() {
+            if node < self.n {
+                self.point_exit[node] = (lambda, cluster);
+            } else {
+                stack.push(merges[node - self.n].left);
+                stack.push(merges[node - self.n].right);
+            }
+        }
+    }
+
+    /// Select the most stable clusters and label the points.
+    fn extract(&self, allow_single_cluster: bool) -> HdbscanResult {
+        let clusters = self.parent.len();
+        // Stability: sum over member points of the density range they stay in the cluster
+        let mut stability = vec![0.0; clusters];
+        for &(lambda, cluster) in &self.point_exit {
+            stability[cluster] += lambda - self.birth[cluster];
+        }
+        for cluster in 1..clusters {
+            // Points that stay until a split count towards the parent up to the split
+            let parent = self.parent[cluster];
+            #[allow(clippy::cast_precision_loss)]
+            let held = self.size[cluster] as f64 * (self.birth[cluster] - self.birth[parent]);
+            stability[parent] += held;
+        }
+
+        // Excess of mass: keep a cluster unless its descendants are more stable together
+        let mut selected = vec![false; clusters];
+        let mut subtree = stability.clone();
+        let mut child_sum = vec![0.0; clusters];
+        for cluster in (0..clusters).rev() {
+            let keep = !self.has_children[cluster] || stability[cluster] >= child_sum[cluster];
+            if keep && (cluster != 0 || allow_single_cluster) {
+                selected[cluster] = true;
+            } else {
+                subtree[cluster] = child_sum[cluster];
+            }
+            if cluster != 0 {
+                child_sum[self.parent[cluster]] += subtree[cluster];
+            }
+        }
+        // Only the topmost selected cluster on each branch is kept
+        for cluster in 1..clusters {
+            let mut ancestor = self.parent[cluster];
+            loop {
+                if selected[ancestor] {
+                    selected[cluster] = false;
+                    break;
+                }
+                if ancestor == 0 {
+                    break;
+                }
+                ancestor = self.parent[ancestor];
+            }
+        }
+
+        let mut label_of = vec![NOISE_LABEL; clusters];
+        let mut next_label = 0;
+        let mut cluster_stability = Vec::new();
+        for cluster in 0..clusters {
+            if selected[cluster] {
+                label_of[cluster] = next_label;
+                next_label += 1;
+                cluster_stability.push(stability[cluster]);
+            }
+        }
+        // Clusters inherit the label of their selected ancestor
+        for cluster in 1..clusters {
+            if label_of[cluster] == NOISE_LABEL {
+                label_of[cluster] = label_of[self.parent[cluster]];
+            }
+        }
+
+        let mut max_lambda = vec![0.0_f64; cluster_stability.len()];
+        let mut labels = Vec::with_capacity(self.n);
+        for &(lambda, cluster) in &self.point_exit {
+            let label = label_of[cluster];
+            if let Ok(index) = usize::try_from(label) {
+                max_lambda[index] = max_lambda[index].max(lambda);
+            }
+            labels.push(label);
+        }
+        let probabilities = self
+            .point_exit
+            .iter()
+            .zip(&labels)
+            .map(|(&(lambda, _), &label)| {
+                usize::try_from(label).map_or(0.0, |index| {
+                    let top = max_lambda[index];
+                    if lambda >= top { 1.0 } else { lambda / top }
+                })
+            })
+            .collect();
+
+        HdbscanResult { labels, probabilities, stability: cluster_stability }
+    }
+}
+
+/// Density level corresponding to a merge distance.
+fn density(distance: f64) -> f64 {
+    // Duplicate points merge at distance 0; keep their density finite so stabilities stay finite
+    1.0 / distance.max(f64::EPSILON)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Euclidean;
+    use crate::rng::SeededRng;
+
+    /// Points scattered around `center` with the given spread.
+    fn blob(rng: &mut SeededRng, center: (f64, f64), spread: f64, count: usize) -> Vec<Vec<f64>> {
+        (0..count)
+            .map(|_| {
+                vec![
+                    spread.mul_add(rng.next_normal(), center.0),
+                    spread.mul_add(rng.next_normal(), center.1),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dbscan_marks_outliers_as_noise() {
+        let mut rng = SeededRng::new(1);
+        let mut vectors = blob(&mut rng, (0.0, 0.0), 0.1, 10);
+        vectors.extend(blob(&mut rng, (5.0, 5.0), 0.1, 10));
+        vectors.push(vec![20.0, -20.0]);
+        let config = DbscanConfig { eps: 0.5, min_points: 3 };
+        let labels = dbscan(&vectors, &config, &Euclidean).expect("DBSCAN failed");
+
+        assert!(labels[..10].iter().all(|&l| l == 0));
+        assert!(labels[10..20].iter().all(|&l| l == 1));
+        assert_eq!(labels[20], NOISE_LABEL);
+    }
+
+    #[test]
+    fn test_dbscan_claims_border_points() {
+        // 0..=2 are core points; 3 is only within reach of 2
+        let vectors = vec![vec![0.0], vec![0.5], vec![1.0], vec![1.9], vec![10.0]];
+        let config = DbscanConfig { eps: 1.0, min_points: 3 };
+        let labels = dbscan(&vectors, &config, &Euclidean).expect("DBSCAN failed");
+
+        assert_eq!(labels, vec![0, 0, 0, 0, NOISE_LABEL]);
+    }
+
+    #[test]
+    fn test_hdbscan_handles_varying_density() {
+        let mut rng = SeededRng::new(2);
+        let mut vectors = blob(&mut rng, (0.0, 0.0), 0.05, 20);
+        vectors.extend(blob(&mut rng, (10.0, 0.0), 1.0, 20));
+        vectors.push(vec![5.0, 30.0]);
+        let result =
+            hdbscan(&vectors, &HdbscanConfig::default(), &Euclidean).expect("HDBSCAN failed");
+
+        let tight = result.labels[0];
+        let loose = result.labels[20];
+        assert_ne!(tight, NOISE_LABEL);
+        assert_ne!(loose, NOISE_LABEL);
+        assert_ne!(tight, loose);
+        assert!(result.labels[..20].iter().all(|&l| l == tight));
+        assert!(
+            result.labels[20..40]
+                .iter()
+                .filter(|&&l| l == loose)
+                .count()
+                >= 18
+        );
+        assert_eq!(result.labels[40], NOISE_LABEL);
+
+        assert_eq!(result.stability.len(), 2);
+        assert!(result.stability.iter().all(|&s| s > 0.0));
+        assert!(result.probabilities.iter().all(|p| (0.0..=1.0).contains(p)));
+        assert!(
+            result.probabilities[..40]
+                .iter()
+                .any(|&p| (p - 1.0).abs() < f64::EPSILON)
+        );
+        assert!(result.probabilities[40].abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_hdbscan_can_return_single_cluster() {
+        let mut rng = SeededRng::new(3);
+        let vectors = blob(&mut rng, (0.0, 0.0), 1.0, 30);
+
+        let config = HdbscanConfig { allow_single_cluster: true, ..HdbscanConfig::default() };
+        let single = hdbscan(&vectors, &config, &Euclidean).expect("HDBSCAN failed");
+        assert!(single.labels.iter().filter(|&&l| l == 0).count() >= 25, "{:?}", single.labels);
+    }
+
+    #[test]
+    fn test_hdbscan_tolerates_duplicates() {
+        let mut vectors = vec![vec![1.0, 1.0]; 6];
+        vectors.extend(vec![vec![9.0, 9.0]; 6]);
+        let config = HdbscanConfig { min_cluster_size: 3, ..HdbscanConfig::default() };
+        let result = hdbscan(&vectors, &config, &Euclidean).expect("HDBSCAN failed");
+
+        assert_eq!(result.labels, [vec![0; 6], vec![1; 6]].concat());
+        assert!(result.stability.iter().all(|s| s.is_finite()));
+    }
+
+    #[test]
+    fn test_invalid_settings_are_rejected() {
+        let vectors = vec![vec![0.0], vec![1.0]];
+        let bad_eps = DbscanConfig { eps: f64::NAN, ..DbscanConfig::default() };
+        let bad_size = HdbscanConfig { min_cluster_size: 1, ..HdbscanConfig::default() };
+
+        assert!(dbscan(&vectors, &bad_eps, &Euclidean).is_err());
+        assert!(hdbscan(&vectors, &bad_size, &Euclidean).is_err());
+        assert!(
+            dbscan(&[vec![0.0], vec![1.0, 2.0]], &DbscanConfig::default(), &Euclidean).is_err()
+        );
+    }
+}
@@ -3,8 +3,15 @@
 //! This module provides trait-based abstractions for reducing high-dimensional
 //! vectors to lower dimensions for visualization purposes.
 
-use crate::PluginError;
+use crate::binary::{ByteReader, ByteWriter};
+use crate::{PluginError, distance_metric_from_name, normalize_vectors};
 use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+/// Magic bytes at the start of a serialized projection model.
+const MODEL_MAGIC: [u8; 4] = *b"PROJ";
+/// Current binary format version for projection models.
+const MODEL_FORMAT_VERSION: u8 = 1;
 
 /// Trait for dimensionality reduction algorithms.
 pub trait DimensionalityReducer {
@@ -27,6 +34,285 @@ pub trait DimensionalityReducer {
 
     /// Get the name of this reduction method.
     fn method_name(&self) -> &str;
+
+    /// Fit a reusable projection model.
+    ///
+    /// Unlike [`DimensionalityReducer::reduce`], the returned model can map
+    /// vectors that were not part of the fit into the same space, so adding a
+    /// note does not reshuffle the existing layout.
+    ///
+    /// # Arguments
+    /// * `vectors` - Input high-dimensional vectors
+    /// * `target_dims` - Target dimensionality (typically 2 or 3)
+    ///
+    /// # Returns
+    /// A fitted projection model
+    ///
+    /// # Errors
+    /// Returns error if fitting fails or the method has no out-of-sample projection
+    fn fit(
+        &self,
+        vectors: &[Vec<f64>],
+        target_dims: usize,
+    ) -> Result<ProjectionModel, PluginError> {
+        let _ = (vectors, target_dims);
+        Err(PluginError::DimensionalityReductionError {
+            method: self.method_name().to_string(),
+            reason: "This method does not support fit/transform".to_string(),
+        })
+    }
+}
+
+/// A fitted linear projection that can be applied to new vectors.
+///
+/// A vector `x` is mapped to `((x - means) / scales) · basisᵀ`, with the
+/// centering and scaling steps skipped when they were not used during the fit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectionModel {
+    /// Name of the method that produced the model.
+    pub method: String,
+    /// Column means subtracted before projecting, if the data was centered.
+    pub means: Option<Vec<f64>>,
+    /// Column scale factors divided out before projecting, if the data was scaled.
+    pub scales: Option<Vec<f64>>,
+    /// Basis vectors (one row per output dimension, one column per input dimension).
+    pub basis: Vec<Vec<f64>>,
+}
+
+impl ProjectionModel {
+    /// Dimensionality of the vectors the model accepts.
+    #[must_use]
+    pub fn input_dims(&self) -> usize {
+        self.basis.first().map_or(0, Vec::len)
+    }
+
+    /// Dimensionality of the projected vectors.
+    #[must_use]
+    pub const fn output_dims(&self) -> usize {
+        self.basis.len()
+    }
+
+    /// Map vectors into the fitted space.
+    ///
+    /// # Arguments
+    /// * `vectors` - Vectors with the same dimensionality as the fitted data
+    ///
+    /// # Returns
+    /// Projected vectors (one per input)
+    ///
+    /// # Errors
+    /// Returns error if a vector's dimensionality does not match the model
+    pub fn transform(&self, vectors: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PluginError> {
+        let dim = self.input_dims();
+        vectors
+            .iter()
+            .enumerate()
+            .map(|(i, vec)| {
+                if vec.len() != dim {
+                    return Err(PluginError::InvalidVectorDimensions {
+                        expected: dim,
+                        got: vec.len(),
+                        vector_index: i,
+                    });
+                }
+
+                let prepared: Vec<f64> = vec
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &x)| {
+                        let centered = self.means.as_ref().map_or(x, |m| x - m[j]);
+                        self.scales.as_ref().map_or(centered, |s| centered / s[j])
+                    })
+                    .collect();
+
+                Ok(self
+                    .basis
+                    .iter()
+                    .map(|axis| axis.iter().zip(&prepared).map(|(a, x)| a * x).sum())
+                    .collect())
+            })
+            .collect()
+    }
+
+    /// Serialize the model to JSON.
+    ///
+    /// # Errors
+    /// Returns error if serialization fails
+    pub fn to_json(&self) -> Result<String, PluginError> {
+        serde_json::to_string(self).map_err(|e| PluginError::SerializationError {
+            context: "ProjectionModel::to_json".to_string(),
+            source: e.to_string(),
+        })
+    }
+
+    /// Load a model from JSON produced by [`ProjectionModel::to_json`].
+    ///
+    /// # Errors
+    /// Returns error if the JSON is invalid or the model is inconsistent
+    pub fn from_json(json: &str) -> Result<Self, PluginError> {
+        let model: Self =
+            serde_json::from_str(json).map_err(|e| PluginError::SerializationError {
+                context: "ProjectionModel::from_json".to_string(),
+                source: e.to_string(),
+            })?;
+        model.validate("ProjectionModel::from_json")?;
+        Ok(model)
+    }
+
+    /// Serialize the model to a compact binary blob.
+    ///
+    /// # Returns
+    /// Encoded model bytes
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = ByteWriter::with_header(MODEL_MAGIC, MODEL_FORMAT_VERSION);
+        writer.write_str(&self.method);
+        writer.write_len(self.output_dims());
+        writer.write_len(self.input_dims());
+        for values in [&self.means, &self.scales] {
+            writer.write_bool(values.is_some());
+            for &value in values.iter().flat_map(|v| v.iter()) {
+                writer.write_f64(value);
+            }
+        }
+        for &value in self.basis.iter().flatten() {
+            writer.write_f64(value);
+        }
+        writer.into_bytes()
+    }
+
+    /// Load a model from bytes produced by [`ProjectionModel::to_bytes`].
+    ///
+    /// # Errors
+    /// Returns error if the data is corrupt, truncated, or uses an unknown version
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PluginError> {
+        let (mut reader, version) =
+            ByteReader::with_header(bytes, MODEL_MAGIC, "ProjectionModel::from_bytes")?;
+        if version != MODEL_FORMAT_VERSION {
+            return Err(reader.error(&format!("unsupported format version {version}")));
+        }
+
+        let method = reader.read_str()?;
+        let output_dims = reader.read_len()?;
+        let input_dims = reader.read_len()?;
+        // Dimensions come from untrusted data; the basis must fit in what is left
+        let basis_bytes = output_dims
+            .checked_mul(input_dims)
+            .and_then(|n| n.checked_mul(8));
+        if basis_bytes.is_none_or(|bytes| bytes > reader.remaining()) {
+            return Err(reader.error("basis larger than the remaining data"));
+        }
+        if input_dims == 0 && output_dims > 0 {
+            return Err(reader.error("basis vectors have no dimensions"));
+        }
+
+        let read_optional = |reader: &mut ByteReader<'_>| -> Result<_, PluginError> {
+            if reader.read_bool()? {
+                Ok(Some(
+                    (0..input_dims)
+                        .map(|_| reader.read_f64())
+                        .collect::<Result<_, _>>()?,
+                ))
+            } else {
+                Ok(None)
+            }
+        };
+        let means = read_optional(&mut reader)?;
+        let scales = read_optional(&mut reader)?;
+        let basis = (0..output_dims)
+            .map(|_| (0..input_dims).map(|_| reader.read_f64()).collect())
+            .collect::<Result<_, _>>()?;
+        reader.finish()?;
+
+        let model = Self { method, means, scales, basis };
+        model.validate("ProjectionModel::from_bytes")?;
+        Ok(model)
+    }
+
+    /// Check that every component has the same input dimensionality.
+    fn validate(&self, context: &str) -> Result<(), PluginError> {
+        let dim = self.input_dims();
+        let consistent = self.basis.iter().all(|axis| axis.len() == dim)
+            && self.means.as_ref().is_none_or(|m| m.len() == dim)
+            && self.scales.as_ref().is_none_or(|s| s.len() == dim);
+        if consistent {
+            Ok(())
+        } else {
+            Err(PluginError::SerializationError {
+                context: context.to_string(),
+                source: "basis, means and scales have inconsistent dimensions".to_string(),
+            })
+        }
+    }
+}
+
+/// Prepare vectors so that a linear projection reflects a distance metric.
+///
+/// SVD and PCA keep the directions of largest Euclidean variance. Unit
+/// vectors have Euclidean distances that grow monotonically with cosine
+/// distance, so cosine is honoured by normalising first. No linear
+/// projection preserves inner-product, Manhattan or Chebyshev geometry;
+/// use UMAP or t-SNE for those.
+///
+/// # Arguments
+/// * `vectors` - Input high-dimensional vectors
+/// * `metric` - Distance metric name (e.g. `"euclidean"`, `"cosine"`)
+///
+/// # Returns
+/// The vectors to reduce, normalised to unit length for cosine
+///
+/// # Errors
+/// Returns error if the metric is unknown or has no linear projection, or a
+/// vector has zero norm under cosine
+pub fn vectors_for_metric(
+    vectors: Vec<Vec<f64>>,
+    metric: &str,
+) -> Result<Vec<Vec<f64>>, PluginError> {
+    // Resolve aliases through the built-in table; names from it are canonical
+    match distance_metric_from_name(metric)?.name() {
+        "euclidean" | "squared_euclidean" => Ok(vectors),
+        "cosine" => normalize_vectors(&vectors),
+        name => Err(PluginError::DimensionalityReductionError {
+            method: "SVD".to_string(),
+            reason: format!("Metric '{name}' has no linear projection; use UMAP or t-SNE"),
+        }),
+    }
+}
+
+/// Sign that makes a component's largest-magnitude loading positive.
+///
+/// Loadings within 1e-12 of the largest magnitude count as ties and the
+/// first of them decides, so rounding differences between platforms cannot
+/// flip the choice.
+///
+/// # Arguments
+/// * `loadings` - Weights of one component over the input dimensions
+///
+/// # Returns
+/// `1.0` or `-1.0`
+#[must_use]
+pub fn canonical_sign(loadings: &[f64]) -> f64 {
+    let largest = loadings.iter().fold(0.0_f64, |m, x| m.max(x.abs()));
+    loadings
+        .iter()
+        .find(|x| x.abs() >= largest - 1e-12)
+        .map_or(1.0, |&x| if x < 0.0 { -1.0 } else { 1.0 })
+}
+
+/// Result of a PCA projection, with the diagnostics needed to explain it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PcaResult {
+    /// Projected coordinates (one row per input vector).
+    pub coordinates: Vec<Vec<f64>>,
+    /// Fraction of the total variance captured by each component.
+    pub explained_variance_ratio: Vec<f64>,
+    /// Running total of `explained_variance_ratio`.
+    pub cumulative_variance: Vec<f64>,
+    /// Component loadings (rows of Vᵀ): `loadings[k][d]` is the weight of input
+    /// dimension `d` in component `k`.
+    pub loadings: Vec<Vec<f64>>,
 }
 
 /// SVD-based dimensionality reduction.
@@ -59,48 +345,31 @@ impl SVDReducer {
         Self { center, scale }
     }
 
-    /// Center the data matrix (subtract column means).
-    fn center_data(matrix: &DMatrix<f64>) -> (DMatrix<f64>, DVector<f64>) {
-        let means = matrix.column_mean();
-        let nrows = matrix.nrows();
-        let ncols = matrix.ncols();
-
-        let centered = DMatrix::from_fn(nrows, ncols, |i, j| matrix[(i, j)] - means[j]);
-
-        (centered, means)
-    }
-
-    /// Scale the data matrix (divide by column standard deviations).
-    #[allow(clippy::cast_precision_loss)]
-    fn scale_data(matrix: &DMatrix<f64>) -> (DMatrix<f64>, DVector<f64>) {
-        let ncols = matrix.ncols();
-        let mut std_devs = DVector::zeros(ncols);
-
-        for col in 0..ncols {
-            let column = matrix.column(col);
-            let variance = column.iter().map(|x| x * x).sum::<f64>() / (matrix.nrows() as f64);
-            std_devs[col] = variance.sqrt().max(1e-10); // Avoid division by zero
-        }
-
-        let scaled =
-            DMatrix::from_fn(matrix.nrows(), matrix.ncols(), |i, j| matrix[(i, j)] / std_devs[j]);
-
-        (scaled, std_devs)
-    }
-}
-
-impl Default for SVDReducer {
-    fn default() -> Self {
-        Self::new()
+    /// Project vectors onto their principal components and report how much
+    /// variance each component explains.
+    ///
+    /// Components beyond the rank of the data (e.g. more components than
+    /// vectors) are returned as zeros.
+    ///
+    /// # Arguments
+    /// * `vectors` - Input high-dimensional vectors
+    /// * `target_dims` - Number of components to keep
+    ///
+    /// # Returns
+    /// Coordinates, explained variance ratios and loadings
+    ///
+    /// # Errors
+    /// Returns error if input is empty, dimensions mismatch, or `target_dims` exceeds the input dimensionality
+    pub fn pca(&self, vectors: &[Vec<f64>], target_dims: usize) -> Result<PcaResult, PluginError> {
+        Ok(self.fit_pca(vectors, target_dims)?.0)
     }
-}
 
-impl DimensionalityReducer for SVDReducer {
-    fn reduce(
+    /// Run PCA, returning both the projection diagnostics and the fitted model.
+    fn fit_pca(
         &self,
         vectors: &[Vec<f64>],
         target_dims: usize,
-    ) -> Result<Vec<Vec<f64>>, PluginError> {
+    ) -> Result<(PcaResult, ProjectionModel), PluginError> {
         if vectors.is_empty() {
             return Err(PluginError::InsufficientData { required: 1, provided: 0 });
         }
@@ -129,42 +398,153 @@ impl DimensionalityReducer for SVDReducer {
         // Convert to matrix (rows = data points, cols = dimensions)
         let mut matrix = DMatrix::from_fn(vectors.len(), dim, |i, j| vectors[i][j]);
 
-        // Center and/or scale if requested
+        // Center and/or scale if requested, keeping the factors for the model
+        let mut means = None;
+        let mut scales = None;
         if self.center {
-            (matrix, _) = Self::center_data(&matrix);
+            let (centered, column_means) = Self::center_data(&matrix);
+            matrix = centered;
+            means = Some(column_means.iter().copied().collect());
         }
         if self.scale {
-            (matrix, _) = Self::scale_data(&matrix);
+            let (normalized, std_devs) = Self::scale_data(&matrix);
+            matrix = normalized;
+            scales = Some(std_devs.iter().copied().collect());
         }
 
-        // Perform SVD
+        // Perform SVD (singular values come back sorted in descending order)
         let svd = matrix.svd(true, true);
 
-        // Project onto top `target_dims` singular vectors
-        let u = svd
+        let mut u = svd
             .u
             .ok_or_else(|| PluginError::DimensionalityReductionError {
                 method: "SVD".to_string(),
                 reason: "SVD failed to compute U matrix".to_string(),
             })?;
+        let mut v_t = svd
+            .v_t
+            .ok_or_else(|| PluginError::DimensionalityReductionError {
+                method: "SVD".to_string(),
+                reason: "SVD failed to compute V matrix".to_string(),
+            })?;
 
         let sigma = &svd.singular_values;
+        let rank = sigma.len();
+        let total_variance: f64 = sigma.iter().map(|s| s * s).sum();
+
+        // Singular vectors are only defined up to sign; fix it so runs agree
+        for j in 0..rank {
+            let loadings: Vec<f64> = v_t.row(j).iter().copied().collect();
+            if canonical_sign(&loadings) < 0.0 {
+                v_t.row_mut(j).neg_mut();
+                u.column_mut(j).neg_mut();
+            }
+        }
 
         // Reduced representation: U * Sigma (first target_dims components)
-        let reduced_matrix =
-            DMatrix::from_fn(vectors.len(), target_dims, |i, j| u[(i, j)] * sigma[j]);
+        let coordinates = (0..vectors.len())
+            .map(|i| {
+                (0..target_dims)
+                    .map(|j| if j < rank { u[(i, j)] * sigma[j] } else { 0.0 })
+                    .collect()
+            })
+            .collect();
 
-        // Convert back to Vec<Vec<f64>>
-        let result = (0..vectors.len())
-            .map(|i| (0..target_dims).map(|j| reduced_matrix[(i, j)]).collect())
+        let explained_variance_ratio: Vec<f64> = (0..target_dims)
+            .map(|j| {
+                if j < rank && total_variance > 0.0 {
+                    sigma[j] * sigma[j] / total_variance
+                } else {
+                    0.0
+                }
+            })
             .collect();
 
-        Ok(result)
+        let cumulative_variance = explained_variance_ratio
+            .iter()
+            .scan(0.0, |total, ratio| {
+                *total += ratio;
+                Some(*total)
+            })
+            .collect();
+
+        let loadings: Vec<Vec<f64>> = (0..target_dims)
+            .map(|j| {
+                if j < rank {
+                    v_t.row(j).iter().copied().collect()
+                } else {
+                    vec![0.0; dim]
+                }
+            })
+            .collect();
+
+        let model = ProjectionModel {
+            method: self.method_name().to_string(),
+            means,
+            scales,
+            basis: loadings.clone(),
+        };
+        let result =
+            PcaResult { coordinates, explained_variance_ratio, cumulative_variance, loadings };
+        Ok((result, model))
+    }
+
+    /// Center the data matrix (subtract column means).
+    fn center_data(matrix: &DMatrix<f64>) -> (DMatrix<f64>, DVector<f64>) {
+        let means = matrix.row_mean().transpose();
+        let nrows = matrix.nrows();
+        let ncols = matrix.ncols();
+
+        let centered = DMatrix::from_fn(nrows, ncols, |i, j| matrix[(i, j)] - means[j]);
+
+        (centered, means)
+    }
+
+    /// Scale the data matrix (divide by column standard deviations).
+    #[allow(clippy::cast_precision_loss)]
+    fn scale_data(matrix: &DMatrix<f64>) -> (DMatrix<f64>, DVector<f64>) {
+        let ncols = matrix.ncols();
+        let mut std_devs = DVector::zeros(ncols);
+
+        for col in 0..ncols {
+            let column = matrix.column(col);
+            let variance = column.iter().map(|x| x * x).sum::<f64>() / (matrix.nrows() as f64);
+            std_devs[col] = variance.sqrt().max(1e-10); // Avoid division by zero
+        }
+
+        let scaled =
+            DMatrix::from_fn(matrix.nrows(), matrix.ncols(), |i, j| matrix[(i, j)] / std_devs[j]);
+
+        (scaled, std_devs)
+    }
+}
+
+impl Default for SVDReducer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DimensionalityReducer for SVDReducer {
+    fn reduce(
+        &self,
+        vectors: &[Vec<f64>],
+        target_dims: usize,
+    ) -> Result<Vec<Vec<f64>>, PluginError> {
+        Ok(self.pca(vectors, target_dims)?.coordinates)
     }
 
     fn method_name(&self) -> &'static str {
         "SVD"
     }
+
+    fn fit(
+        &self,
+        vectors: &[Vec<f64>],
+        target_dims: usize,
+    ) -> Result<ProjectionModel, PluginError> {
+        Ok(self.fit_pca(vectors, target_dims)?.1)
+    }
 }
 
 #[cfg(test)]
@@ -184,6 +564,175 @@ mod tests {
         assert_eq!(result[2].len(), 2);
     }
 
+    #[test]
+    fn test_pca_explained_variance() {
+        // Variance lies almost entirely along the first axis
+        let vectors = vec![
+            vec![-2.0, 0.1, 0.0],
+            vec![-1.0, -0.1, 0.0],
+            vec![1.0, 0.1, 0.0],
+            vec![2.0, -0.1, 0.0],
+        ];
+
+        let result = SVDReducer::new().pca(&vectors, 2).expect("PCA failed");
+
+        assert!(result.explained_variance_ratio[0] > 0.99);
+        assert!(result.explained_variance_ratio[0] >= result.explained_variance_ratio[1]);
+        assert!((result.cumulative_variance[1] - 1.0).abs() < 1e-12);
+        assert!((result.loadings[0][0].abs() - 1.0).abs() < 1e-3);
+        assert_eq!(result.loadings[1].len(), 3);
+    }
+
+    #[test]
+    fn test_pca_pads_components_beyond_rank() {
+        let vectors = vec![vec![1.0, 2.0, 3.0, 4.0], vec![5.0, 6.0, 7.0, 8.0]];
+
+        let result = SVDReducer::new().pca(&vectors, 3).expect("PCA failed");
+
+        assert!(result.coordinates.iter().all(|c| c.len() == 3));
+        assert!(result.explained_variance_ratio[2].abs() < f64::EPSILON);
+        assert!(result.loadings[2].iter().all(|x| x.abs() < f64::EPSILON));
+    }
+
+    #[test]
+    fn test_fit_transform_reproduces_reduce() {
+        let vectors = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 0.0, 6.0],
+            vec![7.0, 8.0, 1.0],
+            vec![2.0, 3.0, 4.0],
+        ];
+        let reducer = SVDReducer::with_options(true, true);
+
+        let coordinates = reducer.reduce(&vectors, 2).expect("SVD reduction failed");
+        let model = reducer.fit(&vectors, 2).expect("Fit failed");
+        let transformed = model.transform(&vectors).expect("Transform failed");
+
+        for (a, b) in coordinates
+            .iter()
+            .flatten()
+            .zip(transformed.iter().flatten())
+        {
+            assert!((a - b).abs() < 1e-9);
+        }
+        assert_eq!(model.input_dims(), 3);
+        assert_eq!(model.output_dims(), 2);
+    }
+
+    #[test]
+    fn test_projection_model_round_trips() {
+        let vectors = vec![vec![1.0, 2.0], vec![3.0, 1.0], vec![0.0, 5.0]];
+        let model = SVDReducer::with_options(true, true)
+            .fit(&vectors, 2)
+            .expect("Fit failed");
+
+        let from_json = ProjectionModel::from_json(&model.to_json().expect("to_json failed"))
+            .expect("from_json failed");
+        let from_bytes = ProjectionModel::from_bytes(&model.to_bytes()).expect("from_bytes failed");
+
+        assert_eq!(from_json, model);
+        assert_eq!(from_bytes, model);
+        assert!(ProjectionModel::from_bytes(&model.to_bytes()[..10]).is_err());
+    }
+
+    /// Encode a model header followed by optional means and no scales.
+    fn model_blob(output_dims: usize, input_dims: usize, means: Option<&[f64]>) -> Vec<u8> {
+        let mut writer = ByteWriter::with_header(MODEL_MAGIC, MODEL_FORMAT_VERSION);
+        writer.write_str("SVD");
+        writer.write_len(output_dims);
+        writer.write_len(input_dims);
+        writer.write_bool(means.is_some());
+        for &value in means.into_iter().flatten() {
+            writer.write_f64(value);
+        }
+        writer.write_bool(false);
+        writer.into_bytes()
+    }
+
+    #[test]
+    fn test_projection_model_from_bytes_rejects_corrupt_blobs() {
+        let huge_empty_basis = model_blob(u32::MAX as usize, 0, None);
+        let truncated_basis = model_blob(2, 1_000_000, None);
+        // No basis rows, so the three means disagree with the input dimensionality
+        let inconsistent = model_blob(0, 3, Some(&[1.0, 2.0, 3.0]));
+
+        for bytes in [huge_empty_basis, truncated_basis, inconsistent] {
+            let result = ProjectionModel::from_bytes(&bytes);
+            assert!(matches!(result, Err(PluginError::SerializationError { .. })));
+        }
+    }
+
+    #[test]
+    fn test_transform_dimension_mismatch() {
+        let model = SVDReducer::new()
+            .fit(&[vec![1.0, 2.0], vec![3.0, 1.0]], 1)
+            .expect("Fit failed");
+        let result = model.transform(&[vec![1.0, 2.0], vec![1.0]]);
+
+        assert!(matches!(
+            result,
+            Err(PluginError::InvalidVectorDimensions { expected: 2, got: 1, vector_index: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_canonical_sign() {
+        assert!((canonical_sign(&[0.1, -0.9, 0.3]) + 1.0).abs() < f64::EPSILON);
+        assert!((canonical_sign(&[0.1, 0.9, -0.3]) - 1.0).abs() < f64::EPSILON);
+        // Ties are decided by the first dimension
+        assert!((canonical_sign(&[-0.5, 0.5]) + 1.0).abs() < f64::EPSILON);
+        assert!((canonical_sign(&[]) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_pca_is_invariant_to_input_sign() {
+        let vectors = vec![vec![1.0, 2.0, 0.5], vec![3.0, -1.0, 2.0], vec![-2.0, 0.0, 1.0]];
+        let negated: Vec<Vec<f64>> = vectors
+            .iter()
+            .map(|v| v.iter().map(|x| -x).collect())
+            .collect();
+
+        let a = SVDReducer::new().pca(&vectors, 2).expect("PCA failed");
+        let b = SVDReducer::new().pca(&negated, 2).expect("PCA failed");
+
+        for loadings in &a.loadings {
+            assert!(canonical_sign(loadings) > 0.0);
+        }
+        // Negating the data negates the coordinates but the loadings stay put
+        for (x, y) in a.loadings.iter().flatten().zip(b.loadings.iter().flatten()) {
+            assert!((x - y).abs() < 1e-9);
+        }
+        for (x, y) in a
+            .coordinates
+            .iter()
+            .flatten()
+            .zip(b.coordinates.iter().flatten())
+        {
+            assert!((x + y).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_vectors_for_metric() {
+        let vectors = vec![vec![3.0, 4.0], vec![0.0, 2.0]];
+
+        assert_eq!(
+            vectors_for_metric(vectors.clone(), "euclidean").expect("Euclidean failed"),
+            vectors
+        );
+        let unit = vectors_for_metric(vectors.clone(), "cosine").expect("Cosine failed");
+        assert!((unit[0][0] - 0.6).abs() < 1e-12 && (unit[0][1] - 0.8).abs() < 1e-12);
+        assert_eq!(unit[1], vec![0.0, 1.0]);
+        assert!(matches!(
+            vectors_for_metric(vectors.clone(), "manhattan"),
+            Err(PluginError::DimensionalityReductionError { .. })
+        ));
+        assert!(matches!(
+            vectors_for_metric(vectors, "nope"),
+            Err(PluginError::ValidationError { .. })
+        ));
+    }
+
     #[test]
     fn test_svd_reducer_invalid_target_dims() {
         let vectors = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
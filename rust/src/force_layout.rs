@@ -0,0 +1,616 @@
+//! Force-directed graph layout in the style of ForceAtlas2.
+//!
+//! Linked notes attract each other, every pair of notes repels (approximated
+//! with a Barnes-Hut tree), and gravity keeps disconnected parts on screen.
+//! The layout is stepped explicitly so the UI can animate it, and keeps its
+//! state between steps so nodes can be pinned or dragged mid-simulation.
+
+use crate::PluginError;
+use crate::rng::SeededRng;
+use crate::space_tree::{MAX_DIMS, SpaceTree};
+use serde::{Deserialize, Serialize};
+use sprs::CsMat;
+use std::collections::BTreeMap;
+
+/// Initial positions are spread over a box of this half-width × √N.
+const INITIAL_SPREAD: f64 = 10.0;
+/// Lower bound on the global speed efficiency.
+const MIN_SPEED_EFFICIENCY: f64 = 0.05;
+/// Upper bound on the jitter tolerance multiplier.
+const MAX_JITTER_TOLERANCE: f64 = 10.0;
+/// Largest relative increase of the global speed in one iteration.
+const MAX_SPEED_RISE: f64 = 0.5;
+/// Upper bound on the global speed; efficiency also stops growing here.
+const MAX_SPEED: f64 = 1000.0;
+
+/// Settings for the force-directed layout.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ForceLayoutConfig {
+    /// Layout dimensionality (2 or 3).
+    pub dims: usize,
+    /// Strength of the pull towards the origin.
+    pub gravity: f64,
+    /// Make gravity grow with distance from the origin instead of being constant.
+    pub strong_gravity: bool,
+    /// Repulsion strength; larger values give a more spread-out layout.
+    pub scaling: f64,
+    /// Exponent applied to edge weights in the attraction (0 ignores weights).
+    pub edge_weight_influence: f64,
+    /// Barnes-Hut accuracy trade-off; 0 computes exact repulsion.
+    pub theta: f64,
+    /// How much swinging is tolerated before the layout slows down.
+    pub jitter_tolerance: f64,
+    /// Seed for the initial positions.
+    pub seed: u64,
+}
+
+impl Default for ForceLayoutConfig {
+    fn default() -> Self {
+        Self {
+            dims: 2,
+            gravity: 1.0,
+            strong_gravity: false,
+            scaling: 2.0,
+            edge_weight_influence: 1.0,
+            theta: 1.2,
+            jitter_tolerance: 1.0,
+            seed: 42,
+        }
+    }
+}
+
+/// Incremental force-directed layout of a link graph.
+///
+/// Node masses are their degree plus one, so hubs push their surroundings
+/// further away. Speed is adapted globally and per node from how much the
+/// forces oscillate between iterations, as in ForceAtlas2.
+pub struct ForceLayout {
+    /// Layout settings.
+    config: ForceLayoutConfig,
+    /// Current position of each node.
+    positions: Vec<Vec<f64>>,
+    /// Pinned nodes keep their position but still exert forces.
+    pinned: Vec<bool>,
+    /// Undirected edge weights keyed by `(min, max)` node index.
+    edges: BTreeMap<(usize, usize), f64>,
+    /// Degree plus one per node.
+    masses: Vec<f64>,
+    /// Forces from the previous iteration, used to measure swinging.
+    previous_forces: Vec<[f64; MAX_DIMS]>,
+    /// Global speed.
+    speed: f64,
+    /// Global speed efficiency.
+    speed_efficiency: f64,
+    /// Number of iterations run so far.
+    iterations: usize,
+}
+
+impl ForceLayout {
+    /// Create a layout with seeded random initial positions.
+    ///
+    /// # Arguments
+    /// * `adjacency` - Square sparse adjacency matrix; links are treated as
+    ///   undirected, weighted by their count, and self-loops are ignored
+    /// * `config` - Layout settings
+    ///
+    /// # Returns
+    /// A layout ready to be stepped
+    ///
+    /// # Errors
+    /// Returns error if the matrix is not square or the settings are invalid
+    pub fn new(adjacency: &CsMat<f64>, config: ForceLayoutConfig) -> Result<Self, PluginError> {
+        if adjacency.rows() != adjacency.cols() {
+            return Err(PluginError::ValidationError {
+                field: "adjacency".to_string(),
+                value: format!("{}x{}", adjacency.rows(), adjacency.cols()),
+                reason: "Adjacency matrix must be square".to_string(),
+            });
+        }
+        validate_config(&config)?;
+
+        let mut edges = BTreeMap::new();
+        for (i, row) in adjacency.outer_iterator().enumerate() {
+            for (j, &w) in row.iter() {
+                if i != j && w != 0.0 {
+                    *edges.entry((i.min(j), i.max(j))).or_insert(0.0) += w;
+                }
+            }
+        }
+
+        let n = adjacency.rows();
+        #[allow(clippy::cast_precision_loss)]
+        let spread = INITIAL_SPREAD * (n as f64).sqrt();
+        let mut rng = SeededRng::new(config.seed);
+        let positions = (0..n)
+            .map(|_| {
+                (0..config.dims)
+                    .map(|_| spread * 2.0f64.mul_add(rng.next_f64(), -1.0))
+                    .collect()
+            })
+            .collect();
+
+        let mut layout = Self {
+            config,
+            positions,
+            pinned: vec![false; n],
+            edges,
+            masses: Vec::new(),
+            previous_forces: vec![[0.0; MAX_DIMS]; n],
+            speed: 1.0,
+            speed_efficiency: 1.0,
+            iterations: 0,
+        };
+        layout.update_masses();
+        Ok(layout)
+    }
+
+    /// Number of nodes.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Whether the layout has no nodes.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Layout settings.
+    #[must_use]
+    pub const fn config(&self) -> &ForceLayoutConfig {
+        &self.config
+    }
+
+    /// Current position of each node.
+    #[must_use]
+    pub fn positions(&self) -> &[Vec<f64>] {
+        &self.positions
+    }
+
+    /// Number of iterations run so far.
+    #[must_use]
+    pub const fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Current global speed; it settles as the layout converges.
+    #[must_use]
+    pub const fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Move a node, e.g. while it is being dragged.
+    ///
+    /// # Errors
+    /// Returns error if the index is out of range, or the position has the wrong
+    /// length or a non-finite coordinate
+    pub fn set_position(&mut self, node: usize, position: &[f64]) -> Result<(), PluginError> {
+        self.check_node(node)?;
+        if position.len() != self.config.dims {
+            return Err(PluginError::InvalidVectorDimensions {
+                expected: self.config.dims,
+                got: position.len(),
+                vector_index: node,
+            });
+        }
+        check_finite("position", position)?;
+        self.positions[node].copy_from_slice(position);
+        self.previous_forces[node] = [0.0; MAX_DIMS];
+        Ok(())
+    }
+
+    /// Replace every position at once, e.g. to resume a saved layout.
+    ///
+    /// # Arguments
+    /// * `positions` - Flat array of `len() × dims` coordinates
+    ///
+    /// # Errors
+    /// Returns error if the array has the wrong length or a non-finite coordinate
+    pub fn set_positions(&mut self, positions: &[f64]) -> Result<(), PluginError> {
+        let dims = self.config.dims;
+        if positions.len() != self.len() * dims {
+            return Err(PluginError::ValidationError {
+                field: "positions".to_string(),
+                value: positions.len().to_string(),
+                reason: format!("Expected {} coordinates", self.len() * dims),
+            });
+        }
+        check_finite("positions", positions)?;
+        for (position, chunk) in self.positions.iter_mut().zip(positions.chunks_exact(dims)) {
+            position.copy_from_slice(chunk);
+        }
+        self.previous_forces.fill([0.0; MAX_DIMS]);
+        Ok(())
+    }
+
+    /// Pin or unpin a node. Pinned nodes do not move but still push and pull others.
+    ///
+    /// # Errors
+    /// Returns error if the index is out of range
+    pub fn set_pinned(&mut self, node: usize, pinned: bool) -> Result<(), PluginError> {
+        self.check_node(node)?;
+        self.pinned[node] = pinned;
+        Ok(())
+    }
+
+    /// Whether a node is pinned.
+    #[must_use]
+    pub fn is_pinned(&self, node: usize) -> bool {
+        self.pinned.get(node).copied().unwrap_or(false)
+    }
+
+    /// Change the gravity settings.
+    ///
+    /// # Errors
+    /// Returns error if the gravity is negative or not finite
+    pub fn set_gravity(&mut self, gravity: f64, strong_gravity: bool) -> Result<(), PluginError> {
+        validate_config(&ForceLayoutConfig { gravity, strong_gravity, ..self.config })?;
+        self.config.gravity = gravity;
+        self.config.strong_gravity = strong_gravity;
+        Ok(())
+    }
+
+    /// Set the weight of the undirected edge between two nodes.
+    ///
+    /// A weight of zero removes the edge; a missing edge is added.
+    ///
+    /// # Errors
+    /// Returns error if an index is out of range, the nodes are equal, or the
+    /// weight is negative or not finite
+    pub fn set_edge_weight(&mut self, a: usize, b: usize, weight: f64) -> Result<(), PluginError> {
+        self.check_node(a)?;
+        self.check_node(b)?;
+        if a == b || !weight.is_finite() || weight < 0.0 {
+            return Err(PluginError::ValidationError {
+                field: "weight".to_string(),
+                value: format!("{a}-{b}: {weight}"),
+                reason: "Edge weights must join two distinct nodes and be non-negative".to_string(),
+            });
+        }
+        let key = (a.min(b), a.max(b));
+        if weight == 0.0 {
+            self.edges.remove(&key);
+        } else {
+            self.edges.insert(key, weight);
+        }
+        self.update_masses();
+        Ok(())
+    }
+
+    /// Run layout iterations.
+    ///
+    /// # Arguments
+    /// * `iterations` - Number of iterations to run
+    pub fn step(&mut self, iterations: usize) {
+        for _ in 0..iterations {
+            self.iterate();
+        }
+    }
+
+    /// Current positions as a flat `len() × dims` array.
+    #[must_use]
+    pub fn flat_positions(&self) -> Vec<f64> {
+        self.positions.concat()
+    }
+
+    /// Run one iteration: compute forces, adapt the speed and move the nodes.
+    fn iterate(&mut self) {
+        if self.is_empty() {
+            return;
+        }
+        let forces = self.compute_forces();
+        let n = self.len();
+
+        let mut swinging = vec![0.0; n];
+        let mut total_swinging = 0.0;
+        let mut total_traction = 0.0;
+        for i in (0..n).filter(|&i| !self.pinned[i]) {
+            let (mut swing, mut traction) = (0.0, 0.0);
+            for (&now, &before) in forces[i].iter().zip(&self.previous_forces[i]) {
+                swing += (now - before) * (now - before);
+                traction += (now + before) * (now + before);
+            }
+            swinging[i] = self.masses[i] * swing.sqrt();
+            total_swinging += swinging[i];
+            total_traction += self.masses[i] * traction.sqrt() / 2.0;
+        }
+        self.adjust_speed(total_swinging, total_traction);
+
+        for i in (0..n).filter(|&i| !self.pinned[i]) {
+            let factor = self.speed / (1.0 + (self.speed * swinging[i]).sqrt());
+            for (p, f) in self.positions[i].iter_mut().zip(&forces[i]) {
+                *p += f * factor;
+            }
+        }
+        self.previous_forces = forces;
+        self.iterations += 1;
+    }
+
+    /// Repulsion, gravity and attraction acting on every node.
+    fn compute_forces(&self) -> Vec<[f64; MAX_DIMS]> {
+        let config = &self.config;
+        let tree = SpaceTree::build(&self.positions, &self.masses);
+
+        let mut forces: Vec<[f64; MAX_DIMS]> = self
+            .positions
+            .iter()
+            .enumerate()
+            .map(|(i, position)| {
+                let mass = self.masses[i];
+                let mut force = [0.0; MAX_DIMS];
+                tree.for_each_interaction(
+                    i,
+                    position,
+                    mass,
+                    config.theta,
+                    |other, offset, dist_sq| {
+                        if dist_sq > 0.0 {
+                            let strength = config.scaling * mass * other / dist_sq;
+                            for (f, o) in force.iter_mut().zip(offset) {
+                                *f += strength * o;
+                            }
+                        }
+                    },
+                );
+
+                let distance = position.iter().map(|p| p * p).sum::<f64>().sqrt();
+                let pull = if config.strong_gravity {
+                    config.gravity * mass
+                } else if distance > 0.0 {
+                    config.gravity * mass / distance
+                } else {
+                    0.0
+                };
+                for (f, p) in force.iter_mut().zip(position) {
+                    *f -= pull * p;
+                }
+                force
+            })
+            .collect();
+
+        for (&(a, b), &weight) in &self.edges {
+            let strength = weight.powf(config.edge_weight_influence);
+            for (d, (pa, pb)) in self.positions[a].iter().zip(&self.positions[b]).enumerate() {
+                let pull = strength * (pa - pb);
+                forces[a][d] -= pull;
+                forces[b][d] += pull;
+            }
+        }
+        forces
+    }
+
+    /// Adapt the global speed to how much the nodes swing versus make progress.
+    fn adjust_speed(&mut self, total_swinging: f64, total_traction: f64) {
+        #[allow(clippy::cast_precision_loss)]
+        let n = self.len() as f64;
+        let estimated_tolerance = 0.05 * n.sqrt();
+        let min_tolerance = estimated_tolerance.sqrt();
+        let mut jitter = self.config.jitter_tolerance
+            * (estimated_tolerance * total_traction / n.powi(2))
+                .clamp(min_tolerance, MAX_JITTER_TOLERANCE.max(min_tolerance));
+
+        if total_traction > 0.0 && total_swinging / total_traction > 2.0 {
+            if self.speed_efficiency > MIN_SPEED_EFFICIENCY {
+                self.speed_efficiency *= 0.5;
+            }
+            jitter = jitter.max(self.config.jitter_tolerance);
+        }
+
+        let target_speed = if total_swinging > 0.0 {
+            jitter * self.speed_efficiency * total_traction / total_swinging
+        } else {
+            f64::INFINITY
+        };
+
+        if total_swinging > jitter * total_traction {
+            if self.speed_efficiency > MIN_SPEED_EFFICIENCY {
+                self.speed_efficiency *= 0.7;
+            }
+        } else if self.speed < MAX_SPEED {
+            self.speed_efficiency *= 1.3;
+        }
+
+        // Nothing swings when every node is pinned, so the target is unbounded
+        self.speed = (self.speed + (target_speed - self.speed).min(MAX_SPEED_RISE * self.speed))
+            .min(MAX_SPEED);
+    }
+
+    /// Recompute node masses from the edge set.
+    fn update_masses(&mut self) {
+        self.masses = vec![1.0; self.len()];
+        for &(a, b) in self.edges.keys() {
+            self.masses[a] += 1.0;
+            self.masses[b] += 1.0;
+        }
+    }
+
+    /// Check that a node index is in range.
+    fn check_node(&self, node: usize) -> Result<(), PluginError> {
+        if node < self.len() {
+            Ok(())
+        } else {
+            Err(PluginError::ValidationError {
+                field: "node".to_string(),
+                value: node.to_string(),
+                reason: format!("Node index must be below {}", self.len()),
+            })
+        }
+    }
+}
+
+/// Check that every coordinate is finite; the space tree cannot bound infinite ones.
+fn check_finite(field: &str, coordinates: &[f64]) -> Result<(), PluginError> {
+    if let Some(index) = coordinates.iter().position(|c| !c.is_finite()) {
+        return Err(PluginError::ValidationError {
+            field: field.to_string(),
+            value: coordinates[index].to_string(),
+            reason: format!("Coordinate {index} must be finite"),
+        });
+    }
+    Ok(())
+}
+
+/// Check that layout settings are usable.
+fn validate_config(config: &ForceLayoutConfig) -> Result<(), PluginError> {
+    let invalid = |field: &str, value: String, reason: &str| PluginError::ValidationError {
+        field: field.to_string(),
+        value,
+        reason: reason.to_string(),
+    };
+    if !(2..=3).contains(&config.dims) {
+        return Err(invalid("dims", config.dims.to_string(), "Layout must be 2D or 3D"));
+    }
+    let non_negative = [
+        ("gravity", config.gravity),
+        ("scaling", config.scaling),
+        ("edgeWeightInfluence", config.edge_weight_influence),
+        ("theta", config.theta),
+        ("jitterTolerance", config.jitter_tolerance),
+    ];
+    for (field, value) in non_negative {
+        if !value.is_finite() || value < 0.0 {
+            return Err(invalid(field, value.to_string(), "Must be a non-negative number"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::graph;
+
+    fn distance(a: &[f64], b: &[f64]) -> f64 {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| (x - y) * (x - y))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    #[test]
+    fn test_linked_nodes_end_up_closer() {
+        // Two triangles joined by a single bridge
+        let adjacency = graph(6, &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)]);
+        let mut layout = ForceLayout::new(&adjacency, ForceLayoutConfig::default())
+            .expect("Failed to create layout");
+        layout.step(300);
+
+        let p = layout.positions();
+        let within = distance(&p[0], &p[1]).max(distance(&p[4], &p[5]));
+        let across = distance(&p[0], &p[5]);
+        assert!(within < across, "{within} vs {across}");
+        assert!(p.iter().flatten().all(|x| x.is_finite()));
+    }
+
+    #[test]
+    fn test_barnes_hut_matches_exact_forces() {
+        let edges: Vec<(usize, usize)> = (0..40).map(|i| (i, (i * 7 + 3) % 40)).collect();
+        let adjacency = graph(40, &edges);
+        let exact =
+            ForceLayout::new(&adjacency, ForceLayoutConfig { theta: 0.0, ..Default::default() })
+                .expect("Failed to create layout");
+        let approx =
+            ForceLayout::new(&adjacency, ForceLayoutConfig { theta: 0.5, ..Default::default() })
+                .expect("Failed to create layout");
+
+        for (e, a) in exact.compute_forces().iter().zip(approx.compute_forces()) {
+            let scale = e.iter().map(|x| x * x).sum::<f64>().sqrt();
+            assert!(distance(e, &a) < 0.05 * scale, "{e:?} vs {a:?}");
+        }
+    }
+
+    #[test]
+    fn test_pinned_nodes_do_not_move() {
+        let adjacency = graph(4, &[(0, 1), (1, 2), (2, 3)]);
+        let config = ForceLayoutConfig { dims: 3, ..Default::default() };
+        let mut layout = ForceLayout::new(&adjacency, config).expect("Failed to create layout");
+        layout
+            .set_position(1, &[5.0, -5.0, 1.0])
+            .expect("Failed to update layout");
+        layout.set_pinned(1, true).expect("Failed to update layout");
+        layout.step(50);
+
+        assert!(layout.is_pinned(1));
+        assert_eq!(layout.positions()[1], vec![5.0, -5.0, 1.0]);
+        assert_ne!(layout.positions()[0], layout.positions()[2]);
+    }
+
+    #[test]
+    fn test_speed_stays_bounded_while_all_nodes_are_pinned() {
+        let adjacency = graph(3, &[(0, 1), (1, 2)]);
+        let mut layout = ForceLayout::new(&adjacency, ForceLayoutConfig::default())
+            .expect("Failed to create layout");
+        for node in 0..3 {
+            layout
+                .set_pinned(node, true)
+                .expect("Failed to update layout");
+        }
+        layout.step(5000);
+        assert!(layout.speed <= MAX_SPEED);
+
+        layout
+            .set_pinned(0, false)
+            .expect("Failed to update layout");
+        layout.step(10);
+        assert!(layout.positions().iter().flatten().all(|x| x.is_finite()));
+    }
+
+    #[test]
+    fn test_heavier_edges_pull_harder() {
+        let adjacency = graph(3, &[(0, 1), (0, 2)]);
+        let mut layout = ForceLayout::new(&adjacency, ForceLayoutConfig::default())
+            .expect("Failed to create layout");
+        layout
+            .set_edge_weight(0, 1, 20.0)
+            .expect("Failed to update layout");
+        layout.step(300);
+
+        let p = layout.positions();
+        assert!(distance(&p[0], &p[1]) < distance(&p[0], &p[2]));
+    }
+
+    #[test]
+    fn test_gravity_keeps_disconnected_nodes_close() {
+        let adjacency = graph(20, &[]);
+        let mut weak = ForceLayout::new(&adjacency, ForceLayoutConfig::default())
+            .expect("Failed to create layout");
+        let mut strong = ForceLayout::new(&adjacency, ForceLayoutConfig::default())
+            .expect("Failed to create layout");
+        strong
+            .set_gravity(1.0, true)
+            .expect("Failed to update layout");
+        weak.step(200);
+        strong.step(200);
+
+        let radius = |layout: &ForceLayout| {
+            layout
+                .positions()
+                .iter()
+                .map(|p| p.iter().map(|x| x * x).sum::<f64>().sqrt())
+                .fold(0.0, f64::max)
+        };
+        assert!(radius(&strong) < radius(&weak));
+    }
+
+    #[test]
+    fn test_invalid_settings_are_rejected() {
+        let adjacency = graph(3, &[(0, 1)]);
+        let config = ForceLayoutConfig { dims: 4, ..Default::default() };
+        assert!(ForceLayout::new(&adjacency, config).is_err());
+
+        let mut layout = ForceLayout::new(&adjacency, ForceLayoutConfig::default())
+            .expect("Failed to create layout");
+        assert!(layout.set_gravity(-1.0, false).is_err());
+        assert!(layout.set_edge_weight(1, 1, 1.0).is_err());
+        assert!(layout.set_pinned(3, true).is_err());
+        assert!(layout.set_position(0, &[1.0]).is_err());
+        assert!(layout.set_position(0, &[0.0, f64::INFINITY, 0.0]).is_err());
+        assert!(layout.set_position(0, &[f64::NAN, 0.0, 0.0]).is_err());
+        let mut positions = layout.flat_positions();
+        positions[4] = f64::NEG_INFINITY;
+        assert!(layout.set_positions(&positions).is_err());
+    }
+}
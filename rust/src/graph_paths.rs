@@ -0,0 +1,302 @@
+//! Shortest link paths between notes.
+//!
+//! Answers "how are these two notes connected" with the fewest-hop path and
+//! loopless alternatives (Yen's algorithm), and finds every note within a
+//! number of hops for neighbourhood filters. Traversal runs on the CSR
+//! adjacency matrix; build it undirected to ignore link direction.
+
+use crate::{AdjacencyMatrixBuilder, NoteLink, PluginError};
+use serde::{Deserialize, Serialize};
+use sprs::CsMat;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+/// A note reachable from a source note.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NoteDistance {
+    /// Note path.
+    pub path: String,
+    /// Number of links followed from the source.
+    pub hops: usize,
+}
+
+/// Find the shortest link paths between two notes.
+///
+/// # Arguments
+/// * `builder` - Builder holding the vault's notes; build it undirected to ignore link direction
+/// * `links` - List of note links
+/// * `from` - Path of the note to start at
+/// * `to` - Path of the note to reach
+/// * `k` - Maximum number of paths to return
+///
+/// # Returns
+/// Up to `k` loopless paths as note paths, shortest first; empty if unreachable
+///
+/// # Errors
+/// Returns error if a path is not a known note or link indices are out of bounds
+pub fn connecting_paths(
+    builder: &AdjacencyMatrixBuilder,
+    links: Vec<NoteLink>,
+    from: &str,
+    to: &str,
+    k: usize,
+) -> Result<Vec<Vec<String>>, PluginError> {
+    let source = resolve_note(builder, from)?;
+    let target = resolve_note(builder, to)?;
+    let adjacency = builder.build(links)?;
+    let paths = builder.note_paths();
+
+    Ok(k_shortest_paths(&adjacency, source, target, k)
+        .into_iter()
+        .map(|route| route.into_iter().map(|node| paths[node].clone()).collect())
+        .collect())
+}
+
+/// Find every note within a number of hops of a note.
+///
+/// # Arguments
+/// * `builder` - Builder holding the vault's notes; build it undirected to ignore link direction
+/// * `links` - List of note links
+/// * `from` - Path of the note to start at
+/// * `max_hops` - Largest number of links to follow
+///
+/// # Returns
+/// Reachable notes other than the source, nearest first and then in note order
+///
+/// # Errors
+/// Returns error if the path is not a known note or link indices are out of bounds
+pub fn notes_within_hops(
+    builder: &AdjacencyMatrixBuilder,
+    links: Vec<NoteLink>,
+    from: &str,
+    max_hops: usize,
+) -> Result<Vec<NoteDistance>, PluginError> {
+    let source = resolve_note(builder, from)?;
+    let adjacency = builder.build(links)?;
+    let paths = builder.note_paths();
+
+    let mut reached: Vec<(usize, usize)> = bfs_distances(&adjacency, source, Some(max_hops))
+        .into_iter()
+        .enumerate()
+        .filter_map(|(node, hops)| hops.filter(|_| node != source).map(|hops| (hops, node)))
+        .collect();
+    reached.sort_unstable();
+    Ok(reached
+        .into_iter()
+        .map(|(hops, node)| NoteDistance { path: paths[node].clone(), hops })
+        .collect())
+}
+
+/// Breadth-first hop counts from a source node.
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix in CSR format
+/// * `source` - Node to start at
+/// * `max_hops` - Stop expanding beyond this many hops, if given
+///
+/// # Returns
+/// Hop count per node, `None` where unreachable within the limit
+#[must_use]
+pub fn bfs_distances(
+    adjacency: &CsMat<f64>,
+    source: usize,
+    max_hops: Option<usize>,
+) -> Vec<Option<usize>> {
+    let mut distances = vec![None; adjacency.rows()];
+    if source >= distances.len() {
+        return distances;
+    }
+    distances[source] = Some(0);
+    let mut queue = VecDeque::from([(source, 0)]);
+    while let Some((node, hops)) = queue.pop_front() {
+        if max_hops.is_some_and(|max| hops >= max) {
+            continue;
+        }
+        for next in successors(adjacency, node) {
+            if distances[next].is_none() {
+                distances[next] = Some(hops + 1);
+                queue.push_back((next, hops + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Fewest-hop path between two nodes.
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix in CSR format
+/// * `source` - Node to start at
+/// * `target` - Node to reach
+///
+/// # Returns
+/// The nodes along the path including both ends, or `None` if unreachable
+#[must_use]
+pub fn shortest_path(adjacency: &CsMat<f64>, source: usize, target: usize) -> Option<Vec<usize>> {
+    let n = adjacency.rows();
+    if source >= n || target >= n {
+        return None;
+    }
+    bfs_path(adjacency, source, target, &vec![false; n], &HashSet::new())
+}
+
+/// The k fewest-hop loopless paths between two nodes (Yen's algorithm).
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix in CSR format
+/// * `source` - Node to start at
+/// * `target` - Node to reach
+/// * `k` - Maximum number of paths
+///
+/// # Returns
+/// Up to `k` distinct paths in non-decreasing length, ties broken by node sequence
+#[must_use]
+pub fn k_shortest_paths(
+    adjacency: &CsMat<f64>,
+    source: usize,
+    target: usize,
+    k: usize,
+) -> Vec<Vec<usize>> {
+    let n = adjacency.rows();
+    let Some(first) = shortest_path(adjacency, source, target).filter(|_| k > 0) else {
+        return Vec::new();
+    };
+
+    let mut accepted = vec![first];
+    let mut candidates: BTreeSet<(usize, Vec<usize>)> = BTreeSet::new();
+    while accepted.len() < k {
+        let previous = &accepted[accepted.len() - 1];
+        for spur_index in 0..previous.len() - 1 {
+            let root = &previous[..=spur_index];
+
+            // Edges already taken from this root by accepted paths may not be reused
+            let blocked_edges: HashSet<(usize, usize)> = accepted
+                .iter()
+                .filter(|route| route.len() > spur_index + 1 && route[..=spur_index] == *root)
+                .map(|route| (route[spur_index], route[spur_index + 1]))
+                .collect();
+            let mut blocked_nodes = vec![false; n];
+            for &node in &root[..spur_index] {
+                blocked_nodes[node] = true;
+            }
+
+            if let Some(spur) =
+                bfs_path(adjacency, root[spur_index], target, &blocked_nodes, &blocked_edges)
+            {
+                let mut route = root[..spur_index].to_vec();
+                route.extend(spur);
+                candidates.insert((route.len(), route));
+            }
+        }
+
+        let Some((_, next)) = candidates.pop_first() else {
+            break;
+        };
+        accepted.push(next);
+    }
+    accepted
+}
+
+/// Breadth-first path search avoiding blocked nodes and edges.
+fn bfs_path(
+    adjacency: &CsMat<f64>,
+    source: usize,
+    target: usize,
+    blocked_nodes: &[bool],
+    blocked_edges: &HashSet<(usize, usize)>,
+) -> Option<Vec<usize>> {
+    let mut parent = vec![usize::MAX; adjacency.rows()];
+    parent[source] = source;
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        if node == target {
+            let mut route = vec![target];
+            while let Some(&last) = route.last()
+                && last != source
+            {
+                route.push(parent[last]);
+            }
+            route.reverse();
+            return Some(route);
+        }
+        for next in successors(adjacency, node) {
+            if parent[next] == usize::MAX
+                && !blocked_nodes[next]
+                && !blocked_edges.contains(&(node, next))
+            {
+                parent[next] = node;
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Nodes linked from `node`, in increasing order.
+fn successors(adjacency: &CsMat<f64>, node: usize) -> impl Iterator<Item = usize> + '_ {
+    adjacency
+        .outer_view(node)
+        .into_iter()
+        .flat_map(|row| row.into_raw_storage().0.iter().copied())
+}
+
+/// Resolve a note path to its index.
+fn resolve_note(builder: &AdjacencyMatrixBuilder, path: &str) -> Result<usize, PluginError> {
+    builder
+        .get_note_index(path)
+        .ok_or_else(|| PluginError::ValidationError {
+            field: "path".to_string(),
+            value: path.to_string(),
+            reason: "No note with this path".to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::graph;
+
+    #[test]
+    fn test_shortest_path_follows_direction() {
+        // 0 -> 1 -> 2 -> 3 plus a shortcut 0 -> 2, and 3 -> 0 back
+        let adjacency = graph(4, &[(0, 1), (1, 2), (2, 3), (0, 2), (3, 0)]);
+
+        assert_eq!(shortest_path(&adjacency, 0, 3), Some(vec![0, 2, 3]));
+        assert_eq!(shortest_path(&adjacency, 3, 2), Some(vec![3, 0, 2]));
+        assert_eq!(shortest_path(&adjacency, 1, 1), Some(vec![1]));
+        assert_eq!(shortest_path(&graph(3, &[(0, 1)]), 1, 0), None);
+    }
+
+    #[test]
+    fn test_k_shortest_paths_shortest_first() {
+        // 0 -> 1 -> 3, 0 -> 2 -> 3, 0 -> 1 -> 2 and a long detour 0 -> 4 -> 5 -> 3
+        let adjacency = graph(6, &[(0, 1), (1, 3), (0, 2), (2, 3), (1, 2), (0, 4), (4, 5), (5, 3)]);
+        let paths = k_shortest_paths(&adjacency, 0, 3, 10);
+
+        assert_eq!(paths, vec![vec![0, 1, 3], vec![0, 2, 3], vec![0, 1, 2, 3], vec![0, 4, 5, 3]]);
+        assert_eq!(k_shortest_paths(&adjacency, 0, 3, 2).len(), 2);
+        assert!(k_shortest_paths(&adjacency, 3, 0, 3).is_empty());
+    }
+
+    #[test]
+    fn test_bfs_distances_with_hop_limit() {
+        let adjacency = graph(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+
+        assert_eq!(
+            bfs_distances(&adjacency, 0, None),
+            vec![Some(0), Some(1), Some(2), Some(3), Some(4)]
+        );
+        assert_eq!(
+            bfs_distances(&adjacency, 0, Some(2)),
+            vec![Some(0), Some(1), Some(2), None, None]
+        );
+        assert_eq!(bfs_distances(&adjacency, 4, None)[0], None);
+    }
+
+    #[test]
+    fn test_unknown_note_is_rejected() {
+        let builder = AdjacencyMatrixBuilder::new(vec!["a.md".to_string()]);
+        let result = connecting_paths(&builder, Vec::new(), "a.md", "missing.md", 1);
+
+        assert!(matches!(result, Err(PluginError::ValidationError { .. })));
+    }
+}
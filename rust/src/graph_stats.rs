@@ -0,0 +1,507 @@
+//! Structural statistics of the note link graph.
+//!
+//! Summarises a vault's links in one report: size and density, degree
+//! distributions, orphans and dead ends, connectivity, clustering,
+//! reciprocity and an estimate of the diameter.
+
+use crate::{AdjacencyMatrixBuilder, NoteLink, PluginError, connected_components};
+use serde::{Deserialize, Serialize};
+use sprs::CsMat;
+use std::collections::BTreeMap;
+
+/// Number of breadth-first sweeps used to estimate the diameter.
+const DIAMETER_SWEEPS: usize = 4;
+
+/// Health report for a vault's link graph.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphStats {
+    /// Number of notes.
+    pub node_count: usize,
+    /// Number of distinct directed links between different notes.
+    pub edge_count: usize,
+    /// Number of notes that link to themselves.
+    pub self_loop_count: usize,
+    /// Fraction of possible directed links that exist.
+    pub density: f64,
+    /// Distribution of the number of distinct notes linking to each note.
+    pub in_degree: DegreeDistribution,
+    /// Distribution of the number of distinct notes each note links to.
+    pub out_degree: DegreeDistribution,
+    /// Notes with neither incoming nor outgoing links.
+    pub orphans: Vec<String>,
+    /// Notes that are linked to but link nowhere.
+    pub dead_ends: Vec<String>,
+    /// Components when link direction is ignored.
+    pub weak_components: ComponentSummary,
+    /// Components in which every note can reach every other along links.
+    pub strong_components: ComponentSummary,
+    /// Mean local clustering coefficient of the undirected graph.
+    pub average_clustering: f64,
+    /// Fraction of links whose reverse link also exists.
+    pub reciprocity: f64,
+    /// Lower bound on the longest shortest path, ignoring direction.
+    pub diameter_estimate: usize,
+}
+
+/// Summary of a degree sequence.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DegreeDistribution {
+    /// Smallest degree.
+    pub min: usize,
+    /// Largest degree.
+    pub max: usize,
+    /// Mean degree.
+    pub mean: f64,
+    /// Median degree.
+    pub median: f64,
+    /// Number of notes per degree, in increasing degree order.
+    pub histogram: Vec<DegreeCount>,
+}
+
+/// One bucket of a degree histogram.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DegreeCount {
+    /// The degree.
+    pub degree: usize,
+    /// Number of notes with this degree.
+    pub count: usize,
+}
+
+/// Number and sizes of a graph's components.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ComponentSummary {
+    /// Number of components.
+    pub count: usize,
+    /// Component sizes, largest first.
+    pub sizes: Vec<usize>,
+}
+
+/// Compute a statistics report for a vault's link graph.
+///
+/// Links are counted once per ordered pair of notes; duplicate links and
+/// self-links do not affect degrees, components or clustering.
+///
+/// # Arguments
+/// * `note_paths` - List of note paths in the vault
+/// * `links` - List of note links
+///
+/// # Returns
+/// The report, with orphans and dead ends listed by path
+///
+/// # Errors
+/// Returns error if link indices are out of bounds
+pub fn compute_graph_stats(
+    note_paths: &[String],
+    links: Vec<NoteLink>,
+) -> Result<GraphStats, PluginError> {
+    let adjacency = AdjacencyMatrixBuilder::new(note_paths.to_vec()).build(links)?;
+    let n = note_paths.len();
+
+    let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut incoming: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut self_loop_count = 0;
+    for (i, row) in adjacency.outer_iterator().enumerate() {
+        for (j, &w) in row.iter() {
+            if w == 0.0 {
+                continue;
+            }
+            if i == j {
+                self_loop_count += 1;
+            } else {
+                outgoing[i].push(j);
+                incoming[j].push(i);
+            }
+        }
+    }
+    let neighbors: Vec<Vec<usize>> = outgoing
+        .iter()
+        .zip(&incoming)
+        .map(|(out, inc)| {
+            let mut all: Vec<usize> = out.iter().chain(inc).copied().collect();
+            all.sort_unstable();
+            all.dedup();
+            all
+        })
+        .collect();
+
+    let edge_count: usize = outgoing.iter().map(Vec::len).sum();
+    #[allow(clippy::cast_precision_loss)]
+    let density = if n > 1 {
+        edge_count as f64 / (n * (n - 1)) as f64
+    } else {
+        0.0
+    };
+    let reciprocated = outgoing
+        .iter()
+        .enumerate()
+        .flat_map(|(i, out)| out.iter().map(move |&j| (i, j)))
+        .filter(|&(i, j)| outgoing[j].binary_search(&i).is_ok())
+        .count();
+    #[allow(clippy::cast_precision_loss)]
+    let reciprocity = if edge_count > 0 {
+        reciprocated as f64 / edge_count as f64
+    } else {
+        0.0
+    };
+
+    let orphans = (0..n)
+        .filter(|&i| neighbors[i].is_empty())
+        .map(|i| note_paths[i].clone())
+        .collect();
+    let dead_ends = (0..n)
+        .filter(|&i| outgoing[i].is_empty() && !incoming[i].is_empty())
+        .map(|i| note_paths[i].clone())
+        .collect();
+
+    let weak_labels = connected_components(&adjacency);
+    let diameter_estimate = estimate_diameter(&neighbors, &weak_labels);
+
+    Ok(GraphStats {
+        node_count: n,
+        edge_count,
+        self_loop_count,
+        density,
+        in_degree: DegreeDistribution::from_degrees(incoming.iter().map(Vec::len).collect()),
+        out_degree: DegreeDistribution::from_degrees(outgoing.iter().map(Vec::len).collect()),
+        orphans,
+        dead_ends,
+        weak_components: ComponentSummary::from_labels(&weak_labels),
+        strong_components: ComponentSummary::from_labels(&strongly_connected_components(
+            &adjacency,
+        )),
+        average_clustering: average_clustering(&neighbors),
+        reciprocity,
+        diameter_estimate,
+    })
+}
+
+impl DegreeDistribution {
+    /// Summarise a degree per node.
+    fn from_degrees(mut degrees: Vec<usize>) -> Self {
+        degrees.sort_unstable();
+        let mut histogram: BTreeMap<usize, usize> = BTreeMap::new();
+        for &degree in &degrees {
+            *histogram.entry(degree).or_insert(0) += 1;
+        }
+
+        let n = degrees.len();
+        #[allow(clippy::cast_precision_loss)]
+        let (mean, median) = if n == 0 {
+            (0.0, 0.0)
+        } else {
+            let mean = degrees.iter().sum::<usize>() as f64 / n as f64;
+            let median = if n % 2 == 1 {
+                degrees[n / 2] as f64
+            } else {
+                f64::midpoint(degrees[n / 2 - 1] as f64, degrees[n / 2] as f64)
+            };
+            (mean, median)
+        };
+
+        Self {
+            min: degrees.first().copied().unwrap_or(0),
+            max: degrees.last().copied().unwrap_or(0),
+            mean,
+            median,
+            histogram: histogram
+                .into_iter()
+                .map(|(degree, count)| DegreeCount { degree, count })
+                .collect(),
+        }
+    }
+}
+
+impl ComponentSummary {
+    /// Summarise component labels numbered from 0 by decreasing size.
+    fn from_labels(labels: &[usize]) -> Self {
+        let count = labels.iter().max().map_or(0, |&max| max + 1);
+        let mut sizes = vec![0; count];
+        for &label in labels {
+            sizes[label] += 1;
+        }
+        Self { count, sizes }
+    }
+}
+
+/// Label the strongly connected components of a directed graph (Tarjan).
+///
+/// # Arguments
+/// * `adjacency` - Square sparse adjacency matrix
+///
+/// # Returns
+/// Component id per node, numbered from 0 by decreasing size (ties by first node)
+#[must_use]
+pub fn strongly_connected_components(adjacency: &CsMat<f64>) -> Vec<usize> {
+    let n = adjacency.rows();
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (i, row) in adjacency.outer_iterator().enumerate() {
+        for (j, &w) in row.iter() {
+            if w != 0.0 {
+                let (from, to) = if adjacency.is_csr() { (i, j) } else { (j, i) };
+                successors[from].push(to);
+            }
+        }
+    }
+
+    let mut index = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    let mut counter = 0;
+
+    for root in 0..n {
+        if index[root] != usize::MAX {
+            continue;
+        }
+        // Explicit call stack of (node, next successor position) keeps deep
+        // link chains from overflowing the wasm stack
+        index[root] = counter;
+        low[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        let mut calls = vec![(root, 0)];
+
+        while let Some(frame) = calls.last_mut() {
+            let node = frame.0;
+            if let Some(&next) = successors[node].get(frame.1) {
+                frame.1 += 1;
+                if index[next] == usize::MAX {
+                    index[next] = counter;
+                    low[next] = counter;
+                    counter += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    calls.push((next, 0));
+                } else if on_stack[next] {
+                    low[node] = low[node].min(index[next]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+            if low[node] == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    for component in &mut components {
+        component.sort_unstable();
+    }
+    components.sort_by_key(|component| (std::cmp::Reverse(component.len()), component[0]));
+    let mut labels = vec![0; n];
+    for (label, component) in components.iter().enumerate() {
+        for &node in component {
+            labels[node] = label;
+        }
+    }
+    labels
+}
+
+/// Mean over all nodes of the fraction of neighbour pairs that are linked.
+///
+/// Nodes with fewer than two neighbours count as zero.
+fn average_clustering(neighbors: &[Vec<usize>]) -> f64 {
+    if neighbors.is_empty() {
+        return 0.0;
+    }
+    let mut marked = vec![false; neighbors.len()];
+    let mut total = 0.0;
+    for adjacent in neighbors {
+        let degree = adjacent.len();
+        if degree < 2 {
+            continue;
+        }
+        for &v in adjacent {
+            marked[v] = true;
+        }
+        // Each triangle through this node is seen from both of its other corners
+        let closed: usize = adjacent
+            .iter()
+            .map(|&v| neighbors[v].iter().filter(|&&w| marked[w]).count())
+            .sum();
+        for &v in adjacent {
+            marked[v] = false;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let coefficient = closed as f64 / (degree * (degree - 1)) as f64;
+        total += coefficient;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let n = neighbors.len() as f64;
+    total / n
+}
+
+/// Estimate the undirected diameter with repeated breadth-first sweeps.
+///
+/// Each sweep starts from the farthest node found by the previous one,
+/// beginning at the best-connected node of every component. The result is a
+/// lower bound that is exact for trees and usually tight in practice.
+fn estimate_diameter(neighbors: &[Vec<usize>], components: &[usize]) -> usize {
+    let mut starts: BTreeMap<usize, usize> = BTreeMap::new();
+    for (node, &component) in components.iter().enumerate() {
+        let start = starts.entry(component).or_insert(node);
+        if neighbors[node].len() > neighbors[*start].len() {
+            *start = node;
+        }
+    }
+
+    let mut diameter = 0;
+    let mut distances = vec![usize::MAX; neighbors.len()];
+    for &start in starts.values() {
+        let mut source = start;
+        for _ in 0..DIAMETER_SWEEPS {
+            let (farthest, eccentricity) = bfs_farthest(neighbors, source, &mut distances);
+            if eccentricity <= diameter && source != start {
+                break;
+            }
+            diameter = diameter.max(eccentricity);
+            source = farthest;
+        }
+    }
+    diameter
+}
+
+/// Breadth-first search returning the farthest node and its distance.
+fn bfs_farthest(
+    neighbors: &[Vec<usize>],
+    source: usize,
+    distances: &mut [usize],
+) -> (usize, usize) {
+    distances.fill(usize::MAX);
+    distances[source] = 0;
+    let mut queue = std::collections::VecDeque::from([source]);
+    let mut farthest = (source, 0);
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[node];
+        if distance > farthest.1 {
+            farthest = (node, distance);
+        }
+        for &next in &neighbors[node] {
+            if distances[next] == usize::MAX {
+                distances[next] = distance + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+    farthest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn links(pairs: &[(usize, usize)]) -> Vec<NoteLink> {
+        pairs
+            .iter()
+            .map(|&(from_id, to_id)| NoteLink { from_id, to_id })
+            .collect()
+    }
+
+    fn paths(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("note{i}.md")).collect()
+    }
+
+    #[test]
+    fn test_counts_density_and_reciprocity() {
+        // 0 <-> 1, 1 -> 2, 2 -> 2 (self-link), duplicate 0 -> 1
+        let stats =
+            compute_graph_stats(&paths(3), links(&[(0, 1), (1, 0), (1, 2), (2, 2), (0, 1)]))
+                .expect("Failed to compute stats");
+
+        assert_eq!(stats.node_count, 3);
+        assert_eq!(stats.edge_count, 3);
+        assert_eq!(stats.self_loop_count, 1);
+        assert!((stats.density - 0.5).abs() < 1e-12);
+        assert!((stats.reciprocity - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(stats.dead_ends, vec!["note2.md"]);
+        assert!(stats.orphans.is_empty());
+    }
+
+    #[test]
+    fn test_degree_distribution() {
+        // A hub linking to four leaves, one isolated note
+        let stats = compute_graph_stats(&paths(6), links(&[(0, 1), (0, 2), (0, 3), (0, 4)]))
+            .expect("Failed to compute stats");
+
+        assert_eq!(stats.out_degree.max, 4);
+        assert_eq!(stats.out_degree.min, 0);
+        assert!((stats.out_degree.mean - 4.0 / 6.0).abs() < 1e-12);
+        assert!(stats.out_degree.median.abs() < 1e-12);
+        assert_eq!(
+            stats.in_degree.histogram,
+            vec![DegreeCount { degree: 0, count: 2 }, DegreeCount { degree: 1, count: 4 }]
+        );
+        assert_eq!(stats.orphans, vec!["note5.md"]);
+        assert_eq!(stats.dead_ends.len(), 4);
+    }
+
+    #[test]
+    fn test_strong_and_weak_components() {
+        // Cycle 0 -> 1 -> 2 -> 0 feeding into cycle 3 <-> 4, then 5; 6 is isolated
+        let adjacency = AdjacencyMatrixBuilder::new(paths(7))
+            .build(links(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (4, 5)]))
+            .expect("Failed to build matrix");
+
+        assert_eq!(strongly_connected_components(&adjacency), vec![0, 0, 0, 1, 1, 2, 3]);
+        let stats = compute_graph_stats(
+            &paths(7),
+            links(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (4, 5)]),
+        )
+        .expect("Failed to compute stats");
+        assert_eq!(stats.strong_components.sizes, vec![3, 2, 1, 1]);
+        assert_eq!(stats.weak_components, ComponentSummary { count: 2, sizes: vec![6, 1] });
+    }
+
+    #[test]
+    fn test_clustering_coefficient() {
+        // Triangle 0-1-2 with a pendant node 3 attached to 0
+        let stats = compute_graph_stats(&paths(4), links(&[(0, 1), (1, 2), (2, 0), (0, 3)]))
+            .expect("Failed to compute stats");
+
+        // Node 0: 1 of 3 pairs linked; nodes 1 and 2: 1 of 1; node 3: 0
+        let expected = (1.0 / 3.0 + 1.0 + 1.0) / 4.0;
+        assert!((stats.average_clustering - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_diameter_of_path_and_cycle() {
+        let chain: Vec<(usize, usize)> = (0..9).map(|i| (i, i + 1)).collect();
+        let stats =
+            compute_graph_stats(&paths(10), links(&chain)).expect("Failed to compute stats");
+        assert_eq!(stats.diameter_estimate, 9);
+
+        let ring: Vec<(usize, usize)> = (0..10).map(|i| (i, (i + 1) % 10)).collect();
+        let stats = compute_graph_stats(&paths(10), links(&ring)).expect("Failed to compute stats");
+        assert_eq!(stats.diameter_estimate, 5);
+    }
+
+    #[test]
+    fn test_empty_vault() {
+        let stats = compute_graph_stats(&[], Vec::new()).expect("Failed to compute stats");
+        assert_eq!(stats.node_count, 0);
+        assert!(stats.density.abs() < f64::EPSILON);
+        assert_eq!(stats.weak_components.count, 0);
+        assert_eq!(stats.diameter_estimate, 0);
+    }
+
+    #[test]
+    fn test_invalid_link_is_rejected() {
+        assert!(compute_graph_stats(&paths(2), links(&[(0, 5)])).is_err());
+    }
+}
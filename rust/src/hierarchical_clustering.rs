@@ -0,0 +1,457 @@
+//! Agglomerative hierarchical clustering.
+//!
+//! Builds the full merge tree (dendrogram) with the nearest-neighbour chain
+//! algorithm and Lance-Williams distance updates, so one run can be cut at any
+//! granularity: a few top-level topics or many fine subtopics. The dendrogram
+//! uses the same flat layout as `SciPy`'s linkage matrix and serialises to JSON.
+//!
+//! Time is quadratic in the number of vectors, and so is memory: the pairwise
+//! distances are kept in a condensed matrix.
+
+use crate::union_find::find_root;
+use crate::{DistanceMetric, PluginError, non_negative_distance};
+use serde::{Deserialize, Serialize};
+
+/// How the distance between two clusters is derived from point distances.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Linkage {
+    /// Distance between the closest pair of points.
+    Single,
+    /// Distance between the farthest pair of points.
+    Complete,
+    /// Mean distance over all pairs of points (UPGMA).
+    Average,
+    /// Increase in within-cluster variance caused by the merge.
+    Ward,
+}
+
+impl Linkage {
+    /// Parse a linkage name (`"single"`, `"complete"`, `"average"` or `"ward"`).
+    ///
+    /// # Errors
+    /// Returns error if the name is unknown
+    pub fn from_name(name: &str) -> Result<Self, PluginError> {
+        match name.to_lowercase().as_str() {
+            "single" => Ok(Self::Single),
+            "complete" => Ok(Self::Complete),
+            "average" => Ok(Self::Average),
+            "ward" => Ok(Self::Ward),
+            _ => Err(PluginError::ValidationError {
+                field: "linkage".to_string(),
+                value: name.to_string(),
+                reason: "Unknown linkage".to_string(),
+            }),
+        }
+    }
+
+    /// Lance-Williams update: distance from the merge of `x` and `y` to `k`.
+    fn update(self, x_to_k: f64, y_to_k: f64, x_to_y: f64, sizes: (usize, usize, usize)) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        let (n_x, n_y, n_k) = (sizes.0 as f64, sizes.1 as f64, sizes.2 as f64);
+        match self {
+            Self::Single => x_to_k.min(y_to_k),
+            Self::Complete => x_to_k.max(y_to_k),
+            Self::Average => n_x.mul_add(x_to_k, n_y * y_to_k) / (n_x + n_y),
+            Self::Ward => {
+                let joined = (n_x + n_k).mul_add(x_to_k.powi(2), (n_y + n_k) * y_to_k.powi(2));
+                let spread = (-n_k).mul_add(x_to_y.powi(2), joined);
+                (spread / (n_x + n_y + n_k)).max(0.0).sqrt()
+            },
+        }
+    }
+}
+
+/// One merge in a dendrogram.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DendrogramMerge {
+    /// First merged node: a leaf below `leafCount`, otherwise the cluster
+    /// created by merge `left - leafCount`.
+    pub left: usize,
+    /// Second merged node, numbered like `left`.
+    pub right: usize,
+    /// Linkage distance at which the two nodes merge.
+    pub height: f64,
+    /// Number of leaves under the new node.
+    pub size: usize,
+}
+
+/// The full merge tree of an agglomerative clustering.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Dendrogram {
+    /// Number of clustered vectors (leaves); one more than the number of merges.
+    pub leaf_count: usize,
+    /// Merges in non-decreasing height; merge `i` creates node `leafCount + i`.
+    pub merges: Vec<DendrogramMerge>,
+}
+
+/// Where to cut a dendrogram.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum DendrogramCut {
+    /// Apply every merge at or below this height.
+    Height(f64),
+    /// Merge until this many clusters remain.
+    Clusters(usize),
+}
+
+impl Dendrogram {
+    /// Flat clusters from cutting the tree.
+    ///
+    /// # Arguments
+    /// * `cut` - Height or number of clusters to cut at
+    ///
+    /// # Returns
+    /// Cluster label per leaf, numbered in order of each cluster's first leaf
+    ///
+    /// # Errors
+    /// Returns error if the cluster count is out of range or the merges are malformed
+    pub fn cut(&self, cut: DendrogramCut) -> Result<Vec<usize>, PluginError> {
+        match cut {
+            DendrogramCut::Height(height) => self.cut_at_height(height),
+            DendrogramCut::Clusters(count) => self.cut_into_clusters(count),
+        }
+    }
+
+    /// Flat clusters from applying every merge at or below a height.
+    ///
+    /// # Arguments
+    /// * `height` - Largest merge height to apply
+    ///
+    /// # Returns
+    /// Cluster label per leaf, numbered in order of each cluster's first leaf
+    ///
+    /// # Errors
+    /// Returns error if the merges are malformed
+    pub fn cut_at_height(&self, height: f64) -> Result<Vec<usize>, PluginError> {
+        self.check_leaf_count()?;
+        let applied = self
+            .merges
+            .iter()
+            .take_while(|merge| merge.height <= height)
+            .count();
+        self.labels_after(applied)
+    }
+
+    /// Flat clusters from merging until a given number of clusters remain.
+    ///
+    /// # Arguments
+    /// * `count` - Number of clusters, between 1 and the number of leaves
+    ///
+    /// # Returns
+    /// Cluster label per leaf, numbered in order of each cluster's first leaf
+    ///
+    /// # Errors
+    /// Returns error if the count is out of range or the merges are malformed
+    pub fn cut_into_clusters(&self, count: usize) -> Result<Vec<usize>, PluginError> {
+        self.check_leaf_count()?;
+        if count == 0 || count > self.leaf_count {
+            return Err(PluginError::ValidationError {
+                field: "clusters".to_string(),
+                value: count.to_string(),
+                reason: format!("Cluster count must be between 1 and {}", self.leaf_count),
+            });
+        }
+        self.labels_after((self.leaf_count - count).min(self.merges.len()))
+    }
+
+    /// Check that the leaf count matches the merges.
+    ///
+    /// A dendrogram over n leaves has exactly n - 1 merges. Checking this before
+    /// allocating per-leaf state keeps a deserialised `leafCount` from requesting
+    /// more memory than its merges justify.
+    fn check_leaf_count(&self) -> Result<(), PluginError> {
+        if self.leaf_count == self.merges.len() + 1 {
+            Ok(())
+        } else {
+            Err(PluginError::ValidationError {
+                field: "leafCount".to_string(),
+                value: self.leaf_count.to_string(),
+                reason: format!(
+                    "Expected {} for {} merges",
+                    self.merges.len() + 1,
+                    self.merges.len()
+                ),
+            })
+        }
+    }
+
+    /// Labels after applying the first `applied` merges.
+    fn labels_after(&self, applied: usize) -> Result<Vec<usize>, PluginError> {
+        let n = self.leaf_count;
+        let mut parent: Vec<usize> = (0..n).collect();
+        // A leaf under each node created so far
+        let mut representative: Vec<usize> = (0..n).collect();
+        for (i, merge) in self.merges[..applied].iter().enumerate() {
+            let node_count = n + i;
+            if merge.left >= node_count || merge.right >= node_count || merge.left == merge.right {
+                return Err(PluginError::ValidationError {
+                    field: "merges".to_string(),
+                    value: i.to_string(),
+                    reason: "Merge refers to a node that does not exist yet".to_string(),
+                });
+            }
+            let left = find_root(&mut parent, representative[merge.left]);
+            let right = find_root(&mut parent, representative[merge.right]);
+            parent[right] = left;
+            representative.push(left);
+        }
+
+        let mut label_of_root = vec![usize::MAX; n];
+        let mut next_label = 0;
+        let mut labels = Vec::with_capacity(n);
+        for leaf in 0..n {
+            let root = find_root(&mut parent, leaf);
+            if label_of_root[root] == usize::MAX {
+                label_of_root[root] = next_label;
+                next_label += 1;
+            }
+            labels.push(label_of_root[root]);
+        }
+        Ok(labels)
+    }
+}
+
+/// Cluster vectors agglomeratively and return the full merge tree.
+///
+/// # Arguments
+/// * `vectors` - Input vectors to cluster
+/// * `linkage` - Cluster distance rule
+/// * `metric` - Distance metric between vectors; Ward linkage assumes Euclidean
+///   geometry and is only approximate for other metrics
+///
+/// # Returns
+/// The dendrogram, with merges in non-decreasing height
+///
+/// # Errors
+/// Returns error if there are no vectors or vectors have mismatched dimensions
+pub fn agglomerative_clustering(
+    vectors: &[Vec<f64>],
+    linkage: Linkage,
+    metric: &dyn DistanceMetric,
+) -> Result<Dendrogram, PluginError> {
+    if vectors.is_empty() {
+        return Err(PluginError::InsufficientData { required: 1, provided: 0 });
+    }
+    let n = vectors.len();
+    let mut distances = CondensedMatrix::new(n);
+    for a in 0..n {
+        for b in a + 1..n {
+            distances.set(a, b, non_negative_distance(metric, &vectors[a], &vectors[b])?);
+        }
+    }
+
+    let mut pairs = nearest_neighbour_chain(&mut distances, linkage)?;
+    pairs.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+    // Replace slot indices with dendrogram node ids
+    let mut parent: Vec<usize> = (0..n).collect();
+    let mut node_of_root: Vec<usize> = (0..n).collect();
+    let mut size = vec![1; n];
+    let mut merges = Vec::with_capacity(n - 1);
+    for (a, b, height) in pairs {
+        let (root_a, root_b) = (find_root(&mut parent, a), find_root(&mut parent, b));
+        let (left, right) = {
+            let (x, y) = (node_of_root[root_a], node_of_root[root_b]);
+            (x.min(y), x.max(y))
+        };
+        parent[root_b] = root_a;
+        size[root_a] += size[root_b];
+        node_of_root[root_a] = n + merges.len();
+        merges.push(DendrogramMerge { left, right, height, size: size[root_a] });
+    }
+    Ok(Dendrogram { leaf_count: n, merges })
+}
+
+/// Upper triangle of a symmetric distance matrix, without the diagonal.
+struct CondensedMatrix {
+    /// Number of rows.
+    n: usize,
+    /// Entries `(a, b)` for `a < b`, row by row.
+    values: Vec<f64>,
+}
+
+impl CondensedMatrix {
+    fn new(n: usize) -> Self {
+        Self { n, values: vec![0.0; n * n.saturating_sub(1) / 2] }
+    }
+
+    const fn index(&self, a: usize, b: usize) -> usize {
+        let (a, b) = if a < b { (a, b) } else { (b, a) };
+        a * (2 * self.n - a - 1) / 2 + (b - a - 1)
+    }
+
+    fn get(&self, a: usize, b: usize) -> f64 {
+        self.values[self.index(a, b)]
+    }
+
+    fn set(&mut self, a: usize, b: usize, value: f64) {
+        let index = self.index(a, b);
+        self.values[index] = value;
+    }
+}
+
+/// Merge reciprocal nearest neighbours until one cluster remains.
+///
+/// # Returns
+/// Merged slot pairs with their heights, in the order found (not sorted); the
+/// merged cluster keeps the second slot
+///
+/// # Errors
+/// Returns error if a chain runs out of active clusters, which a well-formed
+/// matrix never does
+fn nearest_neighbour_chain(
+    distances: &mut CondensedMatrix,
+    linkage: Linkage,
+) -> Result<Vec<(usize, usize, f64)>, PluginError> {
+    let n = distances.n;
+    let mut active = vec![true; n];
+    let mut size = vec![1; n];
+    let mut chain: Vec<usize> = Vec::with_capacity(n);
+    let mut pairs = Vec::with_capacity(n.saturating_sub(1));
+
+    for _ in 1..n {
+        if chain.is_empty() {
+            chain.extend(active.iter().position(|&a| a));
+        }
+        let (x, y) = loop {
+            let x = chain[chain.len() - 1];
+            let previous = chain.len().checked_sub(2).map(|i| chain[i]);
+            // Prefer the previous chain element on ties so the chain always terminates
+            let mut nearest = previous.map(|p| (p, distances.get(x, p)));
+            for k in (0..n).filter(|&k| active[k] && k != x) {
+                let dist = distances.get(x, k);
+                if nearest.is_none_or(|(_, best)| dist < best) {
+                    nearest = Some((k, dist));
+                }
+            }
+            let Some((z, _)) = nearest else {
+                return Err(PluginError::InsufficientData {
+                    required: 2,
+                    provided: active.iter().filter(|&&a| a).count(),
+                });
+            };
+            if Some(z) == previous {
+                chain.truncate(chain.len() - 2);
+                break (x, z);
+            }
+            chain.push(z);
+        };
+
+        let d_xy = distances.get(x, y);
+        pairs.push((x, y, d_xy));
+        active[x] = false;
+        for k in (0..n).filter(|&k| active[k] && k != y) {
+            let updated = linkage.update(
+                distances.get(x, k),
+                distances.get(y, k),
+                d_xy,
+                (size[x], size[y], size[k]),
+            );
+            distances.set(y, k, updated);
+        }
+        size[y] += size[x];
+    }
+    Ok(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Euclidean;
+
+    fn heights(dendrogram: &Dendrogram) -> Vec<f64> {
+        dendrogram.merges.iter().map(|merge| merge.height).collect()
+    }
+
+    #[test]
+    fn test_linkage_heights_on_a_line() {
+        let vectors = vec![vec![0.0], vec![1.0], vec![5.0]];
+        let expected = [
+            (Linkage::Single, 4.0),
+            (Linkage::Complete, 5.0),
+            (Linkage::Average, 4.5),
+            // sqrt(2 * 2 * 1 / 3) * |0.5 - 5|
+            (Linkage::Ward, 27.0_f64.sqrt()),
+        ];
+        for (linkage, top) in expected {
+            let dendrogram =
+                agglomerative_clustering(&vectors, linkage, &Euclidean).expect("Clustering failed");
+
+            assert_eq!(
+                dendrogram.merges[0],
+                DendrogramMerge { left: 0, right: 1, height: 1.0, size: 2 }
+            );
+            assert_eq!((dendrogram.merges[1].left, dendrogram.merges[1].right), (2, 3));
+            assert!((dendrogram.merges[1].height - top).abs() < 1e-12, "{linkage:?}");
+        }
+    }
+
+    #[test]
+    fn test_merges_are_sorted_and_complete() {
+        let vectors: Vec<Vec<f64>> = [0.0, 10.0, 0.5, 10.4, 3.0, 20.0, 9.0]
+            .iter()
+            .map(|&x| vec![x, x / 2.0])
+            .collect();
+        for linkage in [Linkage::Single, Linkage::Complete, Linkage::Average, Linkage::Ward] {
+            let dendrogram =
+                agglomerative_clustering(&vectors, linkage, &Euclidean).expect("Clustering failed");
+
+            assert_eq!(dendrogram.merges.len(), 6);
+            assert!(
+                heights(&dendrogram)
+                    .windows(2)
+                    .all(|pair| pair[0] <= pair[1])
+            );
+            assert_eq!(dendrogram.merges[5].size, 7);
+            assert_eq!(dendrogram.cut_into_clusters(1).expect("Cut failed"), vec![0; 7]);
+        }
+    }
+
+    #[test]
+    fn test_cuts_by_height_and_count() {
+        let vectors = vec![vec![0.0], vec![10.0], vec![0.5], vec![10.4], vec![30.0]];
+        let dendrogram = agglomerative_clustering(&vectors, Linkage::Single, &Euclidean)
+            .expect("Clustering failed");
+
+        assert_eq!(dendrogram.cut_into_clusters(3).expect("Cut failed"), vec![0, 1, 0, 1, 2]);
+        assert_eq!(dendrogram.cut_at_height(0.45).expect("Cut failed"), vec![0, 1, 2, 1, 3]);
+        assert_eq!(
+            dendrogram
+                .cut(DendrogramCut::Height(-1.0))
+                .expect("Cut failed"),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert!(dendrogram.cut_into_clusters(0).is_err());
+        assert!(dendrogram.cut_into_clusters(6).is_err());
+    }
+
+    #[test]
+    fn test_malformed_dendrogram_is_rejected() {
+        let dendrogram = Dendrogram {
+            leaf_count: 2,
+            merges: vec![DendrogramMerge { left: 0, right: 2, height: 1.0, size: 2 }],
+        };
+        assert!(dendrogram.cut_into_clusters(1).is_err());
+
+        // Leaf counts that disagree with the merges are rejected before allocating
+        for leaf_count in [usize::MAX, 1 << 40, 3, 1] {
+            let dendrogram = Dendrogram {
+                leaf_count,
+                merges: vec![DendrogramMerge { left: 0, right: 1, height: 1.0, size: 2 }],
+            };
+            assert!(dendrogram.cut_at_height(2.0).is_err(), "{leaf_count}");
+            assert!(dendrogram.cut_into_clusters(1).is_err(), "{leaf_count}");
+        }
+    }
+
+    #[test]
+    fn test_single_vector() {
+        let dendrogram = agglomerative_clustering(&[vec![1.0]], Linkage::Ward, &Euclidean)
+            .expect("Clustering failed");
+        assert!(dendrogram.merges.is_empty());
+        assert_eq!(dendrogram.cut_into_clusters(1).expect("Cut failed"), vec![0]);
+    }
+}
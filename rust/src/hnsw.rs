@@ -0,0 +1,876 @@
+//! Hierarchical Navigable Small World (HNSW) approximate nearest-neighbour index.
+//!
+//! Brute-force kNN is linear in the vault size; HNSW answers the same queries in
+//! roughly logarithmic time with high recall (Malkov & Yashunin, 2016). The index
+//! supports incremental inserts, tombstone deletion and a compact binary format.
+
+use crate::binary::{ByteReader, ByteWriter};
+use crate::rng::SeededRng;
+use crate::{
+    DistanceMetric, NeighborCandidate, NeighborResult, PluginError, VectorWithMetadata,
+    distance_metric_from_name,
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Magic bytes at the start of a serialized index.
+const MAGIC: [u8; 4] = *b"HNSW";
+/// Current binary format version.
+const FORMAT_VERSION: u8 = 1;
+/// Hard cap on layer count, keeps pathological random draws bounded.
+const MAX_LEVEL: usize = 16;
+
+/// Construction parameters for an HNSW index.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HnswConfig {
+    /// Maximum neighbours per node on upper layers (layer 0 allows twice this).
+    pub m: usize,
+    /// Candidate list size used while inserting.
+    pub ef_construction: usize,
+    /// Seed for the random level assignment.
+    pub seed: u64,
+}
+
+impl Default for HnswConfig {
+    fn default() -> Self {
+        Self { m: 16, ef_construction: 200, seed: 42 }
+    }
+}
+
+impl HnswConfig {
+    /// Check that the parameters can build a connected graph.
+    ///
+    /// # Errors
+    /// Returns error if `m` is below 2 or `ef_construction` is zero
+    pub fn validate(&self) -> Result<(), PluginError> {
+        if self.m < 2 {
+            return Err(PluginError::ValidationError {
+                field: "m".to_string(),
+                value: self.m.to_string(),
+                reason: "Must be at least 2".to_string(),
+            });
+        }
+        if self.ef_construction == 0 {
+            return Err(PluginError::ValidationError {
+                field: "efConstruction".to_string(),
+                value: self.ef_construction.to_string(),
+                reason: "Must be at least 1".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Approximate nearest-neighbour index over note vectors.
+///
+/// Deleted notes are tombstoned: they stay in the graph so it remains well
+/// connected, but never appear in results. Call [`HnswIndex::compact`] to drop
+/// them once many notes have been removed.
+pub struct HnswIndex<'m> {
+    /// Construction parameters.
+    config: HnswConfig,
+    /// Distance metric used for graph construction and search.
+    metric: Box<dyn DistanceMetric + 'm>,
+    /// Vector dimensionality (set by the first insert).
+    dim: Option<usize>,
+    /// Stored records, one per graph node.
+    records: Vec<VectorWithMetadata>,
+    /// Adjacency lists per node, one per layer the node belongs to.
+    links: Vec<Vec<Vec<usize>>>,
+    /// Tombstone flag per node.
+    deleted: Vec<bool>,
+    /// Map from live note id to node index.
+    id_map: HashMap<String, usize>,
+    /// Node where every search starts.
+    entry_point: Option<usize>,
+    /// Generator for random levels.
+    rng: SeededRng,
+}
+
+impl<'m> HnswIndex<'m> {
+    /// Create an empty index.
+    ///
+    /// # Arguments
+    /// * `config` - Construction parameters
+    /// * `metric` - Distance metric used for construction and search
+    ///
+    /// # Errors
+    /// Returns error if the construction parameters are invalid
+    pub fn new(
+        config: HnswConfig,
+        metric: Box<dyn DistanceMetric + 'm>,
+    ) -> Result<Self, PluginError> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            metric,
+            dim: None,
+            records: Vec::new(),
+            links: Vec::new(),
+            deleted: Vec::new(),
+            id_map: HashMap::new(),
+            entry_point: None,
+            rng: SeededRng::new(config.seed),
+        })
+    }
+
+    /// Build an index from a set of records.
+    ///
+    /// # Arguments
+    /// * `records` - Records to insert, in order
+    /// * `config` - Construction parameters
+    /// * `metric` - Distance metric used for construction and search
+    ///
+    /// # Returns
+    /// A populated index
+    ///
+    /// # Errors
+    /// Returns error if the construction parameters are invalid or records have
+    /// mismatched dimensions
+    pub fn build(
+        records: Vec<VectorWithMetadata>,
+        config: HnswConfig,
+        metric: Box<dyn DistanceMetric + 'm>,
+    ) -> Result<Self, PluginError> {
+        let mut index = Self::new(config, metric)?;
+        for record in records {
+            index.insert(record)?;
+        }
+        Ok(index)
+    }
+
+    /// Number of live (non-deleted) notes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.id_map.len()
+    }
+
+    /// Whether the index has no live notes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.id_map.is_empty()
+    }
+
+    /// Number of tombstoned nodes still held in the graph.
+    #[must_use]
+    pub fn tombstone_count(&self) -> usize {
+        self.records.len() - self.id_map.len()
+    }
+
+    /// Whether a live note with this id is in the index.
+    #[must_use]
+    pub fn contains(&self, id: &str) -> bool {
+        self.id_map.contains_key(id)
+    }
+
+    /// Insert a note, replacing any existing note with the same id.
+    ///
+    /// # Arguments
+    /// * `record` - Note to insert
+    ///
+    /// # Errors
+    /// Returns error if the vector dimensionality differs from the index
+    pub fn insert(&mut self, record: VectorWithMetadata) -> Result<(), PluginError> {
+        let index = self.records.len();
+        let dim = *self.dim.get_or_insert(record.vector.len());
+        if record.vector.len() != dim {
+            return Err(PluginError::InvalidVectorDimensions {
+                expected: dim,
+                got: record.vector.len(),
+                vector_index: index,
+            });
+        }
+
+        // Updating a note tombstones the old node and links in a fresh one
+        if let Some(old) = self.id_map.remove(&record.id) {
+            self.deleted[old] = true;
+        }
+
+        let level = self.random_level();
+        let query = record.vector.clone();
+        self.id_map.insert(record.id.clone(), index);
+        self.records.push(record);
+        self.links.push(vec![Vec::new(); level + 1]);
+        self.deleted.push(false);
+
+        let Some(entry) = self.entry_point else {
+            self.entry_point = Some(index);
+            return Ok(());
+        };
+        let top = self.links[entry].len() - 1;
+
+        let mut nearest =
+            vec![NeighborCandidate { distance: self.distance_to(&query, entry)?, index: entry }];
+        for layer in (level + 1..=top).rev() {
+            nearest = self.search_layer(&query, &nearest, 1, layer)?;
+        }
+
+        for layer in (0..=level.min(top)).rev() {
+            let found = self.search_layer(&query, &nearest, self.config.ef_construction, layer)?;
+            let neighbors = self.select_neighbors(&found, self.config.m)?;
+
+            for &neighbor in &neighbors {
+                self.links[neighbor][layer].push(index);
+                if self.links[neighbor][layer].len() > self.max_links(layer) {
+                    self.prune(neighbor, layer)?;
+                }
+            }
+            self.links[index][layer] = neighbors;
+            nearest = found;
+        }
+
+        if level > top {
+            self.entry_point = Some(index);
+        }
+        Ok(())
+    }
+
+    /// Tombstone a note so it no longer appears in results.
+    ///
+    /// # Arguments
+    /// * `id` - Id of the note to remove
+    ///
+    /// # Returns
+    /// Whether a live note with this id existed
+    pub fn remove(&mut self, id: &str) -> bool {
+        self.id_map.remove(id).is_some_and(|index| {
+            self.deleted[index] = true;
+            true
+        })
+    }
+
+    /// Rebuild the graph from the live notes, dropping all tombstones.
+    ///
+    /// The configuration and metric are kept, and the live notes are
+    /// reinserted in their original order from a freshly seeded generator.
+    ///
+    /// # Errors
+    /// Returns error if a live note cannot be reinserted
+    pub fn compact(&mut self) -> Result<(), PluginError> {
+        let records = std::mem::take(&mut self.records);
+        let deleted = std::mem::take(&mut self.deleted);
+        self.links.clear();
+        self.id_map.clear();
+        self.entry_point = None;
+        self.dim = None;
+        self.rng = SeededRng::new(self.config.seed);
+
+        for (record, deleted) in records.into_iter().zip(deleted) {
+            if !deleted {
+                self.insert(record)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Search for the `k` notes closest to a query vector.
+    ///
+    /// # Arguments
+    /// * `query` - Query vector
+    /// * `k` - Maximum number of results
+    /// * `ef` - Search candidate list size (higher is slower but more accurate)
+    ///
+    /// # Returns
+    /// Up to `k` results, most similar first
+    ///
+    /// # Errors
+    /// Returns error if the query dimensionality differs from the index
+    pub fn search(
+        &self,
+        query: &[f64],
+        k: usize,
+        ef: usize,
+    ) -> Result<Vec<NeighborResult>, PluginError> {
+        self.search_excluding(query, k, ef, None)
+    }
+
+    /// Search for the `k` notes closest to an indexed note, excluding the note itself.
+    ///
+    /// # Arguments
+    /// * `id` - Id of a note in the index
+    /// * `k` - Maximum number of results
+    /// * `ef` - Search candidate list size
+    ///
+    /// # Returns
+    /// Up to `k` results, most similar first
+    ///
+    /// # Errors
+    /// Returns error if the id is not in the index
+    pub fn search_by_id(
+        &self,
+        id: &str,
+        k: usize,
+        ef: usize,
+    ) -> Result<Vec<NeighborResult>, PluginError> {
+        let index = self
+            .id_map
+            .get(id)
+            .copied()
+            .ok_or_else(|| PluginError::ValidationError {
+                field: "id".to_string(),
+                value: id.to_string(),
+                reason: "No note with this id".to_string(),
+            })?;
+        self.search_excluding(&self.records[index].vector, k, ef, Some(index))
+    }
+
+    /// Shared search implementation with an optional excluded node.
+    fn search_excluding(
+        &self,
+        query: &[f64],
+        k: usize,
+        ef: usize,
+        exclude: Option<usize>,
+    ) -> Result<Vec<NeighborResult>, PluginError> {
+        let Some(entry) = self.entry_point else {
+            return Ok(Vec::new());
+        };
+        if let Some(dim) = self.dim
+            && query.len() != dim
+        {
+            return Err(PluginError::InvalidVectorDimensions {
+                expected: dim,
+                got: query.len(),
+                vector_index: 0,
+            });
+        }
+
+        let mut nearest =
+            vec![NeighborCandidate { distance: self.distance_to(query, entry)?, index: entry }];
+        for layer in (1..self.links[entry].len()).rev() {
+            nearest = self.search_layer(query, &nearest, 1, layer)?;
+        }
+
+        // Tombstones still occupy candidate slots, so widen the search until
+        // enough live results survive or the whole graph has been considered
+        let target = k.min(self.len() - usize::from(exclude.is_some()));
+        let mut ef = ef.max(k).max(1);
+        loop {
+            let found = self.search_layer(query, &nearest, ef, 0)?;
+            let live: Vec<NeighborCandidate> = found
+                .into_iter()
+                .filter(|c| !self.deleted[c.index] && Some(c.index) != exclude)
+                .take(k)
+                .collect();
+            if live.len() >= target || ef >= self.records.len() {
+                return Ok(live.into_iter().map(|c| self.to_result(c)).collect());
+            }
+            ef = (ef * 2).min(self.records.len());
+        }
+    }
+
+    /// Greedy best-first search restricted to one layer.
+    ///
+    /// Returns up to `ef` candidates sorted from nearest to farthest.
+    fn search_layer(
+        &self,
+        query: &[f64],
+        entry: &[NeighborCandidate],
+        ef: usize,
+        layer: usize,
+    ) -> Result<Vec<NeighborCandidate>, PluginError> {
+        let mut visited: HashSet<usize> = entry.iter().map(|c| c.index).collect();
+        let mut candidates: BinaryHeap<Reverse<NeighborCandidate>> =
+            entry.iter().copied().map(Reverse).collect();
+        let mut results: BinaryHeap<NeighborCandidate> = entry.iter().copied().collect();
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            if results.len() >= ef && results.peek().is_some_and(|worst| current > *worst) {
+                break;
+            }
+            let Some(neighbors) = self.links[current.index].get(layer) else {
+                continue;
+            };
+            for &neighbor in neighbors {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let candidate = NeighborCandidate {
+                    distance: self.distance_to(query, neighbor)?,
+                    index: neighbor,
+                };
+                if results.len() < ef || results.peek().is_some_and(|worst| candidate < *worst) {
+                    candidates.push(Reverse(candidate));
+                    results.push(candidate);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        Ok(results.into_sorted_vec())
+    }
+
+    /// Pick up to `m` diverse neighbours from candidates sorted by distance.
+    ///
+    /// Uses the HNSW heuristic: a candidate is kept only if it is closer to the
+    /// base node than to any neighbour already selected. Pruned candidates fill
+    /// any remaining slots so well-clustered data still gets `m` links.
+    fn select_neighbors(
+        &self,
+        candidates: &[NeighborCandidate],
+        m: usize,
+    ) -> Result<Vec<usize>, PluginError> {
+        let mut selected: Vec<usize> = Vec::with_capacity(m);
+        let mut pruned = Vec::new();
+
+        for candidate in candidates {
+            if selected.len() >= m {
+                break;
+            }
+            let mut diverse = true;
+            for &chosen in &selected {
+                if self.distance_between(candidate.index, chosen)? < candidate.distance {
+                    diverse = false;
+                    break;
+                }
+            }
+            if diverse {
+                selected.push(candidate.index);
+            } else {
+                pruned.push(candidate.index);
+            }
+        }
+
+        let missing = m.saturating_sub(selected.len());
+        selected.extend(pruned.into_iter().take(missing));
+        Ok(selected)
+    }
+
+    /// Shrink a node's neighbour list on one layer back to the allowed size.
+    fn prune(&mut self, node: usize, layer: usize) -> Result<(), PluginError> {
+        let mut candidates = self.links[node][layer]
+            .iter()
+            .map(|&neighbor| {
+                Ok(NeighborCandidate {
+                    distance: self.distance_between(node, neighbor)?,
+                    index: neighbor,
+                })
+            })
+            .collect::<Result<Vec<_>, PluginError>>()?;
+        candidates.sort();
+        self.links[node][layer] = self.select_neighbors(&candidates, self.max_links(layer))?;
+        Ok(())
+    }
+
+    /// Maximum number of links a node may keep on a layer.
+    const fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.config.m * 2
+        } else {
+            self.config.m
+        }
+    }
+
+    /// Draw a random level with the standard exponential distribution.
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn random_level(&mut self) -> usize {
+        let level_multiplier = 1.0 / (self.config.m as f64).ln();
+        let uniform = self.rng.next_f64().max(f64::MIN_POSITIVE);
+        ((-uniform.ln() * level_multiplier).floor() as usize).min(MAX_LEVEL)
+    }
+
+    /// Distance from a query vector to a node.
+    fn distance_to(&self, query: &[f64], node: usize) -> Result<f64, PluginError> {
+        self.metric.distance(query, &self.records[node].vector)
+    }
+
+    /// Distance between two nodes.
+    fn distance_between(&self, a: usize, b: usize) -> Result<f64, PluginError> {
+        self.metric
+            .distance(&self.records[a].vector, &self.records[b].vector)
+    }
+
+    /// Convert a candidate into a public result.
+    fn to_result(&self, candidate: NeighborCandidate) -> NeighborResult {
+        let record = &self.records[candidate.index];
+        NeighborResult {
+            id: record.id.clone(),
+            label: record.label.clone(),
+            score: self.metric.similarity(candidate.distance),
+            distance: candidate.distance,
+            metadata: record.metadata.clone(),
+        }
+    }
+
+    /// Serialize the index to a compact binary blob.
+    ///
+    /// Vectors are stored as 32-bit floats, which is the native precision of
+    /// embedding models and halves the blob size.
+    ///
+    /// # Returns
+    /// Encoded index bytes
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = ByteWriter::with_header(MAGIC, FORMAT_VERSION);
+        writer.write_str(self.metric.name());
+        writer.write_len(self.config.m);
+        writer.write_len(self.config.ef_construction);
+        writer.write_u64(self.config.seed);
+        writer.write_u64(self.rng.state());
+        writer.write_len(self.dim.unwrap_or(0));
+        writer.write_bool(self.entry_point.is_some());
+        writer.write_len(self.entry_point.unwrap_or(0));
+        writer.write_len(self.records.len());
+
+        for ((record, layers), &deleted) in self.records.iter().zip(&self.links).zip(&self.deleted)
+        {
+            writer.write_bool(deleted);
+            writer.write_str(&record.id);
+            writer.write_str(&record.label);
+            writer.write_str(&record.source_id);
+
+            // Sort metadata so identical indices produce identical bytes
+            let mut metadata: Vec<_> = record.metadata.iter().collect();
+            metadata.sort();
+            writer.write_len(metadata.len());
+            for (key, value) in metadata {
+                writer.write_str(key);
+                writer.write_str(value);
+            }
+
+            for &value in &record.vector {
+                writer.write_f32(value as f32);
+            }
+
+            writer.write_len(layers.len());
+            for neighbors in layers {
+                writer.write_len(neighbors.len());
+                for &neighbor in neighbors {
+                    writer.write_len(neighbor);
+                }
+            }
+        }
+
+        writer.into_bytes()
+    }
+
+    /// Load an index from bytes produced by [`HnswIndex::to_bytes`].
+    ///
+    /// # Arguments
+    /// * `bytes` - Encoded index
+    ///
+    /// # Returns
+    /// The decoded index
+    ///
+    /// # Errors
+    /// Returns error if the data is corrupt, truncated, uses an unknown version or
+    /// metric, or stores invalid construction parameters
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PluginError> {
+        let (mut reader, version) = ByteReader::with_header(bytes, MAGIC, "HnswIndex::from_bytes")?;
+        if version != FORMAT_VERSION {
+            return Err(reader.error(&format!("unsupported format version {version}")));
+        }
+
+        let metric = distance_metric_from_name(&reader.read_str()?)?;
+        let config = HnswConfig {
+            m: reader.read_len()?,
+            ef_construction: reader.read_len()?,
+            seed: reader.read_u64()?,
+        };
+        let mut index = Self::new(config, metric)?;
+        index.rng = SeededRng::new(reader.read_u64()?);
+        let dim = reader.read_len()?;
+        let has_entry = reader.read_bool()?;
+        let entry = reader.read_len()?;
+        let count = reader.read_len()?;
+        // Without an entry point the loaded nodes could never be reached
+        if has_entry != (count > 0) {
+            return Err(
+                reader.error(&format!("entry point flag {has_entry} does not match {count} nodes"))
+            );
+        }
+
+        for node in 0..count {
+            let deleted = reader.read_bool()?;
+            let id = reader.read_str()?;
+            let label = reader.read_str()?;
+            let source_id = reader.read_str()?;
+
+            // Lengths come from untrusted data, so never reserve more entries than
+            // the remaining bytes could hold (a key and value take at least 8 bytes)
+            let metadata_len = reader.read_len()?;
+            let mut metadata = HashMap::with_capacity(metadata_len.min(reader.remaining() / 8));
+            for _ in 0..metadata_len {
+                let key = reader.read_str()?;
+                metadata.insert(key, reader.read_str()?);
+            }
+
+            let vector = (0..dim)
+                .map(|_| reader.read_f32().map(f64::from))
+                .collect::<Result<_, _>>()?;
+
+            let layer_count = reader.read_len()?;
+            if layer_count == 0 || layer_count > MAX_LEVEL + 1 {
+                return Err(reader.error(&format!("node {node} has {layer_count} layers")));
+            }
+            let mut layers = Vec::with_capacity(layer_count);
+            for _ in 0..layer_count {
+                let neighbor_count = reader.read_len()?;
+                let neighbors = (0..neighbor_count)
+                    .map(|_| reader.read_len())
+                    .collect::<Result<Vec<_>, _>>()?;
+                if neighbors.iter().any(|&n| n >= count) {
+                    return Err(reader.error("neighbour index out of range"));
+                }
+                layers.push(neighbors);
+            }
+
+            if !deleted {
+                index.id_map.insert(id.clone(), node);
+            }
+            index
+                .records
+                .push(VectorWithMetadata::with_metadata(id, label, vector, source_id, metadata));
+            index.links.push(layers);
+            index.deleted.push(deleted);
+        }
+        reader.finish()?;
+
+        // Insertion indexes `links[neighbor][layer]`, so every neighbour must
+        // exist on the layer it is linked on
+        for (node, layers) in index.links.iter().enumerate() {
+            for (layer, neighbors) in layers.iter().enumerate() {
+                if let Some(&neighbor) = neighbors.iter().find(|&&n| index.links[n].len() <= layer)
+                {
+                    return Err(reader.error(&format!(
+                        "node {node} links to {neighbor} on layer {layer}, which {neighbor} is not on"
+                    )));
+                }
+            }
+        }
+
+        if has_entry {
+            if entry >= count {
+                return Err(reader.error("entry point out of range"));
+            }
+            index.entry_point = Some(entry);
+            index.dim = Some(dim);
+        }
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Euclidean;
+
+    fn record(id: &str, vector: Vec<f64>) -> VectorWithMetadata {
+        VectorWithMetadata::new(id.to_string(), id.to_string(), vector, "test".to_string())
+    }
+
+    fn grid_index() -> HnswIndex<'static> {
+        let records = (0..100)
+            .map(|i| record(&format!("n{i}"), vec![f64::from(i % 10), f64::from(i / 10)]))
+            .collect();
+        HnswIndex::build(records, HnswConfig { m: 4, ..HnswConfig::default() }, Box::new(Euclidean))
+            .expect("Build failed")
+    }
+
+    #[test]
+    fn test_hnsw_finds_exact_match() {
+        let index = grid_index();
+        let results = index.search(&[3.0, 7.0], 1, 32).expect("Search failed");
+
+        assert_eq!(results[0].id, "n73");
+        assert!(results[0].distance.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_hnsw_search_by_id_excludes_self() {
+        let index = grid_index();
+        let results = index.search_by_id("n55", 4, 32).expect("Search failed");
+
+        let mut ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["n45", "n54", "n56", "n65"]);
+    }
+
+    #[test]
+    fn test_hnsw_remove_tombstones_node() {
+        let mut index = grid_index();
+        assert!(index.remove("n73"));
+        assert!(!index.remove("n73"));
+
+        let results = index.search(&[3.0, 7.0], 5, 32).expect("Search failed");
+        assert!(results.iter().all(|r| r.id != "n73"));
+        assert_eq!(results.len(), 5);
+        assert_eq!(index.len(), 99);
+        assert_eq!(index.tombstone_count(), 1);
+
+        index.compact().expect("Compact failed");
+        assert_eq!(index.tombstone_count(), 0);
+        assert_eq!(index.len(), 99);
+        let results = index.search(&[3.0, 7.0], 5, 32).expect("Search failed");
+        assert!(results.iter().all(|r| r.id != "n73"));
+    }
+
+    /// Euclidean distance under a name the metric table does not know.
+    struct ShiftedEuclidean;
+
+    impl DistanceMetric for ShiftedEuclidean {
+        fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
+            Euclidean.distance(a, b)
+        }
+
+        fn name(&self) -> &'static str {
+            "shifted_euclidean"
+        }
+    }
+
+    #[test]
+    fn test_hnsw_compact_keeps_custom_metric() {
+        let records = (0..20)
+            .map(|i| record(&format!("n{i}"), vec![f64::from(i)]))
+            .collect();
+        let mut index =
+            HnswIndex::build(records, HnswConfig::default(), Box::new(ShiftedEuclidean))
+                .expect("Build failed");
+        index.remove("n5");
+
+        index.compact().expect("Compact failed");
+
+        let results = index.search(&[5.0], 2, 32).expect("Search failed");
+        let ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["n4", "n6"]);
+    }
+
+    #[test]
+    fn test_hnsw_update_replaces_vector() {
+        let mut index = grid_index();
+        index
+            .insert(record("n0", vec![100.0, 100.0]))
+            .expect("Insert failed");
+
+        let results = index.search(&[100.0, 100.0], 1, 32).expect("Search failed");
+        assert_eq!(results[0].id, "n0");
+        assert_eq!(index.len(), 100);
+    }
+
+    #[test]
+    fn test_hnsw_dimension_mismatch() {
+        let mut index = grid_index();
+        let result = index.insert(record("bad", vec![1.0]));
+        assert!(matches!(result, Err(PluginError::InvalidVectorDimensions { .. })));
+    }
+
+    #[test]
+    fn test_hnsw_bytes_round_trip() {
+        let mut index = grid_index();
+        index.remove("n10");
+        let bytes = index.to_bytes();
+        let restored = HnswIndex::from_bytes(&bytes).expect("Decode failed");
+
+        assert_eq!(restored.len(), index.len());
+        assert_eq!(restored.to_bytes(), bytes);
+        assert_eq!(
+            restored.search(&[2.0, 2.0], 3, 32).expect("Search failed"),
+            index.search(&[2.0, 2.0], 3, 32).expect("Search failed")
+        );
+    }
+
+    #[test]
+    fn test_hnsw_from_bytes_rejects_garbage() {
+        let result = HnswIndex::from_bytes(b"nope");
+        assert!(matches!(result, Err(PluginError::SerializationError { .. })));
+
+        let bytes = grid_index().to_bytes();
+        let result = HnswIndex::from_bytes(&bytes[..bytes.len() - 3]);
+        assert!(matches!(result, Err(PluginError::SerializationError { .. })));
+    }
+
+    /// Encode a one-dimensional index with the given config and per-node layers.
+    fn blob(m: usize, ef_construction: usize, links: &[Vec<Vec<usize>>]) -> Vec<u8> {
+        let mut writer = ByteWriter::with_header(MAGIC, FORMAT_VERSION);
+        writer.write_str("euclidean");
+        writer.write_len(m);
+        writer.write_len(ef_construction);
+        writer.write_u64(0);
+        writer.write_u64(0);
+        writer.write_len(1);
+        writer.write_bool(true);
+        writer.write_len(0);
+        writer.write_len(links.len());
+        for (node, layers) in links.iter().enumerate() {
+            writer.write_bool(false);
+            for _ in 0..3 {
+                writer.write_str(&format!("n{node}"));
+            }
+            writer.write_len(0);
+            writer.write_f32(0.0);
+            writer.write_len(layers.len());
+            for neighbors in layers {
+                writer.write_len(neighbors.len());
+                for &neighbor in neighbors {
+                    writer.write_len(neighbor);
+                }
+            }
+        }
+        writer.into_bytes()
+    }
+
+    #[test]
+    fn test_hnsw_from_bytes_validates_graph() {
+        let valid = vec![vec![vec![1], vec![1]], vec![vec![0], vec![0]]];
+        assert!(HnswIndex::from_bytes(&blob(4, 8, &valid)).is_ok());
+
+        let missing_layer = vec![vec![vec![1], vec![1]], vec![vec![0]]];
+        let no_layers = vec![vec![vec![]], vec![]];
+        let too_many_layers = vec![vec![Vec::new(); MAX_LEVEL + 2]];
+        for links in [missing_layer, no_layers, too_many_layers] {
+            let result = HnswIndex::from_bytes(&blob(4, 8, &links));
+            assert!(matches!(result, Err(PluginError::SerializationError { .. })), "{links:?}");
+        }
+    }
+
+    #[test]
+    fn test_hnsw_from_bytes_requires_entry_point_with_nodes() {
+        // Nodes without an entry point
+        let mut bytes = blob(4, 8, &[vec![vec![]]]);
+        // Header, metric name, m, ef_construction, both seeds and the dimension
+        let has_entry = MAGIC.len() + 1 + 4 + "euclidean".len() + 4 * 2 + 8 * 2 + 4;
+        assert_eq!(bytes[has_entry], 1);
+        bytes[has_entry] = 0;
+        // An entry point without nodes
+        let empty = blob(4, 8, &[]);
+
+        for bytes in [bytes, empty] {
+            let result = HnswIndex::from_bytes(&bytes);
+            assert!(matches!(result, Err(PluginError::SerializationError { .. })));
+        }
+        let empty = HnswIndex::new(HnswConfig::default(), Box::new(Euclidean))
+            .expect("Index creation failed");
+        assert!(HnswIndex::from_bytes(&empty.to_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_hnsw_from_bytes_bounds_untrusted_lengths() {
+        let mut bytes = blob(4, 8, &[vec![vec![]]]);
+        // Claim u32::MAX metadata entries for the only node, then cut the data short;
+        // the node ends with its metadata length, vector, layer count and neighbour count
+        let metadata_len = bytes.len() - 16;
+        bytes[metadata_len..metadata_len + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        bytes.truncate(metadata_len + 4);
+
+        let result = HnswIndex::from_bytes(&bytes);
+        assert!(matches!(result, Err(PluginError::SerializationError { .. })));
+    }
+
+    #[test]
+    fn test_hnsw_rejects_invalid_config() {
+        for (m, ef_construction) in [(1, 8), (0, 8), (4, 0)] {
+            let config = HnswConfig { m, ef_construction, seed: 0 };
+            let result = HnswIndex::new(config, Box::new(Euclidean));
+            assert!(matches!(result, Err(PluginError::ValidationError { .. })));
+            let result = HnswIndex::from_bytes(&blob(m, ef_construction, &[vec![vec![]]]));
+            assert!(matches!(result, Err(PluginError::ValidationError { .. })));
+        }
+    }
+}
@@ -0,0 +1,386 @@
+//! Seeded k-means clustering with k-means++ initialisation and restarts.
+//!
+//! Each restart seeds centroids with k-means++ (Arthur & Vassilvitskii, 2007)
+//! and runs Lloyd iterations; the restart with the lowest inertia wins.
+//! Clusters that lose all their points are re-seeded from the point farthest
+//! from its centroid, so every run returns exactly k non-empty clusters.
+
+use crate::rng::SeededRng;
+use crate::{DistanceMetric, PluginError, non_negative_distance};
+use serde::{Deserialize, Serialize};
+
+/// Settings for k-means.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct KMeansConfig {
+    /// Number of independent k-means++ initialisations; the best is kept.
+    pub restarts: usize,
+    /// Upper bound on Lloyd iterations per restart.
+    pub max_iterations: usize,
+    /// Stop once the squared centroid movement falls below this fraction of
+    /// the data's total variance.
+    pub tolerance: f64,
+    /// Seed for the k-means++ draws.
+    pub seed: u64,
+}
+
+impl Default for KMeansConfig {
+    fn default() -> Self {
+        Self { restarts: 4, max_iterations: 100, tolerance: 1e-4, seed: 42 }
+    }
+}
+
+/// Result of k-means clustering.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KMeansResult {
+    /// Cluster id per vector, in `0..k`.
+    pub assignments: Vec<usize>,
+    /// Mean of each cluster's vectors.
+    pub centroids: Vec<Vec<f64>>,
+    /// Sum of squared distances from each vector to its centroid.
+    pub inertia: f64,
+    /// Lloyd iterations run by the winning restart.
+    pub iterations: usize,
+    /// Whether the winning restart met the tolerance before `max_iterations`.
+    pub converged: bool,
+}
+
+/// Cluster vectors with k-means.
+///
+/// Points are assigned with `metric`, while centroids are arithmetic means,
+/// so non-Euclidean metrics give the usual spherical/L1 approximations.
+///
+/// # Arguments
+/// * `vectors` - Input vectors to cluster
+/// * `k` - Number of clusters
+/// * `config` - Restart, stopping and seed settings
+/// * `metric` - Distance metric used to assign points to centroids
+///
+/// # Returns
+/// The lowest-inertia clustering over all restarts
+///
+/// # Errors
+/// Returns error if k is invalid, the settings are invalid, or vectors have
+/// mismatched dimensions
+pub fn kmeans(
+    vectors: &[Vec<f64>],
+    k: usize,
+    config: &KMeansConfig,
+    metric: &dyn DistanceMetric,
+) -> Result<KMeansResult, PluginError> {
+    if vectors.is_empty() || k == 0 {
+        return Err(PluginError::InsufficientData { required: 1, provided: 0 });
+    }
+    if k > vectors.len() {
+        return Err(PluginError::InsufficientData { required: k, provided: vectors.len() });
+    }
+    if !config.tolerance.is_finite() || config.tolerance < 0.0 {
+        return Err(PluginError::ValidationError {
+            field: "tolerance".to_string(),
+            value: config.tolerance.to_string(),
+            reason: "Tolerance must be a non-negative number".to_string(),
+        });
+    }
+    let dim = vectors[0].len();
+    for (i, vec) in vectors.iter().enumerate() {
+        if vec.len() != dim {
+            return Err(PluginError::InvalidVectorDimensions {
+                expected: dim,
+                got: vec.len(),
+                vector_index: i,
+            });
+        }
+    }
+
+    let shift_tolerance = config.tolerance * total_variance(vectors);
+    let mut rng = SeededRng::new(config.seed);
+    let mut best: Option<KMeansResult> = None;
+    for _ in 0..config.restarts.max(1) {
+        let centroids = kmeans_plus_plus(vectors, k, metric, &mut rng)?;
+        let result = lloyd(vectors, centroids, config.max_iterations, shift_tolerance, metric)?;
+        if best.as_ref().is_none_or(|b| result.inertia < b.inertia) {
+            best = Some(result);
+        }
+    }
+    best.ok_or(PluginError::InsufficientData { required: 1, provided: 0 })
+}
+
+/// Pick initial centroids with k-means++: each new centroid is drawn with
+/// probability proportional to its squared distance from the nearest one so far.
+fn kmeans_plus_plus(
+    vectors: &[Vec<f64>],
+    k: usize,
+    metric: &dyn DistanceMetric,
+    rng: &mut SeededRng,
+) -> Result<Vec<Vec<f64>>, PluginError> {
+    let mut centroids = vec![vectors[rng.next_index(vectors.len())].clone()];
+    let mut weights = vec![f64::INFINITY; vectors.len()];
+    while centroids.len() < k {
+        let newest = &centroids[centroids.len() - 1];
+        for (weight, vec) in weights.iter_mut().zip(vectors) {
+            let dist = non_negative_distance(metric, vec, newest)?;
+            *weight = weight.min(dist * dist);
+        }
+
+        let total: f64 = weights.iter().sum();
+        let chosen = if total > 0.0 {
+            let mut target = rng.next_f64() * total;
+            weights
+                .iter()
+                .position(|&w| {
+                    target -= w;
+                    target < 0.0
+                })
+                .unwrap_or_else(|| weights.iter().rposition(|&w| w > 0.0).unwrap_or(0))
+        } else {
+            // Every point coincides with a centroid already
+            rng.next_index(vectors.len())
+        };
+        centroids.push(vectors[chosen].clone());
+    }
+    Ok(centroids)
+}
+
+/// Run Lloyd iterations from the given centroids.
+fn lloyd(
+    vectors: &[Vec<f64>],
+    mut centroids: Vec<Vec<f64>>,
+    max_iterations: usize,
+    shift_tolerance: f64,
+    metric: &dyn DistanceMetric,
+) -> Result<KMeansResult, PluginError> {
+    let k = centroids.len();
+    let mut iterations = 0;
+    let mut converged = false;
+    while iterations < max_iterations {
+        iterations += 1;
+        let (mut assignments, mut distances) = assign(vectors, &centroids, metric)?;
+        repair_empty_clusters(&mut assignments, &mut distances, k);
+
+        let updated = compute_means(vectors, &assignments, k);
+        let shift: f64 = updated
+            .iter()
+            .zip(&centroids)
+            .map(|(new, old)| {
+                new.iter()
+                    .zip(old)
+                    .map(|(a, b)| (a - b) * (a - b))
+                    .sum::<f64>()
+            })
+            .sum();
+        centroids = updated;
+        if shift <= shift_tolerance {
+            converged = true;
+            break;
+        }
+    }
+
+    // Final assignment, with centroids and inertia matching it exactly
+    let (mut assignments, mut distances) = assign(vectors, &centroids, metric)?;
+    repair_empty_clusters(&mut assignments, &mut distances, k);
+    let centroids = compute_means(vectors, &assignments, k);
+    let mut inertia = 0.0;
+    for (vec, &cluster) in vectors.iter().zip(&assignments) {
+        let dist = non_negative_distance(metric, vec, &centroids[cluster])?;
+        inertia += dist * dist;
+    }
+    Ok(KMeansResult { assignments, centroids, inertia, iterations, converged })
+}
+
+/// Assign each vector to its nearest centroid.
+///
+/// # Returns
+/// The assignments and each vector's distance to its centroid
+fn assign(
+    vectors: &[Vec<f64>],
+    centroids: &[Vec<f64>],
+    metric: &dyn DistanceMetric,
+) -> Result<(Vec<usize>, Vec<f64>), PluginError> {
+    let mut assignments = Vec::with_capacity(vectors.len());
+    let mut distances = Vec::with_capacity(vectors.len());
+    for vec in vectors {
+        let mut best = (0, f64::INFINITY);
+        for (cluster, centroid) in centroids.iter().enumerate() {
+            let dist = metric.distance(vec, centroid)?;
+            if dist < best.1 {
+                best = (cluster, dist);
+            }
+        }
+        assignments.push(best.0);
+        distances.push(best.1.max(0.0));
+    }
+    Ok((assignments, distances))
+}
+
+/// Give every empty cluster the worst-fitting point of a cluster that can spare one.
+fn repair_empty_clusters(assignments: &mut [usize], distances: &mut [f64], k: usize) {
+    let mut sizes = vec![0_usize; k];
+    for &cluster in assignments.iter() {
+        sizes[cluster] += 1;
+    }
+    for empty in 0..k {
+        if sizes[empty] > 0 {
+            continue;
+        }
+        let donor = (0..assignments.len())
+            .filter(|&i| sizes[assignments[i]] > 1)
+            .max_by(|&a, &b| distances[a].total_cmp(&distances[b]));
+        if let Some(point) = donor {
+            sizes[assignments[point]] -= 1;
+            assignments[point] = empty;
+            distances[point] = 0.0;
+            sizes[empty] = 1;
+        }
+    }
+}
+
+/// Arithmetic mean of each cluster's vectors.
+// The module is glob re-exported, so plain `pub` would make this public API
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn compute_means(
+    vectors: &[Vec<f64>],
+    assignments: &[usize],
+    k: usize,
+) -> Vec<Vec<f64>> {
+    let dim = vectors.first().map_or(0, Vec::len);
+    let mut means = vec![vec![0.0; dim]; k];
+    let mut counts = vec![0_u32; k];
+    for (vec, &cluster) in vectors.iter().zip(assignments) {
+        for (sum, &val) in means[cluster].iter_mut().zip(vec) {
+            *sum += val;
+        }
+        counts[cluster] += 1;
+    }
+    for (mean, &count) in means.iter_mut().zip(&counts) {
+        if count > 0 {
+            for val in mean {
+                *val /= f64::from(count);
+            }
+        }
+    }
+    means
+}
+
+/// Sum over features of the variance of the vectors.
+fn total_variance(vectors: &[Vec<f64>]) -> f64 {
+    let mean = compute_means(vectors, &vec![0; vectors.len()], 1).remove(0);
+    #[allow(clippy::cast_precision_loss)]
+    let n = vectors.len() as f64;
+    vectors
+        .iter()
+        .map(|vec| {
+            vec.iter()
+                .zip(&mean)
+                .map(|(x, m)| (x - m) * (x - m))
+                .sum::<f64>()
+        })
+        .sum::<f64>()
+        / n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Euclidean;
+
+    /// Three tight blobs of `per_blob` points around (0, 0), (10, 0) and (0, 10).
+    fn three_blobs(per_blob: usize) -> Vec<Vec<f64>> {
+        let mut rng = SeededRng::new(7);
+        [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)]
+            .iter()
+            .flat_map(|&(x, y)| {
+                (0..per_blob)
+                    .map(|_| {
+                        vec![
+                            0.3f64.mul_add(rng.next_normal(), x),
+                            0.3f64.mul_add(rng.next_normal(), y),
+                        ]
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_kmeans_recovers_blobs() {
+        let vectors = three_blobs(20);
+        let result =
+            kmeans(&vectors, 3, &KMeansConfig::default(), &Euclidean).expect("k-means failed");
+
+        assert!(result.converged);
+        assert_eq!(result.centroids.len(), 3);
+        for blob in vectors.chunks(20).zip(result.assignments.chunks(20)) {
+            assert!(blob.1.iter().all(|&c| c == blob.1[0]));
+        }
+        let distinct: std::collections::HashSet<usize> =
+            result.assignments.iter().copied().collect();
+        assert_eq!(distinct.len(), 3);
+        // About 60 points with per-axis variance 0.09
+        assert!(result.inertia < 60.0 * 2.0 * 0.09 * 2.0, "{}", result.inertia);
+    }
+
+    #[test]
+    fn test_partition_does_not_depend_on_note_order() {
+        let vectors = three_blobs(10);
+        let reversed: Vec<Vec<f64>> = vectors.iter().rev().cloned().collect();
+        let config = KMeansConfig::default();
+
+        let forward = kmeans(&vectors, 3, &config, &Euclidean).expect("k-means failed");
+        let backward = kmeans(&reversed, 3, &config, &Euclidean).expect("k-means failed");
+        let n = vectors.len();
+        for i in 0..n {
+            for j in 0..n {
+                let together = forward.assignments[i] == forward.assignments[j];
+                let still = backward.assignments[n - 1 - i] == backward.assignments[n - 1 - j];
+                assert_eq!(together, still);
+            }
+        }
+    }
+
+    #[test]
+    fn test_restarts_never_increase_inertia() {
+        let vectors = three_blobs(15);
+        let single = KMeansConfig { restarts: 1, ..KMeansConfig::default() };
+        let many = KMeansConfig { restarts: 10, ..KMeansConfig::default() };
+
+        let one = kmeans(&vectors, 5, &single, &Euclidean).expect("k-means failed");
+        let best = kmeans(&vectors, 5, &many, &Euclidean).expect("k-means failed");
+        assert!(best.inertia <= one.inertia);
+    }
+
+    #[test]
+    fn test_kmeans_is_deterministic_per_seed() {
+        let vectors = three_blobs(10);
+        let config = KMeansConfig { seed: 11, ..KMeansConfig::default() };
+
+        let first = kmeans(&vectors, 4, &config, &Euclidean).expect("k-means failed");
+        let second = kmeans(&vectors, 4, &config, &Euclidean).expect("k-means failed");
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_empty_clusters_are_repaired() {
+        // Only two distinct locations for three clusters
+        let vectors = vec![vec![0.0], vec![0.0], vec![0.0], vec![5.0]];
+        let result =
+            kmeans(&vectors, 3, &KMeansConfig::default(), &Euclidean).expect("k-means failed");
+
+        let mut sizes = vec![0; 3];
+        for &c in &result.assignments {
+            sizes[c] += 1;
+        }
+        assert!(sizes.iter().all(|&s| s > 0), "{sizes:?}");
+    }
+
+    #[test]
+    fn test_kmeans_rejects_bad_input() {
+        let vectors = vec![vec![1.0, 2.0], vec![3.0]];
+        let config = KMeansConfig::default();
+
+        assert!(kmeans(&vectors, 1, &config, &Euclidean).is_err());
+        assert!(kmeans(&vectors[..1], 2, &config, &Euclidean).is_err());
+        let bad = KMeansConfig { tolerance: -1.0, ..config };
+        assert!(kmeans(&vectors[..1], 1, &bad, &Euclidean).is_err());
+    }
+}
@@ -9,20 +9,64 @@ use wasm_bindgen::prelude::*;
 
 // Module declarations
 mod adjacency_matrix;
+mod alignment;
+mod binary;
+mod centrality;
+mod cluster_count;
+mod cluster_labels;
 mod commands;
+mod community;
+mod dag;
+mod density_clustering;
 mod dimensionality_reduction;
 mod error;
+mod force_layout;
+mod graph_paths;
+mod graph_stats;
+mod hierarchical_clustering;
+mod hnsw;
+mod kmeans;
+mod link_extractor;
+mod nearest_neighbors;
+mod rng;
 mod settings;
+mod space_tree;
+mod sparse_svd;
+mod spectral;
+#[cfg(test)]
+mod test_utils;
+mod tsne;
+mod umap;
+mod union_find;
 mod utils;
 mod vector_ops;
 mod vector_source;
 
 // Re-export all public functions from modules
 pub use adjacency_matrix::*;
+pub use alignment::*;
+pub use centrality::*;
+pub use cluster_count::*;
+pub use cluster_labels::*;
 pub use commands::*;
+pub use community::*;
+pub use dag::*;
+pub use density_clustering::*;
 pub use dimensionality_reduction::*;
 pub use error::*;
+pub use force_layout::*;
+pub use graph_paths::*;
+pub use graph_stats::*;
+pub use hierarchical_clustering::*;
+pub use hnsw::*;
+pub use kmeans::*;
+pub use link_extractor::*;
+pub use nearest_neighbors::*;
 pub use settings::*;
+pub use sparse_svd::*;
+pub use spectral::*;
+pub use tsne::*;
+pub use umap::*;
 pub use utils::*;
 pub use vector_ops::*;
 pub use vector_source::*;
@@ -57,7 +101,7 @@ pub fn fibonacci(n: u32) -> u32 {
 ///
 /// # Arguments
 /// * `note_paths_json` - JSON array of note paths
-/// * `links_json` - JSON array of links (objects with from_id and to_id)
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
 ///
 /// # Returns
 /// JSON string of vectors (adjacency matrix rows)
@@ -83,11 +127,76 @@ pub fn build_adjacency_matrix(note_paths_json: &str, links_json: &str) -> Result
         .map_err(|e| JsValue::from_str(&format!("Failed to serialize: {e}")))
 }
 
+/// Build an adjacency matrix from note links with custom graph options.
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+/// * `options_json` - JSON `GraphOptions` (`directed`, `laplacian`, `selfLoops`,
+///   `binarize`); omitted fields keep their defaults
+///
+/// # Returns
+/// JSON string of vectors (adjacency matrix rows)
+///
+/// # Errors
+/// Returns error if parsing fails or link indices are invalid
+#[wasm_bindgen]
+pub fn build_adjacency_matrix_with_options(
+    note_paths_json: &str,
+    links_json: &str,
+    options_json: &str,
+) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let options: GraphOptions = serde_json::from_str(options_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse graph options: {e}")))?;
+
+    let builder = AdjacencyMatrixBuilder::with_options(note_paths, options);
+
+    let matrix = builder
+        .build(links)
+        .map_err(|e| JsValue::from_str(&format!("Failed to build matrix: {e}")))?;
+
+    let vectors = builder.matrix_to_vectors(&matrix);
+
+    serde_json::to_string(&vectors)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize: {e}")))
+}
+
+/// Extract and resolve links from raw note contents.
+///
+/// # Arguments
+/// * `notes_json` - JSON array of notes (objects with `path` and `content`)
+/// * `vault_paths_json` - JSON array of every path in the vault
+///
+/// # Returns
+/// JSON object with `links` (resolved, indexed against the vault paths) and
+/// `unresolved` (links whose targets were not found)
+///
+/// # Errors
+/// Returns error if parsing fails or a note path is not in the vault path list
+#[wasm_bindgen]
+pub fn extract_note_links(notes_json: &str, vault_paths_json: &str) -> Result<String, JsValue> {
+    let notes: Vec<NoteContent> = serde_json::from_str(notes_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse notes: {e}")))?;
+
+    let vault_paths: Vec<String> = serde_json::from_str(vault_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vault paths: {e}")))?;
+
+    let result = extract_links(&notes, &vault_paths)?;
+
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
 /// Build graph Laplacian matrix from note links.
 ///
 /// # Arguments
 /// * `note_paths_json` - JSON array of note paths
-/// * `links_json` - JSON array of links (objects with from_id and to_id)
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
 ///
 /// # Returns
 /// JSON string of vectors (Laplacian matrix rows)
@@ -113,21 +222,70 @@ pub fn build_laplacian_matrix(note_paths_json: &str, links_json: &str) -> Result
         .map_err(|e| JsValue::from_str(&format!("Failed to serialize: {e}")))
 }
 
+/// Build a graph Laplacian from note links with custom graph options.
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+/// * `options_json` - JSON `GraphOptions`; `laplacian` is one of
+///   `"combinatorial"`, `"symmetricNormalized"` or `"randomWalk"`
+///
+/// # Returns
+/// JSON string of vectors (Laplacian matrix rows)
+///
+/// # Errors
+/// Returns error if parsing fails or link indices are invalid
+#[wasm_bindgen]
+pub fn build_laplacian_matrix_with_options(
+    note_paths_json: &str,
+    links_json: &str,
+    options_json: &str,
+) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let options: GraphOptions = serde_json::from_str(options_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse graph options: {e}")))?;
+
+    let builder = AdjacencyMatrixBuilder::with_options(note_paths, options);
+
+    let matrix = builder
+        .build_laplacian(links)
+        .map_err(|e| JsValue::from_str(&format!("Failed to build Laplacian: {e}")))?;
+
+    let vectors = builder.matrix_to_vectors(&matrix);
+
+    serde_json::to_string(&vectors)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize: {e}")))
+}
+
 /// Reduce dimensionality using SVD.
 ///
 /// # Arguments
 /// * `vectors_json` - JSON array of vectors
 /// * `target_dims` - Target dimensionality (typically 2 or 3)
+/// * `metric` - Optional distance metric name; Euclidean when omitted, and
+///   `"cosine"` normalises vectors first (see [`vectors_for_metric`])
 ///
 /// # Returns
 /// JSON string of reduced vectors
 ///
 /// # Errors
-/// Returns error if parsing fails or reduction fails
+/// Returns error if parsing fails, the metric is unknown or unsupported, or reduction fails
 #[wasm_bindgen]
-pub fn reduce_dimensions_svd(vectors_json: &str, target_dims: usize) -> Result<String, JsValue> {
-    let vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
+pub fn reduce_dimensions_svd(
+    vectors_json: &str,
+    target_dims: usize,
+    metric: Option<String>,
+) -> Result<String, JsValue> {
+    let mut vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
         .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
+    if let Some(metric) = metric {
+        vectors = vectors_for_metric(vectors, &metric)?;
+    }
 
     let reducer = SVDReducer::new();
     let result = reducer
@@ -137,25 +295,1241 @@ pub fn reduce_dimensions_svd(vectors_json: &str, target_dims: usize) -> Result<S
     serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
 }
 
-/// Cluster vectors using k-means.
+/// Reduce dimensionality using PCA and report explained variance and loadings.
 ///
 /// # Arguments
 /// * `vectors_json` - JSON array of vectors
-/// * `num_clusters` - Number of clusters
+/// * `target_dims` - Number of components to keep (typically 2 or 3)
+/// * `metric` - Optional distance metric name; Euclidean when omitted, and
+///   `"cosine"` normalises vectors first (see [`vectors_for_metric`])
 ///
 /// # Returns
-/// JSON string of cluster assignments (one per vector)
+/// JSON object with `coordinates`, `explainedVarianceRatio`,
+/// `cumulativeVariance` and `loadings`
+///
+/// # Errors
+/// Returns error if parsing fails, the metric is unknown or unsupported, or reduction fails
+#[wasm_bindgen]
+pub fn reduce_dimensions_pca(
+    vectors_json: &str,
+    target_dims: usize,
+    metric: Option<String>,
+) -> Result<String, JsValue> {
+    let mut vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
+    if let Some(metric) = metric {
+        vectors = vectors_for_metric(vectors, &metric)?;
+    }
+
+    let result = SVDReducer::new()
+        .pca(&vectors, target_dims)
+        .map_err(|e| JsValue::from_str(&format!("Reduction error: {e}")))?;
+
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Align a new layout to a previous one so the view does not jump between refreshes.
+///
+/// # Arguments
+/// * `current_json` - JSON array of `{id, coordinates}` for the new layout
+/// * `previous_json` - JSON array of `{id, coordinates}` for the layout to match
+///
+/// # Returns
+/// JSON array of aligned `{id, coordinates}`, in the order of `current_json`
+///
+/// # Errors
+/// Returns error if parsing fails, dimensions differ, or fewer than two notes are shared
+#[wasm_bindgen]
+pub fn align_layout(current_json: &str, previous_json: &str) -> Result<String, JsValue> {
+    let current: Vec<NotePosition> = serde_json::from_str(current_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse current layout: {e}")))?;
+    let previous: Vec<NotePosition> = serde_json::from_str(previous_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse previous layout: {e}")))?;
+
+    let aligned = procrustes_align(&current, &previous)?;
+
+    serde_json::to_string(&aligned).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Reduce dimensionality using UMAP.
+///
+/// # Arguments
+/// * `vectors_json` - JSON array of vectors
+/// * `target_dims` - Target dimensionality (typically 2 or 3)
+/// * `n_neighbors` - Size of the local neighbourhood (e.g. 15)
+/// * `min_dist` - Minimum distance between embedded points (e.g. 0.1)
+/// * `metric` - Distance metric name (e.g. `"euclidean"`, `"cosine"`)
+/// * `n_epochs` - Number of optimisation epochs (e.g. 200)
+/// * `seed` - Random seed; the same seed always gives the same layout
+///
+/// # Returns
+/// JSON string of reduced vectors
 ///
 /// # Errors
-/// Returns error if parsing fails or clustering fails
+/// Returns error if parsing fails, the metric is unknown, or reduction fails
 #[wasm_bindgen]
-pub fn cluster_vectors(vectors_json: &str, num_clusters: usize) -> Result<String, JsValue> {
+pub fn reduce_dimensions_umap(
+    vectors_json: &str,
+    target_dims: usize,
+    n_neighbors: usize,
+    min_dist: f64,
+    metric: &str,
+    n_epochs: usize,
+    seed: u32,
+) -> Result<String, JsValue> {
     let vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
         .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
 
-    let clusters = simple_kmeans_clustering(&vectors, num_clusters)
-        .map_err(|e| JsValue::from_str(&format!("Clustering error: {e}")))?;
+    let metric = distance_metric_from_name(metric)?;
+    let reducer =
+        UMAPReducer::with_options(n_neighbors, min_dist, metric, n_epochs, u64::from(seed));
+    let result = reducer
+        .reduce(&vectors, target_dims)
+        .map_err(|e| JsValue::from_str(&format!("Reduction error: {e}")))?;
 
-    serde_json::to_string(&clusters)
-        .map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Reduce dimensionality using Barnes-Hut t-SNE.
+///
+/// # Arguments
+/// * `vectors_json` - JSON array of vectors
+/// * `target_dims` - Target dimensionality (2 or 3)
+/// * `perplexity` - Effective number of neighbours (e.g. 30)
+/// * `early_exaggeration` - Affinity multiplier for the early iterations (e.g. 12)
+/// * `learning_rate` - Gradient descent step size (e.g. 200)
+/// * `metric` - Distance metric name (e.g. `"euclidean"`, `"cosine"`)
+/// * `n_iter` - Number of iterations (e.g. 1000)
+/// * `seed` - Random seed; the same seed always gives the same layout
+///
+/// # Returns
+/// JSON string of reduced vectors
+///
+/// # Errors
+/// Returns error if parsing fails, the metric is unknown, or reduction fails
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn reduce_dimensions_tsne(
+    vectors_json: &str,
+    target_dims: usize,
+    perplexity: f64,
+    early_exaggeration: f64,
+    learning_rate: f64,
+    metric: &str,
+    n_iter: usize,
+    seed: u32,
+) -> Result<String, JsValue> {
+    let vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
+
+    let metric = distance_metric_from_name(metric)?;
+    let reducer = TSNEReducer::with_options(
+        perplexity,
+        early_exaggeration,
+        learning_rate,
+        metric,
+        n_iter,
+        u64::from(seed),
+    );
+    let result = reducer
+        .reduce(&vectors, target_dims)
+        .map_err(|e| JsValue::from_str(&format!("Reduction error: {e}")))?;
+
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Reduce a link graph to low-dimensional coordinates using sparse truncated SVD.
+///
+/// The adjacency (or Laplacian) matrix stays in sparse form throughout, so memory
+/// use scales with the number of links rather than the square of the note count.
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+/// * `target_dims` - Target dimensionality (typically 2 or 3)
+/// * `laplacian` - Whether to reduce the graph Laplacian instead of the adjacency matrix
+///
+/// # Returns
+/// JSON string of reduced vectors (one per note)
+///
+/// # Errors
+/// Returns error if parsing fails, link indices are invalid, or reduction fails
+#[wasm_bindgen]
+pub fn reduce_link_graph_svd(
+    note_paths_json: &str,
+    links_json: &str,
+    target_dims: usize,
+    laplacian: bool,
+) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let builder = AdjacencyMatrixBuilder::new(note_paths);
+    let matrix = if laplacian {
+        builder.build_laplacian(links)
+    } else {
+        builder.build(links)
+    }
+    .map_err(|e| JsValue::from_str(&format!("Failed to build matrix: {e}")))?;
+
+    let reducer = TruncatedSVDReducer::new();
+    let result = reducer
+        .reduce_sparse(&matrix, target_dims)
+        .map_err(|e| JsValue::from_str(&format!("Reduction error: {e}")))?;
+
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Embed a link graph with Laplacian eigenmaps.
+///
+/// Links are treated as undirected and self-links are ignored; coordinates
+/// come from the symmetric normalized Laplacian. Disconnected components are
+/// embedded separately and laid out side by side.
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+/// * `target_dims` - Target dimensionality (2 or 3)
+///
+/// # Returns
+/// JSON object with `coordinates` (one per note), `components` (component id
+/// per note), `componentCount` and `converged`
+///
+/// # Errors
+/// Returns error if parsing fails, link indices are invalid, or `target_dims` is not 2 or 3
+#[wasm_bindgen]
+pub fn reduce_link_graph_spectral(
+    note_paths_json: &str,
+    links_json: &str,
+    target_dims: usize,
+) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let options = GraphOptions {
+        directed: false,
+        laplacian: LaplacianKind::SymmetricNormalized,
+        self_loops: false,
+        binarize: false,
+    };
+    let builder = AdjacencyMatrixBuilder::with_options(note_paths, options);
+    let laplacian = builder
+        .build_laplacian(links)
+        .map_err(|e| JsValue::from_str(&format!("Failed to build Laplacian: {e}")))?;
+
+    let result = SpectralReducer::new()
+        .embed(&laplacian, target_dims)
+        .map_err(|e| JsValue::from_str(&format!("Reduction error: {e}")))?;
+
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Rank notes by importance using PageRank.
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+/// * `damping` - Probability of following a link (typically 0.85)
+///
+/// # Returns
+/// JSON object with `scores` (one per note, summing to 1), `iterations` and `converged`
+///
+/// # Errors
+/// Returns error if parsing fails, link indices are invalid, or the damping factor is invalid
+#[wasm_bindgen]
+pub fn note_pagerank(
+    note_paths_json: &str,
+    links_json: &str,
+    damping: f64,
+) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let builder = AdjacencyMatrixBuilder::new(note_paths);
+    let matrix = builder
+        .build(links)
+        .map_err(|e| JsValue::from_str(&format!("Failed to build matrix: {e}")))?;
+
+    let config = PageRankConfig { damping, ..PageRankConfig::default() };
+    let result = pagerank(&matrix, &config)?;
+
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Compute HITS hub and authority scores for notes.
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+///
+/// # Returns
+/// JSON object with `hubs`, `authorities` (one score per note each),
+/// `iterations`, `converged` and `finalChange`
+///
+/// # Errors
+/// Returns error if parsing fails or link indices are invalid
+#[wasm_bindgen]
+pub fn note_hits(note_paths_json: &str, links_json: &str) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let builder = AdjacencyMatrixBuilder::new(note_paths);
+    let matrix = builder
+        .build(links)
+        .map_err(|e| JsValue::from_str(&format!("Failed to build matrix: {e}")))?;
+
+    let result = hits(&matrix, &HitsConfig::default())?;
+
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Find structurally critical notes: high betweenness, articulation points and bridges.
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+/// * `directed` - Whether betweenness follows links only in their direction
+/// * `samples` - Number of source notes to sample for betweenness (0 for exact)
+/// * `seed` - Seed for choosing sampled sources
+///
+/// # Returns
+/// JSON object with `betweenness` (one normalised score per note),
+/// `articulationPoints` (note indices) and `bridges` (`[from, to]` index pairs)
+///
+/// # Errors
+/// Returns error if parsing fails or link indices are invalid
+#[wasm_bindgen]
+pub fn find_critical_notes(
+    note_paths_json: &str,
+    links_json: &str,
+    directed: bool,
+    samples: usize,
+    seed: u32,
+) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let builder = AdjacencyMatrixBuilder::new(note_paths);
+    let matrix = builder
+        .build(links)
+        .map_err(|e| JsValue::from_str(&format!("Failed to build matrix: {e}")))?;
+
+    let config = BetweennessConfig {
+        directed,
+        samples,
+        seed: u64::from(seed),
+        ..BetweennessConfig::default()
+    };
+    let result = critical_notes(&matrix, &config)?;
+
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Score every note by its relevance to a set of seed notes (e.g. a MOC).
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+/// * `seed_paths_json` - JSON array of seed note paths
+/// * `damping` - Probability of following a link (typically 0.85)
+///
+/// # Returns
+/// JSON object with `scores` (one per note, summing to 1), `iterations` and `converged`
+///
+/// # Errors
+/// Returns error if parsing fails, link indices are invalid, a seed path is
+/// unknown, or the damping factor is invalid
+#[wasm_bindgen]
+pub fn note_personalized_pagerank(
+    note_paths_json: &str,
+    links_json: &str,
+    seed_paths_json: &str,
+    damping: f64,
+) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let seed_paths: Vec<String> = serde_json::from_str(seed_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse seed paths: {e}")))?;
+
+    let builder = AdjacencyMatrixBuilder::new(note_paths);
+    let matrix = builder
+        .build(links)
+        .map_err(|e| JsValue::from_str(&format!("Failed to build matrix: {e}")))?;
+
+    let seeds = seed_paths
+        .iter()
+        .map(|path| {
+            builder
+                .get_note_index(path)
+                .ok_or_else(|| PluginError::ValidationError {
+                    field: "seeds".to_string(),
+                    value: path.clone(),
+                    reason: "Seed note is not in the note path list".to_string(),
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let config = PageRankConfig { damping, ..PageRankConfig::default() };
+    let result = personalized_pagerank(&matrix, &seeds, &config)?;
+
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Cluster vectors using k-means.
+///
+/// # Arguments
+/// * `vectors_json` - JSON array of vectors
+/// * `num_clusters` - Number of clusters
+/// * `metric` - Distance metric name (e.g. `"euclidean"`, `"cosine"`, `"inner_product"`)
+///
+/// # Returns
+/// JSON string of cluster assignments (one per vector)
+///
+/// # Errors
+/// Returns error if parsing fails, the metric is unknown, or clustering fails
+#[wasm_bindgen]
+pub fn cluster_vectors(
+    vectors_json: &str,
+    num_clusters: usize,
+    metric: &str,
+) -> Result<String, JsValue> {
+    let vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
+
+    let metric = distance_metric_from_name(metric)?;
+    let clusters = kmeans_clustering_with_metric(&vectors, num_clusters, metric.as_ref())
+        .map_err(|e| JsValue::from_str(&format!("Clustering error: {e}")))?;
+
+    serde_json::to_string(&clusters)
+        .map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Cluster vectors using k-means, choosing the cluster count automatically.
+///
+/// Runs k-means for every count from 2 to 10 and keeps the one with the best
+/// silhouette, so it costs several times more than [`cluster_vectors`].
+///
+/// # Arguments
+/// * `vectors_json` - JSON array of vectors
+/// * `metric` - Distance metric name (e.g. `"euclidean"`, `"cosine"`, `"inner_product"`)
+///
+/// # Returns
+/// JSON string of cluster assignments (one per vector)
+///
+/// # Errors
+/// Returns error if parsing fails, the metric is unknown, or clustering fails
+#[wasm_bindgen]
+pub fn cluster_vectors_auto(vectors_json: &str, metric: &str) -> Result<String, JsValue> {
+    let vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
+
+    let metric = distance_metric_from_name(metric)?;
+    let report = choose_cluster_count(&vectors, &ClusterCountConfig::default(), metric.as_ref())
+        .map_err(|e| JsValue::from_str(&format!("Clustering error: {e}")))?;
+
+    serde_json::to_string(&report.assignments)
+        .map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Cluster vectors by density, leaving outliers unclustered.
+///
+/// # Arguments
+/// * `vectors_json` - JSON array of vectors
+/// * `algorithm` - `"dbscan"` or `"hdbscan"`
+/// * `metric` - Distance metric name (e.g. `"euclidean"`, `"cosine"`, `"inner_product"`)
+/// * `config_json` - JSON config for the algorithm: `eps` and `minPoints` for DBSCAN,
+///   `minClusterSize`, `minSamples` and `allowSingleCluster` for HDBSCAN; missing
+///   fields use defaults
+///
+/// # Returns
+/// JSON string of cluster assignments (one per vector), with -1 for noise
+///
+/// # Errors
+/// Returns error if parsing fails, the algorithm or metric is unknown, or clustering fails
+#[wasm_bindgen]
+pub fn cluster_vectors_density(
+    vectors_json: &str,
+    algorithm: &str,
+    metric: &str,
+    config_json: &str,
+) -> Result<String, JsValue> {
+    let vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
+
+    let algorithm = DensityAlgorithm::from_name(algorithm)?;
+    let metric = distance_metric_from_name(metric)?;
+    let clusters = match algorithm {
+        DensityAlgorithm::Dbscan => {
+            let config: DbscanConfig = serde_json::from_str(config_json)
+                .map_err(|e| JsValue::from_str(&format!("Failed to parse config: {e}")))?;
+            dbscan(&vectors, &config, metric.as_ref())
+        },
+        DensityAlgorithm::Hdbscan => {
+            let config: HdbscanConfig = serde_json::from_str(config_json)
+                .map_err(|e| JsValue::from_str(&format!("Failed to parse config: {e}")))?;
+            hdbscan(&vectors, &config, metric.as_ref()).map(|result| result.labels)
+        },
+    }
+    .map_err(|e| JsValue::from_str(&format!("Clustering error: {e}")))?;
+
+    serde_json::to_string(&clusters)
+        .map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Cluster vectors with HDBSCAN and report how stable each cluster is.
+///
+/// # Arguments
+/// * `vectors_json` - JSON array of vectors
+/// * `metric` - Distance metric name (e.g. `"euclidean"`, `"cosine"`, `"inner_product"`)
+/// * `config_json` - JSON HDBSCAN config (`minClusterSize`, `minSamples`,
+///   `allowSingleCluster`); missing fields use defaults
+///
+/// # Returns
+/// JSON object with `labels` (-1 for noise), `probabilities` and per-cluster `stability`
+///
+/// # Errors
+/// Returns error if parsing fails, the metric is unknown, or clustering fails
+#[wasm_bindgen]
+pub fn cluster_vectors_hdbscan(
+    vectors_json: &str,
+    metric: &str,
+    config_json: &str,
+) -> Result<String, JsValue> {
+    let vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
+    let config: HdbscanConfig = serde_json::from_str(config_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse config: {e}")))?;
+
+    let metric = distance_metric_from_name(metric)?;
+    let result = hdbscan(&vectors, &config, metric.as_ref())
+        .map_err(|e| JsValue::from_str(&format!("Clustering error: {e}")))?;
+
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Cluster vectors agglomeratively into a dendrogram.
+///
+/// # Arguments
+/// * `vectors_json` - JSON array of vectors
+/// * `linkage` - `"single"`, `"complete"`, `"average"` or `"ward"`
+/// * `metric` - Distance metric name (e.g. `"euclidean"`, `"cosine"`, `"inner_product"`)
+///
+/// # Returns
+/// JSON object with `leafCount` and `merges` (`left`, `right`, `height`, `size`),
+/// where merge `i` creates node `leafCount + i`
+///
+/// # Errors
+/// Returns error if parsing fails, the linkage or metric is unknown, or clustering fails
+#[wasm_bindgen]
+pub fn cluster_vectors_hierarchical(
+    vectors_json: &str,
+    linkage: &str,
+    metric: &str,
+) -> Result<String, JsValue> {
+    let vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
+
+    let linkage = Linkage::from_name(linkage)?;
+    let metric = distance_metric_from_name(metric)?;
+    let dendrogram = agglomerative_clustering(&vectors, linkage, metric.as_ref())
+        .map_err(|e| JsValue::from_str(&format!("Clustering error: {e}")))?;
+
+    serde_json::to_string(&dendrogram)
+        .map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Cut a dendrogram into flat clusters.
+///
+/// # Arguments
+/// * `dendrogram_json` - JSON dendrogram from `cluster_vectors_hierarchical`
+/// * `cut_json` - `{"height": h}` to apply merges up to height `h`, or
+///   `{"clusters": k}` to keep `k` clusters
+///
+/// # Returns
+/// JSON string of cluster assignments (one per vector)
+///
+/// # Errors
+/// Returns error if parsing fails, the dendrogram is malformed, or the cut is out of range
+#[wasm_bindgen]
+pub fn cut_dendrogram(dendrogram_json: &str, cut_json: &str) -> Result<String, JsValue> {
+    let dendrogram: Dendrogram = serde_json::from_str(dendrogram_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse dendrogram: {e}")))?;
+    let cut: DendrogramCut = serde_json::from_str(cut_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse cut: {e}")))?;
+
+    let clusters = dendrogram.cut(cut)?;
+
+    serde_json::to_string(&clusters)
+        .map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Name clusters after their most distinctive terms (class-based TF-IDF).
+///
+/// # Arguments
+/// * `assignments_json` - JSON array of cluster ids, one per note (e.g. from
+///   `cluster_vectors`); negative ids are treated as noise and skipped
+/// * `texts_json` - JSON array of note titles or texts, one per note
+/// * `config_json` - JSON labelling config (`topKeywords`, `labelTerms`,
+///   `minDocumentFrequency`, `minTermLength`, `defaultStopwords`, `stopwords`);
+///   missing fields use defaults
+///
+/// # Returns
+/// JSON array with `cluster`, `size`, `label` and `keywords` (`term`, `score`)
+/// per cluster, in increasing cluster id
+///
+/// # Errors
+/// Returns error if parsing fails or the arrays differ in length
+#[wasm_bindgen]
+pub fn label_vector_clusters(
+    assignments_json: &str,
+    texts_json: &str,
+    config_json: &str,
+) -> Result<String, JsValue> {
+    let assignments: Vec<i64> = serde_json::from_str(assignments_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse assignments: {e}")))?;
+    let texts: Vec<String> = serde_json::from_str(texts_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse texts: {e}")))?;
+    let config: ClusterLabelConfig = serde_json::from_str(config_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse config: {e}")))?;
+
+    let labels = label_clusters(&assignments, &texts, &config)?;
+
+    serde_json::to_string(&labels).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Score a range of k-means cluster counts and recommend one.
+///
+/// # Arguments
+/// * `vectors_json` - JSON array of vectors
+/// * `metric` - Distance metric name (e.g. `"euclidean"`, `"cosine"`, `"inner_product"`)
+/// * `config_json` - JSON sweep config (`minK`, `maxK`, `silhouetteSample`,
+///   `kmeans`); missing fields use defaults
+///
+/// # Returns
+/// JSON object with `scores` (`k`, `silhouette`, `inertia`, `daviesBouldin` per
+/// count), `recommendedK`, `elbowK` and the recommended `assignments`
+///
+/// # Errors
+/// Returns error if parsing fails, the metric is unknown, or clustering fails
+#[wasm_bindgen]
+pub fn suggest_cluster_count(
+    vectors_json: &str,
+    metric: &str,
+    config_json: &str,
+) -> Result<String, JsValue> {
+    let vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
+    let config: ClusterCountConfig = serde_json::from_str(config_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse config: {e}")))?;
+
+    let metric = distance_metric_from_name(metric)?;
+    let report = choose_cluster_count(&vectors, &config, metric.as_ref())
+        .map_err(|e| JsValue::from_str(&format!("Clustering error: {e}")))?;
+
+    serde_json::to_string(&report).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Cluster vectors using seeded k-means with restarts.
+///
+/// # Arguments
+/// * `vectors_json` - JSON array of vectors
+/// * `num_clusters` - Number of clusters
+/// * `metric` - Distance metric name (e.g. `"euclidean"`, `"cosine"`, `"inner_product"`)
+/// * `config_json` - JSON k-means config (`restarts`, `maxIterations`, `tolerance`,
+///   `seed`); missing fields use defaults
+///
+/// # Returns
+/// JSON object with `assignments`, `centroids`, `inertia`, `iterations` and `converged`
+///
+/// # Errors
+/// Returns error if parsing fails, the metric is unknown, or clustering fails
+#[wasm_bindgen]
+pub fn cluster_vectors_kmeans(
+    vectors_json: &str,
+    num_clusters: usize,
+    metric: &str,
+    config_json: &str,
+) -> Result<String, JsValue> {
+    let vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
+    let config: KMeansConfig = serde_json::from_str(config_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse config: {e}")))?;
+
+    let metric = distance_metric_from_name(metric)?;
+    let result = kmeans(&vectors, num_clusters, &config, metric.as_ref())
+        .map_err(|e| JsValue::from_str(&format!("Clustering error: {e}")))?;
+
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Group notes into communities from the link graph.
+///
+/// A graph-based alternative to `cluster_vectors` that needs no cluster count.
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+/// * `resolution` - Resolution γ; higher values give more, smaller communities
+/// * `algorithm` - `"louvain"` or `"leiden"`
+/// * `seed` - Seed for the node visiting order
+///
+/// # Returns
+/// JSON object with `assignments` (one community id per note, the same shape
+/// as `cluster_vectors` output), `communityCount`, `modularity` and `levels`
+///
+/// # Errors
+/// Returns error if parsing fails, the algorithm is unknown, or the resolution is not positive
+#[wasm_bindgen]
+pub fn cluster_graph(
+    note_paths_json: &str,
+    links_json: &str,
+    resolution: f64,
+    algorithm: &str,
+    seed: u32,
+) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let builder = AdjacencyMatrixBuilder::new(note_paths);
+    let matrix = builder
+        .build(links)
+        .map_err(|e| JsValue::from_str(&format!("Failed to build matrix: {e}")))?;
+
+    let config = CommunityConfig {
+        resolution,
+        algorithm: CommunityAlgorithm::from_name(algorithm)?,
+        seed: u64::from(seed),
+    };
+    let result = detect_communities(&matrix, &config)?;
+
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Compute a statistics report for the vault's link graph.
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+///
+/// # Returns
+/// JSON object with counts, density, degree distributions, orphans, dead ends,
+/// weak and strong components, average clustering, reciprocity and a diameter estimate
+///
+/// # Errors
+/// Returns error if parsing fails or link indices are invalid
+#[wasm_bindgen]
+pub fn graph_stats(note_paths_json: &str, links_json: &str) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let stats = compute_graph_stats(&note_paths, links)?;
+
+    serde_json::to_string(&stats).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Check whether the link graph is acyclic and report its cycles.
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+/// * `max_cycle_length` - Longest cycle to enumerate, in links
+/// * `max_cycles` - Maximum number of cycles to report
+///
+/// # Returns
+/// JSON object with `isDag`, `cyclicComponents`, `cycles`, `cyclesTruncated` and
+/// `topologicalOrder` (`null` when the graph has cycles), all as note paths
+///
+/// # Errors
+/// Returns error if parsing fails or link indices are invalid
+#[wasm_bindgen]
+pub fn analyze_link_dag(
+    note_paths_json: &str,
+    links_json: &str,
+    max_cycle_length: usize,
+    max_cycles: usize,
+) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let builder = AdjacencyMatrixBuilder::new(note_paths);
+    let config = DagConfig { max_cycle_length, max_cycles };
+    let report = analyze_dag(&builder, links, &config)?;
+
+    serde_json::to_string(&report).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Find how two notes are connected through links.
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+/// * `from` - Path of the note to start at
+/// * `to` - Path of the note to reach
+/// * `k` - Maximum number of alternative paths
+/// * `undirected` - Whether links can be followed in either direction
+///
+/// # Returns
+/// JSON array of paths, each an array of note paths from `from` to `to`, shortest first
+///
+/// # Errors
+/// Returns error if parsing fails, a note is unknown, or link indices are invalid
+#[wasm_bindgen]
+pub fn find_connecting_paths(
+    note_paths_json: &str,
+    links_json: &str,
+    from: &str,
+    to: &str,
+    k: usize,
+    undirected: bool,
+) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let options = GraphOptions { directed: !undirected, ..GraphOptions::default() };
+    let builder = AdjacencyMatrixBuilder::with_options(note_paths, options);
+    let paths = connecting_paths(&builder, links, from, to, k)?;
+
+    serde_json::to_string(&paths).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Find the notes within a number of links of a note.
+///
+/// # Arguments
+/// * `note_paths_json` - JSON array of note paths
+/// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+/// * `from` - Path of the note to start at
+/// * `max_hops` - Largest number of links to follow
+/// * `undirected` - Whether links can be followed in either direction
+///
+/// # Returns
+/// JSON array of `{path, hops}` objects, nearest first, excluding the note itself
+///
+/// # Errors
+/// Returns error if parsing fails, the note is unknown, or link indices are invalid
+#[wasm_bindgen]
+pub fn find_notes_within_hops(
+    note_paths_json: &str,
+    links_json: &str,
+    from: &str,
+    max_hops: usize,
+    undirected: bool,
+) -> Result<String, JsValue> {
+    let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+    let links: Vec<NoteLink> = serde_json::from_str(links_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+    let options = GraphOptions { directed: !undirected, ..GraphOptions::default() };
+    let builder = AdjacencyMatrixBuilder::with_options(note_paths, options);
+    let notes = notes_within_hops(&builder, links, from, max_hops)?;
+
+    serde_json::to_string(&notes).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Find the notes most similar to a query note or vector.
+///
+/// # Arguments
+/// * `notes_json` - JSON array of `VectorWithMetadata` objects
+/// * `query_json` - JSON query, either `{"id": "path.md"}` or `{"vector": [...]}`
+/// * `k` - Maximum number of results
+/// * `metric` - Distance metric name (e.g. `"cosine"`, `"euclidean"`)
+/// * `filter_json` - JSON `{"key": ..., "value": ...}` metadata filter, or `null`
+///
+/// # Returns
+/// JSON array of results (`id`, `label`, `score`, `distance`, `metadata`), most similar first
+///
+/// # Errors
+/// Returns error if parsing fails, the metric or query id is unknown, or dimensions mismatch
+#[wasm_bindgen]
+pub fn related_notes(
+    notes_json: &str,
+    query_json: &str,
+    k: usize,
+    metric: &str,
+    filter_json: &str,
+) -> Result<String, JsValue> {
+    let notes: Vec<VectorWithMetadata> = serde_json::from_str(notes_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse notes: {e}")))?;
+
+    let query: NeighborQuery = serde_json::from_str(query_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse query: {e}")))?;
+
+    let filter: Option<MetadataFilter> = serde_json::from_str(filter_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse filter: {e}")))?;
+
+    let metric = distance_metric_from_name(metric)?;
+    let results = find_related_notes(&notes, &query, k, metric.as_ref(), filter.as_ref())?;
+
+    serde_json::to_string(&results).map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+}
+
+/// Fitted projection that keeps an existing layout stable as notes are added.
+///
+/// Fit once, then `transform` new vectors into the same space instead of
+/// recomputing the whole reduction. Persist with `toJson`/`toBytes`.
+#[wasm_bindgen]
+pub struct NoteProjection {
+    /// The underlying projection model.
+    inner: ProjectionModel,
+}
+
+#[wasm_bindgen]
+impl NoteProjection {
+    /// Fit an SVD projection.
+    ///
+    /// # Arguments
+    /// * `vectors_json` - JSON array of vectors
+    /// * `target_dims` - Target dimensionality (typically 2 or 3)
+    /// * `center` - Whether to center the data
+    /// * `scale` - Whether to scale the data
+    ///
+    /// # Errors
+    /// Returns error if parsing fails or fitting fails
+    #[wasm_bindgen(js_name = fitSvd)]
+    pub fn fit_svd(
+        vectors_json: &str,
+        target_dims: usize,
+        center: bool,
+        scale: bool,
+    ) -> Result<Self, JsValue> {
+        let vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
+        let inner = SVDReducer::with_options(center, scale).fit(&vectors, target_dims)?;
+        Ok(Self { inner })
+    }
+
+    /// Load a projection from JSON produced by `toJson`.
+    ///
+    /// # Errors
+    /// Returns error if the JSON is not a valid projection model
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<Self, JsValue> {
+        Ok(Self { inner: ProjectionModel::from_json(json)? })
+    }
+
+    /// Serialize the projection to JSON.
+    ///
+    /// # Errors
+    /// Returns error if serialization fails
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        Ok(self.inner.to_json()?)
+    }
+
+    /// Load a projection from bytes produced by `toBytes`.
+    ///
+    /// # Errors
+    /// Returns error if the bytes are not a valid projection model
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, JsValue> {
+        Ok(Self { inner: ProjectionModel::from_bytes(bytes)? })
+    }
+
+    /// Serialize the projection to a compact byte blob.
+    #[wasm_bindgen(js_name = toBytes)]
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner.to_bytes()
+    }
+
+    /// Map vectors into the fitted space.
+    ///
+    /// # Arguments
+    /// * `vectors_json` - JSON array of vectors
+    ///
+    /// # Returns
+    /// JSON string of projected vectors
+    ///
+    /// # Errors
+    /// Returns error if parsing fails or dimensions mismatch
+    pub fn transform(&self, vectors_json: &str) -> Result<String, JsValue> {
+        let vectors: Vec<Vec<f64>> = serde_json::from_str(vectors_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {e}")))?;
+        let result = self.inner.transform(&vectors)?;
+        serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+    }
+}
+
+/// Approximate nearest-neighbour index that lives on the wasm side.
+///
+/// Keeps the HNSW graph in wasm memory so queries do not re-send every vector
+/// across the boundary. Persist it with `toBytes` and reload with `fromBytes`.
+#[wasm_bindgen]
+pub struct NoteVectorIndex {
+    /// The underlying HNSW index.
+    inner: HnswIndex<'static>,
+}
+
+#[wasm_bindgen]
+impl NoteVectorIndex {
+    /// Create an empty index.
+    ///
+    /// # Arguments
+    /// * `metric` - Distance metric name (e.g. `"cosine"`)
+    /// * `m` - Maximum neighbours per node on upper layers
+    /// * `ef_construction` - Candidate list size used while inserting
+    /// * `seed` - Seed for the random level assignment
+    ///
+    /// # Errors
+    /// Returns error if the metric is unknown, `m` is below 2 or `ef_construction` is zero
+    #[wasm_bindgen(constructor)]
+    pub fn new(metric: &str, m: usize, ef_construction: usize, seed: u32) -> Result<Self, JsValue> {
+        let metric = distance_metric_from_name(metric)?;
+        let config = HnswConfig { m, ef_construction, seed: u64::from(seed) };
+        Ok(Self { inner: HnswIndex::new(config, metric)? })
+    }
+
+    /// Load an index from bytes produced by `toBytes`.
+    ///
+    /// # Errors
+    /// Returns error if the bytes are not a valid index
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, JsValue> {
+        Ok(Self { inner: HnswIndex::from_bytes(bytes)? })
+    }
+
+    /// Serialize the index to a compact byte blob.
+    #[wasm_bindgen(js_name = toBytes)]
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner.to_bytes()
+    }
+
+    /// Insert or update notes.
+    ///
+    /// # Arguments
+    /// * `records_json` - JSON array of `VectorWithMetadata` objects
+    ///
+    /// # Errors
+    /// Returns error if parsing fails or dimensions mismatch
+    pub fn insert(&mut self, records_json: &str) -> Result<(), JsValue> {
+        let records: Vec<VectorWithMetadata> = serde_json::from_str(records_json)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse records: {e}")))?;
+        for record in records {
+            self.inner.insert(record)?;
+        }
+        Ok(())
+    }
+
+    /// Tombstone a note. Returns whether the note was present.
+    pub fn remove(&mut self, id: &str) -> bool {
+        self.inner.remove(id)
+    }
+
+    /// Rebuild the index without tombstoned notes.
+    ///
+    /// # Errors
+    /// Returns error if the rebuild fails
+    pub fn compact(&mut self) -> Result<(), JsValue> {
+        self.inner.compact()?;
+        Ok(())
+    }
+
+    /// Number of live notes in the index.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Whether the index has no live notes.
+    #[wasm_bindgen(js_name = isEmpty)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Find the notes nearest to a query.
+    ///
+    /// # Arguments
+    /// * `query_json` - JSON query, either `{"id": "path.md"}` or `{"vector": [...]}`
+    /// * `k` - Maximum number of results
+    /// * `ef` - Search candidate list size (higher is slower but more accurate)
+    ///
+    /// # Returns
+    /// JSON array of results (`id`, `label`, `score`, `distance`, `metadata`), most similar first
+    ///
+    /// # Errors
+    /// Returns error if parsing fails, the id is unknown, or dimensions mismatch
+    pub fn search(&self, query_json: &str, k: usize, ef: usize) -> Result<String, JsValue> {
+        let query: NeighborQuery = serde_json::from_str(query_json)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse query: {e}")))?;
+
+        let results = match query {
+            NeighborQuery::Vector(vector) => self.inner.search(&vector, k, ef)?,
+            NeighborQuery::Id(id) => self.inner.search_by_id(&id, k, ef)?,
+        };
+
+        serde_json::to_string(&results)
+            .map_err(|e| JsValue::from_str(&format!("Serialize error: {e}")))
+    }
+}
+
+/// Force-directed layout of the link graph that lives on the wasm side.
+///
+/// Keeps positions and speeds in wasm memory so the UI can call `step` once
+/// per animation frame and draw the returned coordinates.
+#[wasm_bindgen]
+pub struct NoteForceLayout {
+    /// The underlying layout state.
+    inner: ForceLayout,
+}
+
+#[wasm_bindgen]
+impl NoteForceLayout {
+    /// Create a layout with seeded random initial positions.
+    ///
+    /// # Arguments
+    /// * `note_paths_json` - JSON array of note paths
+    /// * `links_json` - JSON array of links (objects with `fromId` and `toId`)
+    /// * `config_json` - JSON `ForceLayoutConfig`; omitted fields use defaults
+    ///
+    /// # Errors
+    /// Returns error if parsing fails, link indices are invalid, or the settings are invalid
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        note_paths_json: &str,
+        links_json: &str,
+        config_json: &str,
+    ) -> Result<Self, JsValue> {
+        let note_paths: Vec<String> = serde_json::from_str(note_paths_json)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse note paths: {e}")))?;
+
+        let links: Vec<NoteLink> = serde_json::from_str(links_json)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse links: {e}")))?;
+
+        let config: ForceLayoutConfig = serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse config: {e}")))?;
+
+        let builder = AdjacencyMatrixBuilder::new(note_paths);
+        let matrix = builder
+            .build(links)
+            .map_err(|e| JsValue::from_str(&format!("Failed to build matrix: {e}")))?;
+
+        Ok(Self { inner: ForceLayout::new(&matrix, config)? })
+    }
+
+    /// Run layout iterations.
+    ///
+    /// # Arguments
+    /// * `iterations` - Number of iterations to run before returning
+    ///
+    /// # Returns
+    /// Flat array of positions, `dims` coordinates per note
+    pub fn step(&mut self, iterations: usize) -> Vec<f64> {
+        self.inner.step(iterations);
+        self.inner.flat_positions()
+    }
+
+    /// Current positions as a flat array, `dims` coordinates per note.
+    #[must_use]
+    pub fn positions(&self) -> Vec<f64> {
+        self.inner.flat_positions()
+    }
+
+    /// Replace every position, e.g. to resume a saved layout.
+    ///
+    /// # Errors
+    /// Returns error if the array length is not `notes × dims` or a coordinate is not finite
+    #[wasm_bindgen(js_name = setPositions)]
+    pub fn set_positions(&mut self, positions: &[f64]) -> Result<(), JsValue> {
+        Ok(self.inner.set_positions(positions)?)
+    }
+
+    /// Move a single note, e.g. while it is being dragged.
+    ///
+    /// # Errors
+    /// Returns error if the index is out of range, the position has the wrong length,
+    /// or a coordinate is not finite
+    #[wasm_bindgen(js_name = setPosition)]
+    pub fn set_position(&mut self, node: usize, position: &[f64]) -> Result<(), JsValue> {
+        Ok(self.inner.set_position(node, position)?)
+    }
+
+    /// Pin a note in place.
+    ///
+    /// # Errors
+    /// Returns error if the index is out of range
+    pub fn pin(&mut self, node: usize) -> Result<(), JsValue> {
+        Ok(self.inner.set_pinned(node, true)?)
+    }
+
+    /// Let a pinned note move again.
+    ///
+    /// # Errors
+    /// Returns error if the index is out of range
+    pub fn unpin(&mut self, node: usize) -> Result<(), JsValue> {
+        Ok(self.inner.set_pinned(node, false)?)
+    }
+
+    /// Change the gravity settings.
+    ///
+    /// # Errors
+    /// Returns error if the gravity is negative or not finite
+    #[wasm_bindgen(js_name = setGravity)]
+    pub fn set_gravity(&mut self, gravity: f64, strong_gravity: bool) -> Result<(), JsValue> {
+        Ok(self.inner.set_gravity(gravity, strong_gravity)?)
+    }
+
+    /// Set the weight of the link between two notes; zero removes it.
+    ///
+    /// # Errors
+    /// Returns error if an index is out of range or the weight is invalid
+    #[wasm_bindgen(js_name = setEdgeWeight)]
+    pub fn set_edge_weight(&mut self, from: usize, to: usize, weight: f64) -> Result<(), JsValue> {
+        Ok(self.inner.set_edge_weight(from, to, weight)?)
+    }
+
+    /// Number of iterations run so far.
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // wasm_bindgen doesn't support const fn
+    pub fn iterations(&self) -> usize {
+        self.inner.iterations()
+    }
+
+    /// Current global speed; it settles as the layout converges.
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // wasm_bindgen doesn't support const fn
+    pub fn speed(&self) -> f64 {
+        self.inner.speed()
+    }
+
+    /// Layout dimensionality (2 or 3).
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // wasm_bindgen doesn't support const fn
+    pub fn dims(&self) -> usize {
+        self.inner.config().dims
+    }
 }
@@ -0,0 +1,520 @@
+//! Markdown link extraction and Obsidian-style link resolution.
+//!
+//! This module turns raw note contents into the `NoteLink` list consumed by
+//! `AdjacencyMatrixBuilder`, resolving link targets against the vault's path
+//! list the same way Obsidian does (case-insensitive, `.md` optional, shortest
+//! matching path wins). Links that cannot be resolved are reported separately.
+
+use crate::{NoteLink, PluginError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Raw content of a single note.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NoteContent {
+    /// Vault-relative note path (must appear in the vault path list).
+    pub path: String,
+    /// Markdown source of the note.
+    pub content: String,
+}
+
+/// Syntax a link was written in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    /// `[[target]]`, including alias, heading and block variants.
+    Wikilink,
+    /// `![[target]]` transclusion.
+    Embed,
+    /// `[text](target)` Markdown link.
+    Markdown,
+}
+
+/// A link as written in the note, before resolution.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ParsedLink {
+    /// Link target with alias, heading and block reference removed.
+    pub target: String,
+    /// Syntax the link was written in.
+    pub kind: LinkKind,
+}
+
+/// A link whose target does not match any path in the vault.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UnresolvedLink {
+    /// Source note index.
+    #[serde(rename = "fromId")]
+    pub from_id: usize,
+    /// Link target as written.
+    pub target: String,
+    /// Syntax the link was written in.
+    pub kind: LinkKind,
+}
+
+/// Result of extracting links from a set of notes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LinkExtractionResult {
+    /// Resolved links, indexed against the vault path list.
+    pub links: Vec<NoteLink>,
+    /// Links whose targets could not be resolved.
+    pub unresolved: Vec<UnresolvedLink>,
+}
+
+/// Extract all links from note contents and resolve them against the vault.
+///
+/// # Arguments
+/// * `notes` - Note contents to scan
+/// * `vault_paths` - Every path in the vault; indices into this list become note ids
+///
+/// # Returns
+/// Resolved `NoteLink`s plus the links that could not be resolved
+///
+/// # Errors
+/// Returns error if a note's path is not present in `vault_paths`
+pub fn extract_links(
+    notes: &[NoteContent],
+    vault_paths: &[String],
+) -> Result<LinkExtractionResult, PluginError> {
+    let resolver = LinkResolver::new(vault_paths);
+    let mut result = LinkExtractionResult::default();
+
+    for note in notes {
+        let from_id =
+            resolver
+                .index_of(&note.path)
+                .ok_or_else(|| PluginError::ValidationError {
+                    field: "path".to_string(),
+                    value: note.path.clone(),
+                    reason: "Note path is not in the vault path list".to_string(),
+                })?;
+
+        for link in parse_links(&note.content) {
+            match resolver.resolve(&link.target, link.kind, Some(from_id)) {
+                Some(to_id) => result.links.push(NoteLink { from_id, to_id }),
+                None => result.unresolved.push(UnresolvedLink {
+                    from_id,
+                    target: link.target,
+                    kind: link.kind,
+                }),
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Parse every link in a Markdown document.
+///
+/// Links inside fenced code blocks and inline code spans are ignored, as are
+/// external URLs and same-note anchors such as `[[#heading]]`.
+///
+/// # Arguments
+/// * `content` - Markdown source
+///
+/// # Returns
+/// Parsed links in document order
+#[must_use]
+pub fn parse_links(content: &str) -> Vec<ParsedLink> {
+    let mut links = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+            continue;
+        }
+        if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+            continue;
+        }
+
+        parse_line(&strip_inline_code(line), &mut links);
+    }
+
+    links
+}
+
+/// Replace inline code spans with spaces so their contents are not parsed.
+fn strip_inline_code(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find('`') {
+        let ticks = rest[start..].bytes().take_while(|&b| b == b'`').count();
+        let delimiter = &rest[start..start + ticks];
+        let after = &rest[start + ticks..];
+        let Some(end) = after.find(delimiter) else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        out.push_str(&" ".repeat(ticks * 2 + end));
+        rest = &after[end + ticks..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Scan a single line for wikilinks and Markdown links.
+fn parse_line(line: &str, links: &mut Vec<ParsedLink>) {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'[' {
+            i += 1;
+            continue;
+        }
+        let embedded = i > 0 && bytes[i - 1] == b'!';
+
+        if line[i..].starts_with("[[") {
+            if let Some(end) = line[i + 2..].find("]]") {
+                let inner = &line[i + 2..i + 2 + end];
+                if let Some(target) = wikilink_target(inner) {
+                    let kind = if embedded {
+                        LinkKind::Embed
+                    } else {
+                        LinkKind::Wikilink
+                    };
+                    links.push(ParsedLink { target, kind });
+                }
+                i += end + 4;
+                continue;
+            }
+        } else if let Some(text_end) = line[i + 1..].find(']') {
+            let open = i + 1 + text_end + 1;
+            if bytes.get(open) == Some(&b'(')
+                && let Some((destination, consumed)) = markdown_destination(&line[open + 1..])
+            {
+                if let Some(target) = markdown_target(destination) {
+                    let kind = if embedded {
+                        LinkKind::Embed
+                    } else {
+                        LinkKind::Markdown
+                    };
+                    links.push(ParsedLink { target, kind });
+                }
+                i = open + 1 + consumed;
+                continue;
+            }
+        }
+        i += 1;
+    }
+}
+
+/// Extract the note target from the inside of a `[[...]]` wikilink.
+fn wikilink_target(inner: &str) -> Option<String> {
+    let before_alias = inner.split('|').next().unwrap_or(inner);
+    // Escaped pipes inside tables leave a trailing backslash behind
+    let before_alias = before_alias.trim_end_matches('\\');
+    let target = before_alias
+        .split(['#', '^'])
+        .next()
+        .unwrap_or(before_alias)
+        .trim();
+
+    if target.is_empty() {
+        None
+    } else {
+        Some(target.to_string())
+    }
+}
+
+/// Split off the destination of a Markdown link, given the text after `(`.
+///
+/// Returns the raw destination and the number of bytes consumed up to and
+/// including the closing parenthesis.
+fn markdown_destination(rest: &str) -> Option<(&str, usize)> {
+    if let Some(bracketed) = rest.strip_prefix('<') {
+        let end = bracketed.find('>')?;
+        let close = bracketed[end..].find(')')?;
+        return Some((&bracketed[..end], 1 + end + close + 1));
+    }
+
+    let close = rest.find(')')?;
+    let inside = rest[..close].trim();
+    // Drop an optional link title: [text](target "title")
+    let destination = inside.split_whitespace().next().unwrap_or(inside);
+    Some((destination, close + 1))
+}
+
+/// Normalise a Markdown link destination into a note target.
+fn markdown_target(destination: &str) -> Option<String> {
+    let is_external = destination.contains("://")
+        || destination.starts_with("mailto:")
+        || destination.starts_with("obsidian:");
+    if is_external || destination.starts_with('#') {
+        return None;
+    }
+
+    let without_fragment = destination.split('#').next().unwrap_or(destination);
+    let decoded = percent_decode(without_fragment);
+    let target = decoded.trim();
+
+    if target.is_empty() {
+        None
+    } else {
+        Some(target.to_string())
+    }
+}
+
+/// Decode `%XX` escapes, leaving malformed sequences untouched.
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(value) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(value);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8(out).unwrap_or_else(|_| input.to_string())
+}
+
+/// Resolves link targets to vault path indices the way Obsidian does.
+///
+/// Matching is case-insensitive and ignores the `.md` extension. A target
+/// containing folders matches any path ending with those folders; when several
+/// paths match, a note in the source's own folder wins, then the shortest path.
+pub struct LinkResolver {
+    /// Normalised lookup key for each path.
+    keys: Vec<String>,
+    /// Map from normalised key to path index.
+    exact: HashMap<String, usize>,
+    /// Map from normalised file name to every path index with that name.
+    by_name: HashMap<String, Vec<usize>>,
+    /// Map from original path to index.
+    path_index: HashMap<String, usize>,
+}
+
+impl LinkResolver {
+    /// Create a resolver for the given vault paths.
+    ///
+    /// # Arguments
+    /// * `vault_paths` - Every path in the vault
+    ///
+    /// # Returns
+    /// A resolver whose results are indices into `vault_paths`
+    #[must_use]
+    pub fn new(vault_paths: &[String]) -> Self {
+        let keys: Vec<String> = vault_paths.iter().map(|p| normalize_key(p)).collect();
+        let mut exact = HashMap::new();
+        let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+
+        for (i, key) in keys.iter().enumerate() {
+            exact.entry(key.clone()).or_insert(i);
+            by_name
+                .entry(file_name(key).to_string())
+                .or_default()
+                .push(i);
+        }
+
+        let path_index = vault_paths
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, path)| (path.clone(), i))
+            .collect();
+
+        Self { keys, exact, by_name, path_index }
+    }
+
+    /// Get the index of an exact vault path.
+    #[must_use]
+    pub fn index_of(&self, path: &str) -> Option<usize> {
+        self.path_index.get(path).copied()
+    }
+
+    /// Resolve a link target to a vault path index.
+    ///
+    /// # Arguments
+    /// * `target` - Link target with alias, heading and block reference removed
+    /// * `kind` - Syntax the link was written in
+    /// * `source` - Index of the note containing the link, if known
+    ///
+    /// # Returns
+    /// Index of the resolved path, or `None` if no path matches
+    #[must_use]
+    pub fn resolve(&self, target: &str, kind: LinkKind, source: Option<usize>) -> Option<usize> {
+        let key = normalize_key(target);
+        if key.is_empty() {
+            return None;
+        }
+        let source_folder = source
+            .and_then(|s| self.keys.get(s))
+            .map(|k| parent_folder(k));
+
+        // Markdown links and explicit ./ or ../ paths are relative to the source note
+        let explicit_relative = target.starts_with("./") || target.starts_with("../");
+        if (kind == LinkKind::Markdown || explicit_relative)
+            && let Some(folder) = source_folder
+            && let Some(joined) = join_relative(folder, &key)
+            && let Some(&index) = self.exact.get(&joined)
+        {
+            return Some(index);
+        }
+
+        if let Some(&index) = self.exact.get(&key) {
+            return Some(index);
+        }
+
+        let suffix = format!("/{key}");
+        self.by_name
+            .get(file_name(&key))?
+            .iter()
+            .copied()
+            .filter(|&i| self.keys[i].ends_with(&suffix))
+            .min_by_key(|&i| {
+                let same_folder = source_folder == Some(parent_folder(&self.keys[i]));
+                (!same_folder, self.keys[i].matches('/').count(), &self.keys[i])
+            })
+    }
+}
+
+/// Lower-case a path or target, trim leading slashes and drop the `.md` extension.
+fn normalize_key(path: &str) -> String {
+    let lowered = path.trim().trim_start_matches('/').to_lowercase();
+    lowered
+        .strip_suffix(".md")
+        .map_or_else(|| lowered.clone(), str::to_string)
+}
+
+/// Last path component of a normalised key.
+fn file_name(key: &str) -> &str {
+    key.rsplit('/').next().unwrap_or(key)
+}
+
+/// Folder portion of a normalised key (empty for the vault root).
+fn parent_folder(key: &str) -> &str {
+    key.rfind('/').map_or("", |i| &key[..i])
+}
+
+/// Join a relative target onto a folder, resolving `.` and `..` segments.
+///
+/// Returns `None` if the path escapes the vault root.
+fn join_relative(folder: &str, target: &str) -> Option<String> {
+    let mut segments: Vec<&str> = folder.split('/').filter(|s| !s.is_empty()).collect();
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {},
+            ".." => {
+                segments.pop()?;
+            },
+            other => segments.push(other),
+        }
+    }
+    Some(segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_wikilink_variants() {
+        let content = "[[Alpha]] [[Beta|alias]] [[Gamma#Heading]] [[Delta^block]] ![[Embed.png]]";
+        let links = parse_links(content);
+
+        let targets: Vec<&str> = links.iter().map(|l| l.target.as_str()).collect();
+        assert_eq!(targets, vec!["Alpha", "Beta", "Gamma", "Delta", "Embed.png"]);
+        assert_eq!(links[0].kind, LinkKind::Wikilink);
+        assert_eq!(links[4].kind, LinkKind::Embed);
+    }
+
+    #[test]
+    fn test_parse_markdown_links() {
+        let content =
+            "See [doc](sub/My%20Note.md#part) and [site](https://example.com) and [top](#x)";
+        let links = parse_links(content);
+
+        assert_eq!(
+            links,
+            vec![ParsedLink { target: "sub/My Note.md".to_string(), kind: LinkKind::Markdown }]
+        );
+    }
+
+    #[test]
+    fn test_parse_ignores_code() {
+        let content = "```\n[[InFence]]\n```\nText `[[InCode]]` and [[Real]]";
+        let links = parse_links(content);
+
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, "Real");
+    }
+
+    #[test]
+    fn test_resolve_case_insensitive_and_extension_optional() {
+        let resolver = LinkResolver::new(&paths(&["Folder/Note.md", "Other.md"]));
+
+        assert_eq!(resolver.resolve("note", LinkKind::Wikilink, None), Some(0));
+        assert_eq!(resolver.resolve("NOTE.md", LinkKind::Wikilink, None), Some(0));
+        assert_eq!(resolver.resolve("folder/note", LinkKind::Wikilink, None), Some(0));
+        assert_eq!(resolver.resolve("missing", LinkKind::Wikilink, None), None);
+    }
+
+    #[test]
+    fn test_resolve_ambiguous_prefers_same_folder_then_shortest() {
+        let resolver = LinkResolver::new(&paths(&["a/b/Note.md", "c/Note.md", "a/Source.md"]));
+
+        assert_eq!(resolver.resolve("Note", LinkKind::Wikilink, None), Some(1));
+        assert_eq!(resolver.resolve("b/Note", LinkKind::Wikilink, None), Some(0));
+
+        let resolver = LinkResolver::new(&paths(&["x/Note.md", "Note.md", "x/Source.md"]));
+        assert_eq!(resolver.resolve("Note", LinkKind::Wikilink, Some(2)), Some(1));
+    }
+
+    #[test]
+    fn test_resolve_relative_markdown_link() {
+        let resolver = LinkResolver::new(&paths(&["a/One.md", "b/Two.md", "Two.md"]));
+
+        assert_eq!(resolver.resolve("../b/Two.md", LinkKind::Markdown, Some(0)), Some(1));
+        assert_eq!(resolver.resolve("Two.md", LinkKind::Markdown, Some(0)), Some(2));
+    }
+
+    #[test]
+    fn test_extract_links_reports_unresolved() {
+        let vault = paths(&["One.md", "Two.md"]);
+        let notes = vec![NoteContent {
+            path: "One.md".to_string(),
+            content: "[[Two]] [[Nowhere]]".to_string(),
+        }];
+
+        let result = extract_links(&notes, &vault).expect("Extraction failed");
+
+        assert_eq!(result.links, vec![NoteLink { from_id: 0, to_id: 1 }]);
+        assert_eq!(
+            result.unresolved,
+            vec![UnresolvedLink {
+                from_id: 0,
+                target: "Nowhere".to_string(),
+                kind: LinkKind::Wikilink,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_extract_links_unknown_note_path() {
+        let notes = vec![NoteContent { path: "Missing.md".to_string(), content: String::new() }];
+        let result = extract_links(&notes, &paths(&["One.md"]));
+
+        assert!(matches!(result, Err(PluginError::ValidationError { .. })));
+    }
+}
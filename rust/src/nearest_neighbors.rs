@@ -0,0 +1,369 @@
+//! Exact k-nearest-neighbour queries over note vectors.
+//!
+//! Powers "related notes" lookups: given a query vector or the id of an
+//! existing note, return the most similar notes under any `DistanceMetric`.
+
+use crate::{DistanceMetric, HnswConfig, HnswIndex, PluginError, VectorWithMetadata};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// What to search for.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum NeighborQuery {
+    /// Search with an explicit query vector.
+    Vector(Vec<f64>),
+    /// Search with the vector of an existing note, which is excluded from the results.
+    Id(String),
+}
+
+/// Restrict results to notes whose metadata has `key` set to `value`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MetadataFilter {
+    /// Metadata key to match.
+    pub key: String,
+    /// Required metadata value.
+    pub value: String,
+}
+
+impl MetadataFilter {
+    /// Check whether a note passes this filter.
+    #[must_use]
+    pub fn matches(&self, note: &VectorWithMetadata) -> bool {
+        note.metadata.get(&self.key) == Some(&self.value)
+    }
+}
+
+/// A single nearest-neighbour result.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NeighborResult {
+    /// Note file path or unique ID.
+    pub id: String,
+    /// Note title or display name.
+    pub label: String,
+    /// Similarity score (higher is more similar).
+    pub score: f64,
+    /// Raw distance under the chosen metric (lower is more similar).
+    pub distance: f64,
+    /// Metadata of the matched note.
+    pub metadata: HashMap<String, String>,
+}
+
+/// Candidate kept in a bounded top-k heap.
+///
+/// Ordered by distance, then by index so results are deterministic under ties.
+#[derive(Debug, Clone, Copy)]
+pub struct NeighborCandidate {
+    /// Distance to the query.
+    pub distance: f64,
+    /// Index of the candidate in the searched collection.
+    pub index: usize,
+}
+
+impl PartialEq for NeighborCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for NeighborCandidate {}
+
+impl PartialOrd for NeighborCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NeighborCandidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+/// Find the `k` notes most similar to a query.
+///
+/// # Arguments
+/// * `notes` - Notes to search
+/// * `query` - Query vector or id of a note in `notes`
+/// * `k` - Maximum number of results
+/// * `metric` - Distance metric used for ranking
+/// * `filter` - Optional metadata filter applied before ranking
+///
+/// # Returns
+/// Up to `k` results, most similar first
+///
+/// # Errors
+/// Returns error if the query id is unknown or vector dimensions do not match
+pub fn find_related_notes(
+    notes: &[VectorWithMetadata],
+    query: &NeighborQuery,
+    k: usize,
+    metric: &dyn DistanceMetric,
+    filter: Option<&MetadataFilter>,
+) -> Result<Vec<NeighborResult>, PluginError> {
+    let (query_vector, exclude) =
+        match query {
+            NeighborQuery::Vector(vector) => (vector.as_slice(), None),
+            NeighborQuery::Id(id) => {
+                let note = notes.iter().find(|n| &n.id == id).ok_or_else(|| {
+                    PluginError::ValidationError {
+                        field: "id".to_string(),
+                        value: id.clone(),
+                        reason: "No note with this id".to_string(),
+                    }
+                })?;
+                (note.vector.as_slice(), Some(id.as_str()))
+            },
+        };
+
+    let candidates = notes.iter().enumerate().filter(|(_, note)| {
+        exclude != Some(note.id.as_str()) && filter.is_none_or(|f| f.matches(note))
+    });
+
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for (index, note) in candidates {
+        let distance = metric
+            .distance(query_vector, &note.vector)
+            .map_err(|e| match e {
+                PluginError::InvalidVectorDimensions { expected, got, .. } => {
+                    PluginError::InvalidVectorDimensions { expected, got, vector_index: index }
+                },
+                other => other,
+            })?;
+        push_bounded(&mut heap, NeighborCandidate { distance, index }, k);
+    }
+
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|c| {
+            let note = &notes[c.index];
+            NeighborResult {
+                id: note.id.clone(),
+                label: note.label.clone(),
+                score: metric.similarity(c.distance),
+                distance: c.distance,
+                metadata: note.metadata.clone(),
+            }
+        })
+        .collect())
+}
+
+/// Compute the `k` nearest neighbours of every vector (excluding itself).
+///
+/// Small inputs use an exact all-pairs search; larger inputs go through an
+/// HNSW index so neighbourhood graphs stay tractable for big vaults.
+///
+/// # Arguments
+/// * `vectors` - Input vectors
+/// * `k` - Neighbours per vector
+/// * `metric` - Distance metric
+///
+/// # Returns
+/// For each vector, its neighbours sorted from nearest to farthest
+///
+/// # Errors
+/// Returns error if vectors have mismatched dimensions
+pub fn k_nearest_neighbor_graph(
+    vectors: &[Vec<f64>],
+    k: usize,
+    metric: &dyn DistanceMetric,
+) -> Result<Vec<Vec<NeighborCandidate>>, PluginError> {
+    if vectors.len() > EXACT_GRAPH_LIMIT {
+        return approximate_neighbor_graph(vectors, k, metric);
+    }
+
+    vectors
+        .iter()
+        .enumerate()
+        .map(|(i, query)| {
+            let mut heap = BinaryHeap::with_capacity(k + 1);
+            for (j, other) in vectors.iter().enumerate() {
+                if i != j {
+                    let distance = metric.distance(query, other).map_err(|e| match e {
+                        PluginError::InvalidVectorDimensions { expected, got, .. } => {
+                            PluginError::InvalidVectorDimensions { expected, got, vector_index: j }
+                        },
+                        other => other,
+                    })?;
+                    push_bounded(&mut heap, NeighborCandidate { distance, index: j }, k);
+                }
+            }
+            Ok(heap.into_sorted_vec())
+        })
+        .collect()
+}
+
+/// Above this many vectors the neighbour graph is built with HNSW.
+const EXACT_GRAPH_LIMIT: usize = 2048;
+
+/// Build a neighbour graph via an HNSW index.
+fn approximate_neighbor_graph(
+    vectors: &[Vec<f64>],
+    k: usize,
+    metric: &dyn DistanceMetric,
+) -> Result<Vec<Vec<NeighborCandidate>>, PluginError> {
+    let records = vectors
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            VectorWithMetadata::new(i.to_string(), String::new(), v.clone(), String::new())
+        })
+        .collect();
+    let index = HnswIndex::build(records, HnswConfig::default(), Box::new(metric))?;
+
+    let ef = (2 * k).max(64);
+    (0..vectors.len())
+        .map(|i| {
+            let results = index.search_by_id(&i.to_string(), k, ef)?;
+            Ok(results
+                .into_iter()
+                .filter_map(|r| {
+                    r.id.parse()
+                        .ok()
+                        .map(|index| NeighborCandidate { distance: r.distance, index })
+                })
+                .collect())
+        })
+        .collect()
+}
+
+/// Push a candidate onto a max-heap, keeping only the `k` closest.
+pub fn push_bounded(
+    heap: &mut BinaryHeap<NeighborCandidate>,
+    candidate: NeighborCandidate,
+    k: usize,
+) {
+    if k == 0 {
+        return;
+    }
+    if heap.len() < k {
+        heap.push(candidate);
+    } else if heap.peek().is_some_and(|worst| candidate < *worst) {
+        heap.pop();
+        heap.push(candidate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cosine, Euclidean};
+
+    fn note(id: &str, vector: Vec<f64>, folder: &str) -> VectorWithMetadata {
+        let mut note =
+            VectorWithMetadata::new(id.to_string(), id.to_uppercase(), vector, "test".to_string());
+        note.add_metadata("folder".to_string(), folder.to_string());
+        note
+    }
+
+    fn sample_notes() -> Vec<VectorWithMetadata> {
+        vec![
+            note("a", vec![1.0, 0.0], "x"),
+            note("b", vec![0.9, 0.1], "y"),
+            note("c", vec![0.0, 1.0], "x"),
+            note("d", vec![0.7, 0.3], "x"),
+        ]
+    }
+
+    #[test]
+    fn test_related_by_id_excludes_query() {
+        let notes = sample_notes();
+        let results =
+            find_related_notes(&notes, &NeighborQuery::Id("a".to_string()), 2, &Cosine, None)
+                .expect("Query failed");
+
+        let ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "d"]);
+        assert!(results[0].score > results[1].score);
+        assert_eq!(results[0].label, "B");
+    }
+
+    #[test]
+    fn test_related_by_vector_with_filter() {
+        let notes = sample_notes();
+        let filter = MetadataFilter { key: "folder".to_string(), value: "x".to_string() };
+        let results = find_related_notes(
+            &notes,
+            &NeighborQuery::Vector(vec![0.8, 0.2]),
+            10,
+            &Euclidean,
+            Some(&filter),
+        )
+        .expect("Query failed");
+
+        let ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["d", "a", "c"]);
+    }
+
+    #[test]
+    fn test_k_nearest_neighbor_graph() {
+        let vectors = vec![vec![0.0], vec![1.0], vec![3.0], vec![7.0]];
+        let graph = k_nearest_neighbor_graph(&vectors, 2, &Euclidean).expect("Graph failed");
+
+        let indices: Vec<Vec<usize>> = graph
+            .iter()
+            .map(|row| row.iter().map(|c| c.index).collect())
+            .collect();
+        assert_eq!(indices, vec![vec![1, 2], vec![0, 2], vec![1, 0], vec![2, 1]]);
+        assert!((graph[3][0].distance - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_related_unknown_id() {
+        let notes = sample_notes();
+        let result =
+            find_related_notes(&notes, &NeighborQuery::Id("zzz".to_string()), 2, &Cosine, None);
+
+        assert!(matches!(result, Err(PluginError::ValidationError { .. })));
+    }
+
+    #[test]
+    fn test_related_dimension_mismatch_reports_index() {
+        let mut notes = sample_notes();
+        notes[2].vector = vec![1.0];
+        let result =
+            find_related_notes(&notes, &NeighborQuery::Vector(vec![1.0, 0.0]), 2, &Euclidean, None);
+
+        match result {
+            Err(PluginError::InvalidVectorDimensions { vector_index, .. }) => {
+                assert_eq!(vector_index, 2);
+            },
+            _ => panic!("Expected InvalidVectorDimensions error"),
+        }
+    }
+
+    /// Euclidean distance doubled, under a name no built-in metric uses.
+    struct DoubledEuclidean;
+
+    impl DistanceMetric for DoubledEuclidean {
+        fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
+            Ok(2.0 * Euclidean.distance(a, b)?)
+        }
+
+        fn name(&self) -> &'static str {
+            "doubled_euclidean"
+        }
+    }
+
+    #[test]
+    fn test_large_graph_uses_callers_metric() {
+        let width = 50;
+        #[allow(clippy::cast_precision_loss)]
+        let vectors: Vec<Vec<f64>> = (0..EXACT_GRAPH_LIMIT + 52)
+            .map(|i| vec![(i % width) as f64, (i / width) as f64])
+            .collect();
+        let graph = k_nearest_neighbor_graph(&vectors, 4, &DoubledEuclidean).expect("Graph failed");
+
+        // Interior grid points have four neighbours one step away
+        for i in [width + 1, 20 * width + 25, 40 * width + 48] {
+            let mut neighbors: Vec<usize> = graph[i].iter().map(|c| c.index).collect();
+            neighbors.sort_unstable();
+            assert_eq!(neighbors, vec![i - width, i - 1, i + 1, i + width]);
+            assert!(graph[i].iter().all(|c| (c.distance - 2.0).abs() < 1e-12));
+        }
+    }
+}
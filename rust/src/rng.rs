@@ -0,0 +1,75 @@
+//! Small seedable pseudo-random number generator.
+//!
+//! Randomized algorithms in this crate must be reproducible across runs and
+//! platforms, so they draw from this generator instead of an OS entropy source.
+
+/// `SplitMix64` generator with helpers for the distributions we need.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    /// Internal generator state.
+    state: u64,
+    /// Cached second value from the last Box-Muller draw.
+    spare_normal: Option<f64>,
+}
+
+impl SeededRng {
+    /// Create a new generator from a seed.
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed, spare_normal: None }
+    }
+
+    /// Current internal state, for persisting and later resuming the sequence.
+    #[must_use]
+    pub const fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// Next raw 64-bit value.
+    pub const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform sample in `[0, 1)`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn next_f64(&mut self) -> f64 {
+        // Use the top 53 bits so every value is exactly representable
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform sample in `[0, upper)`. Returns 0 when `upper` is 0.
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn next_index(&mut self, upper: usize) -> usize {
+        if upper == 0 {
+            return 0;
+        }
+        (self.next_u64() % (upper as u64)) as usize
+    }
+
+    /// Shuffle a slice in place (Fisher-Yates).
+    pub const fn shuffle<T>(&mut self, items: &mut [T]) {
+        let mut i = items.len();
+        while i > 1 {
+            let j = self.next_index(i);
+            i -= 1;
+            items.swap(i, j);
+        }
+    }
+
+    /// Standard normal sample using the Box-Muller transform.
+    pub fn next_normal(&mut self) -> f64 {
+        if let Some(spare) = self.spare_normal.take() {
+            return spare;
+        }
+        let u1 = self.next_f64().max(f64::MIN_POSITIVE);
+        let u2 = self.next_f64();
+        let radius = (-2.0 * u1.ln()).sqrt();
+        let angle = std::f64::consts::TAU * u2;
+        self.spare_normal = Some(radius * angle.sin());
+        radius * angle.cos()
+    }
+}
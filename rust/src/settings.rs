@@ -10,11 +10,15 @@ pub struct PluginSettings {
     /// Example setting value
     #[serde(rename = "mySetting")]
     pub my_setting: String,
+    /// Pick the number of vector clusters by silhouette instead of from the
+    /// note count; runs k-means several times, so it is off by default
+    #[serde(rename = "autoClusterCount", default)]
+    pub auto_cluster_count: bool,
 }
 
 impl Default for PluginSettings {
     fn default() -> Self {
-        Self { my_setting: "default".to_string() }
+        Self { my_setting: "default".to_string(), auto_cluster_count: false }
     }
 }
 
@@ -34,6 +38,16 @@ pub fn validate_setting_internal(key: &str, value: &str) -> Result<(), PluginErr
             }
             Ok(())
         },
+        "autoClusterCount" => {
+            if value.parse::<bool>().is_err() {
+                return Err(PluginError::ValidationError {
+                    field: key.to_string(),
+                    value: value.to_string(),
+                    reason: "Setting value must be true or false".to_string(),
+                });
+            }
+            Ok(())
+        },
         _ => Err(PluginError::UnknownSetting { key: key.to_string() }),
     }
 }
@@ -80,7 +94,7 @@ pub fn get_default_settings() -> String {
 /// * `value` - Setting value to validate
 ///
 /// # Returns
-/// `Ok(())` if valid, or error as JsValue
+/// `Ok(())` if valid, or error as `JsValue`
 ///
 /// # Errors
 /// Returns `JsValue` error if validation fails or key is unknown
@@ -103,10 +117,14 @@ pub fn merge_settings(defaults: &str, loaded: &str) -> String {
     let mut merged = deserialize_settings(defaults).unwrap_or_else(|_| PluginSettings::default());
 
     if let Ok(loaded_settings) = serde_json::from_str::<serde_json::Value>(loaded) {
-        if let Some(my_setting) = loaded_settings.get("mySetting") {
-            if let Some(value) = my_setting.as_str() {
-                merged.my_setting = value.to_string();
-            }
+        if let Some(value) = loaded_settings.get("mySetting").and_then(|v| v.as_str()) {
+            merged.my_setting = value.to_string();
+        }
+        if let Some(value) = loaded_settings
+            .get("autoClusterCount")
+            .and_then(serde_json::Value::as_bool)
+        {
+            merged.auto_cluster_count = value;
         }
     }
 
@@ -0,0 +1,221 @@
+//! Barnes-Hut space-partitioning tree for force-based layouts.
+//!
+//! A quadtree (2D) or octree (3D) over weighted points. Distant cells are
+//! summarised by their total mass at their centre of mass, which turns
+//! O(N²) pairwise force sums into O(N log N).
+
+/// Largest supported dimensionality; 2D trees leave the last axis at zero.
+pub const MAX_DIMS: usize = 3;
+
+/// Cells narrower than this stop subdividing and hold duplicate points together.
+const MIN_CELL_WIDTH: f64 = 1e-10;
+
+/// One cell of a [`SpaceTree`].
+struct Cell {
+    /// Geometric centre of the cell.
+    center: [f64; MAX_DIMS],
+    /// Half of the cell's side length.
+    half_width: f64,
+    /// Mass-weighted sum of the positions in the cell while building; the
+    /// centre of mass once the tree is built.
+    mass_center: [f64; MAX_DIMS],
+    /// Total mass of the points in the cell.
+    mass: f64,
+    /// Number of points in the cell.
+    count: usize,
+    /// Index of the first of 2^d contiguous children, if subdivided.
+    first_child: Option<usize>,
+    /// A point stored in this leaf; further points only share a leaf when they coincide.
+    point: Option<usize>,
+}
+
+impl Cell {
+    /// Create an empty cell.
+    const fn new(center: [f64; MAX_DIMS], half_width: f64) -> Self {
+        Self {
+            center,
+            half_width,
+            mass_center: [0.0; MAX_DIMS],
+            mass: 0.0,
+            count: 0,
+            first_child: None,
+            point: None,
+        }
+    }
+
+    /// Index offset of the child quadrant/octant containing `point`.
+    fn child_offset(&self, point: &[f64]) -> usize {
+        point
+            .iter()
+            .zip(&self.center)
+            .enumerate()
+            .map(|(d, (p, c))| usize::from(p > c) << d)
+            .sum()
+    }
+}
+
+/// Quadtree (2D) or octree (3D) over weighted points.
+pub struct SpaceTree {
+    /// Arena of cells; index 0 is the root.
+    cells: Vec<Cell>,
+    /// Point dimensionality.
+    dims: usize,
+}
+
+impl SpaceTree {
+    /// Build a tree containing every point of `y`, with one mass per point.
+    ///
+    /// The root cell is sized from finite coordinates only. An infinite or NaN
+    /// bound would make every cell width infinite or NaN, and a leaf could then
+    /// never get below `MIN_CELL_WIDTH` and would subdivide forever.
+    pub fn build(y: &[Vec<f64>], masses: &[f64]) -> Self {
+        let dims = y.first().map_or(0, Vec::len).min(MAX_DIMS);
+        let mut min = [0.0; MAX_DIMS];
+        let mut max = [0.0; MAX_DIMS];
+        for d in 0..dims {
+            let finite = || y.iter().map(|p| p[d]).filter(|x| x.is_finite());
+            if let (Some(lo), Some(hi)) = (finite().reduce(f64::min), finite().reduce(f64::max)) {
+                (min[d], max[d]) = (lo, hi);
+            }
+        }
+        let mut center = [0.0; MAX_DIMS];
+        for d in 0..dims {
+            center[d] = f64::midpoint(min[d], max[d]);
+        }
+        let half_width = (0..dims)
+            .map(|d| (max[d] - min[d]) / 2.0)
+            .fold(0.0, f64::max)
+            + 1e-5;
+
+        let mut tree = Self { cells: Vec::with_capacity(2 * y.len()), dims };
+        tree.cells.push(Cell::new(center, half_width));
+        for (index, point) in y.iter().enumerate() {
+            tree.insert(index, point, y, masses);
+        }
+        for cell in tree.cells.iter_mut().filter(|c| c.count > 0) {
+            for c in &mut cell.mass_center {
+                *c /= cell.mass;
+            }
+        }
+        tree
+    }
+
+    /// Insert point `index` at `point`, subdividing leaves as needed.
+    fn insert(&mut self, index: usize, point: &[f64], y: &[Vec<f64>], masses: &[f64]) {
+        let mass = masses[index];
+        let mut cell = 0;
+        loop {
+            let current = &mut self.cells[cell];
+            current.count += 1;
+            current.mass += mass;
+            for (sum, p) in current.mass_center.iter_mut().zip(point) {
+                *sum += mass * p;
+            }
+
+            if let Some(first) = current.first_child {
+                cell = first + current.child_offset(point);
+                continue;
+            }
+            let Some(resident) = current.point else {
+                current.point = Some(index);
+                return;
+            };
+            if current.half_width < MIN_CELL_WIDTH {
+                return;
+            }
+
+            // Split the leaf and push its single resident down one level
+            current.point = None;
+            let first = self.subdivide(cell);
+            let resident_cell = first + self.cells[cell].child_offset(&y[resident]);
+            let child = &mut self.cells[resident_cell];
+            child.count = 1;
+            child.mass = masses[resident];
+            for (sum, p) in child.mass_center.iter_mut().zip(&y[resident]) {
+                *sum = masses[resident] * p;
+            }
+            child.point = Some(resident);
+            cell = first + self.cells[cell].child_offset(point);
+        }
+    }
+
+    /// Create the 2^d children of `cell` and return the index of the first.
+    fn subdivide(&mut self, cell: usize) -> usize {
+        let first = self.cells.len();
+        let parent_center = self.cells[cell].center;
+        let quarter = self.cells[cell].half_width / 2.0;
+        for offset in 0..(1 << self.dims) {
+            let mut center = parent_center;
+            for (d, c) in center.iter_mut().enumerate().take(self.dims) {
+                *c += if offset >> d & 1 == 1 {
+                    quarter
+                } else {
+                    -quarter
+                };
+            }
+            self.cells.push(Cell::new(center, quarter));
+        }
+        self.cells[cell].first_child = Some(first);
+        first
+    }
+
+    /// Visit the cells (or points) that interact with point `i`.
+    ///
+    /// A cell is summarised when its side is below `theta` × its distance to
+    /// the point. The visitor receives the interacting mass (excluding point
+    /// `i`'s own mass), the offset from the centre of mass to point `i`, and
+    /// the squared distance.
+    pub fn for_each_interaction(
+        &self,
+        i: usize,
+        yi: &[f64],
+        own_mass: f64,
+        theta: f64,
+        mut visit: impl FnMut(f64, &[f64; MAX_DIMS], f64),
+    ) {
+        let mut stack = vec![0];
+        while let Some(cell) = stack.pop() {
+            let cell = &self.cells[cell];
+            if cell.count == 0 {
+                continue;
+            }
+
+            let mut offset = [0.0; MAX_DIMS];
+            for d in 0..self.dims {
+                offset[d] = yi[d] - cell.mass_center[d];
+            }
+            let dist_sq: f64 = offset.iter().map(|o| o * o).sum();
+
+            let mass = match (cell.first_child, cell.point) {
+                (Some(first), _)
+                    if 4.0 * cell.half_width * cell.half_width >= theta * theta * dist_sq =>
+                {
+                    stack.extend(first..first + (1 << self.dims));
+                    continue;
+                },
+                // A leaf holding point `i` itself (possibly with exact duplicates)
+                (None, Some(j)) if j == i || dist_sq < MIN_CELL_WIDTH * MIN_CELL_WIDTH => {
+                    cell.mass - own_mass
+                },
+                _ => cell.mass,
+            };
+            if mass > 0.0 {
+                visit(mass, &offset, dist_sq);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_finite_points_do_not_hang_the_build() {
+        let y = vec![vec![0.0, 1.0], vec![f64::INFINITY, 2.0], vec![f64::NAN, f64::NEG_INFINITY]];
+        let tree = SpaceTree::build(&y, &[1.0; 3]);
+
+        assert!(tree.cells[0].half_width.is_finite());
+        assert_eq!(tree.cells[0].count, 3);
+    }
+}
@@ -0,0 +1,358 @@
+//! Truncated SVD that operates directly on sparse matrices.
+//!
+//! Link-graph matrices are N×N but contain only a handful of non-zeros per row,
+//! so densifying them before a full SVD is wasteful. This module implements a
+//! randomized truncated SVD (Halko, Martinsson & Tropp) that only ever touches
+//! the sparse matrix through products with thin dense blocks of width
+//! `target_dims + oversampling`.
+
+use crate::rng::SeededRng;
+use crate::{DimensionalityReducer, PluginError, canonical_sign};
+use nalgebra::{DMatrix, DVector};
+use sprs::{CsMat, TriMat};
+
+/// Randomized truncated SVD on sparse CSR matrices.
+///
+/// Centering is applied implicitly (A - 1μᵀ is never materialised), so the
+/// output matches a dense, centered SVD without losing sparsity.
+pub struct TruncatedSVDReducer {
+    /// Whether to center data (subtract column means).
+    center: bool,
+    /// Extra random directions sampled beyond `target_dims`.
+    oversampling: usize,
+    /// Number of power iterations used to sharpen the spectrum.
+    power_iterations: usize,
+    /// Seed for the random test matrix.
+    seed: u64,
+}
+
+impl TruncatedSVDReducer {
+    /// Create a new truncated SVD reducer with default settings.
+    ///
+    /// Default: center=true, oversampling=10, `power_iterations`=4, seed=42
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { center: true, oversampling: 10, power_iterations: 4, seed: 42 }
+    }
+
+    /// Create a new truncated SVD reducer with custom settings.
+    ///
+    /// # Arguments
+    /// * `center` - Whether to center the data
+    /// * `oversampling` - Extra random directions beyond `target_dims`
+    /// * `power_iterations` - Number of power iterations
+    /// * `seed` - Seed for the random test matrix
+    #[must_use]
+    pub const fn with_options(
+        center: bool,
+        oversampling: usize,
+        power_iterations: usize,
+        seed: u64,
+    ) -> Self {
+        Self { center, oversampling, power_iterations, seed }
+    }
+
+    /// Reduce the rows of a sparse matrix to `target_dims` components.
+    ///
+    /// # Arguments
+    /// * `matrix` - Sparse matrix (rows = data points, cols = dimensions)
+    /// * `target_dims` - Target dimensionality (typically 2 or 3)
+    ///
+    /// # Returns
+    /// Reduced vectors (U * Sigma restricted to the top components), one per row
+    ///
+    /// # Errors
+    /// Returns error if the matrix is empty or `target_dims` exceeds the column count
+    pub fn reduce_sparse(
+        &self,
+        matrix: &CsMat<f64>,
+        target_dims: usize,
+    ) -> Result<Vec<Vec<f64>>, PluginError> {
+        let (nrows, ncols) = matrix.shape();
+        if nrows == 0 {
+            return Err(PluginError::InsufficientData { required: 1, provided: 0 });
+        }
+        if target_dims > ncols {
+            return Err(PluginError::DimensionalityReductionError {
+                method: self.method_name().to_string(),
+                reason: format!(
+                    "Target dimensions ({target_dims}) cannot exceed input dimensions ({ncols})"
+                ),
+            });
+        }
+
+        let converted;
+        let csr = if matrix.is_csr() {
+            matrix
+        } else {
+            converted = matrix.to_csr();
+            &converted
+        };
+        let operator = CenteredOperator::new(csr, self.center);
+
+        let (q, singular_values, u_small) = self.decompose(&operator, target_dims);
+        let available = singular_values.len().min(target_dims);
+
+        // U = Q * U_small, reduced representation = U * Sigma
+        let u = &q * u_small.columns(0, available);
+        let result = (0..nrows)
+            .map(|i| {
+                (0..target_dims)
+                    .map(|j| {
+                        if j < available {
+                            u[(i, j)] * singular_values[j]
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(result)
+    }
+
+    /// Run the randomized range finder and the small dense SVD.
+    ///
+    /// Returns the orthonormal range basis Q, the singular values and the left
+    /// singular vectors of Qᵀ A, with signs fixed by [`canonical_sign`].
+    fn decompose(
+        &self,
+        operator: &CenteredOperator<'_>,
+        target_dims: usize,
+    ) -> (DMatrix<f64>, DVector<f64>, DMatrix<f64>) {
+        let (nrows, ncols) = operator.shape();
+        let sketch_width = (target_dims + self.oversampling)
+            .min(nrows.min(ncols))
+            .max(1);
+
+        let mut rng = SeededRng::new(self.seed);
+        let omega = DMatrix::from_fn(ncols, sketch_width, |_, _| rng.next_normal());
+
+        let mut q = orthonormalize(operator.apply(&omega));
+        for _ in 0..self.power_iterations {
+            let z = orthonormalize(operator.apply_transpose(&q));
+            q = orthonormalize(operator.apply(&z));
+        }
+
+        // B = Qᵀ A is small (sketch_width × ncols); compute it as (Aᵀ Q)ᵀ
+        let b = operator.apply_transpose(&q).transpose();
+        let svd = b.svd(true, true);
+        let mut u_small = svd
+            .u
+            .unwrap_or_else(|| DMatrix::identity(q.ncols(), q.ncols()));
+
+        // B and A share right singular vectors, so apply the same sign
+        // convention as the dense SVD
+        if let Some(v_t) = &svd.v_t {
+            for j in 0..svd.singular_values.len() {
+                let loadings: Vec<f64> = v_t.row(j).iter().copied().collect();
+                if canonical_sign(&loadings) < 0.0 {
+                    u_small.column_mut(j).neg_mut();
+                }
+            }
+        }
+
+        (q, svd.singular_values, u_small)
+    }
+}
+
+impl Default for TruncatedSVDReducer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DimensionalityReducer for TruncatedSVDReducer {
+    fn reduce(
+        &self,
+        vectors: &[Vec<f64>],
+        target_dims: usize,
+    ) -> Result<Vec<Vec<f64>>, PluginError> {
+        if vectors.is_empty() {
+            return Err(PluginError::InsufficientData { required: 1, provided: 0 });
+        }
+
+        let dim = vectors[0].len();
+        let mut triplets = TriMat::new((vectors.len(), dim));
+        for (i, vec) in vectors.iter().enumerate() {
+            if vec.len() != dim {
+                return Err(PluginError::InvalidVectorDimensions {
+                    expected: dim,
+                    got: vec.len(),
+                    vector_index: i,
+                });
+            }
+            for (j, &val) in vec.iter().enumerate() {
+                if val != 0.0 {
+                    triplets.add_triplet(i, j, val);
+                }
+            }
+        }
+
+        self.reduce_sparse(&triplets.to_csr(), target_dims)
+    }
+
+    fn method_name(&self) -> &'static str {
+        "Truncated SVD"
+    }
+}
+
+/// Linear operator for a CSR matrix with optional implicit column centering.
+struct CenteredOperator<'a> {
+    /// The sparse matrix in CSR format.
+    matrix: &'a CsMat<f64>,
+    /// Column means (all zeros when centering is disabled).
+    means: DVector<f64>,
+}
+
+impl<'a> CenteredOperator<'a> {
+    /// Wrap a CSR matrix, computing column means if centering is requested.
+    #[allow(clippy::cast_precision_loss)]
+    fn new(matrix: &'a CsMat<f64>, center: bool) -> Self {
+        let (nrows, ncols) = matrix.shape();
+        let mut means = DVector::zeros(ncols);
+        if center {
+            for row in matrix.outer_iterator() {
+                for (col, &val) in row.iter() {
+                    means[col] += val;
+                }
+            }
+            means /= nrows as f64;
+        }
+        Self { matrix, means }
+    }
+
+    /// Shape of the underlying matrix.
+    fn shape(&self) -> (usize, usize) {
+        self.matrix.shape()
+    }
+
+    /// Compute (A - 1μᵀ) X.
+    fn apply(&self, x: &DMatrix<f64>) -> DMatrix<f64> {
+        let (nrows, _) = self.shape();
+        let mut out = DMatrix::zeros(nrows, x.ncols());
+        for (i, row) in self.matrix.outer_iterator().enumerate() {
+            for (col, &val) in row.iter() {
+                for k in 0..x.ncols() {
+                    out[(i, k)] += val * x[(col, k)];
+                }
+            }
+        }
+
+        // Subtract the rank-one centering term 1 (μᵀ X)
+        let shift = self.means.transpose() * x;
+        for mut row in out.row_iter_mut() {
+            row -= &shift;
+        }
+        out
+    }
+
+    /// Compute (A - 1μᵀ)ᵀ Y.
+    fn apply_transpose(&self, y: &DMatrix<f64>) -> DMatrix<f64> {
+        let (_, ncols) = self.shape();
+        let mut out = DMatrix::zeros(ncols, y.ncols());
+        for (i, row) in self.matrix.outer_iterator().enumerate() {
+            for (col, &val) in row.iter() {
+                for k in 0..y.ncols() {
+                    out[(col, k)] += val * y[(i, k)];
+                }
+            }
+        }
+
+        // Subtract the rank-one centering term μ (1ᵀ Y)
+        let column_sums = y.row_sum();
+        out -= &self.means * column_sums;
+        out
+    }
+}
+
+/// Orthonormalize the columns of a thin matrix via QR.
+fn orthonormalize(matrix: DMatrix<f64>) -> DMatrix<f64> {
+    matrix.qr().q()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SVDReducer;
+
+    fn sample_matrix() -> CsMat<f64> {
+        let mut triplets = TriMat::new((6, 6));
+        for (i, j, v) in [
+            (0, 1, 1.0),
+            (0, 2, 1.0),
+            (1, 2, 2.0),
+            (2, 0, 1.0),
+            (3, 4, 1.0),
+            (4, 5, 3.0),
+            (5, 3, 1.0),
+            (5, 0, 1.0),
+        ] {
+            triplets.add_triplet(i, j, v);
+        }
+        triplets.to_csr()
+    }
+
+    fn dense_rows(matrix: &CsMat<f64>) -> Vec<Vec<f64>> {
+        let (nrows, ncols) = matrix.shape();
+        let dense = matrix.to_dense();
+        (0..nrows)
+            .map(|i| (0..ncols).map(|j| dense[[i, j]]).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_truncated_svd_matches_dense_svd() {
+        let matrix = sample_matrix();
+        let sparse = TruncatedSVDReducer::new()
+            .reduce_sparse(&matrix, 3)
+            .expect("Truncated SVD failed");
+        let dense = SVDReducer::new()
+            .reduce(&dense_rows(&matrix), 3)
+            .expect("Dense SVD failed");
+
+        // Both follow the same sign convention, so components match exactly
+        for j in 0..3 {
+            let diff: f64 = (0..6).map(|i| (sparse[i][j] - dense[i][j]).abs()).sum();
+            assert!(diff < 1e-6, "component {j} differs");
+        }
+    }
+
+    #[test]
+    fn test_truncated_svd_is_deterministic() {
+        let matrix = sample_matrix();
+        let reducer = TruncatedSVDReducer::new();
+        let first = reducer
+            .reduce_sparse(&matrix, 2)
+            .expect("Truncated SVD failed");
+        let second = reducer
+            .reduce_sparse(&matrix, 2)
+            .expect("Truncated SVD failed");
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_truncated_svd_empty_graph() {
+        let matrix: CsMat<f64> = TriMat::new((4, 4)).to_csr();
+        let result = TruncatedSVDReducer::new()
+            .reduce_sparse(&matrix, 3)
+            .expect("Truncated SVD failed");
+
+        assert_eq!(result.len(), 4);
+        assert!(result.iter().flatten().all(|x| x.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_truncated_svd_invalid_target_dims() {
+        let matrix = sample_matrix();
+        let result = TruncatedSVDReducer::new().reduce_sparse(&matrix, 7);
+
+        match result {
+            Err(PluginError::DimensionalityReductionError { method, reason: _ }) => {
+                assert_eq!(method, "Truncated SVD");
+            },
+            _ => panic!("Expected DimensionalityReductionError"),
+        }
+    }
+}
@@ -0,0 +1,635 @@
+//! Spectral embedding (Laplacian eigenmaps) of the link graph.
+//!
+//! Coordinates are the eigenvectors of a symmetric graph Laplacian with the
+//! smallest non-trivial eigenvalues. Each connected component has its own
+//! zero eigenvalue, so components are embedded separately and then packed
+//! side by side instead of collapsing onto degenerate eigenvectors.
+
+use crate::rng::SeededRng;
+use crate::union_find::find_root;
+use crate::{DimensionalityReducer, PluginError, canonical_sign};
+use nalgebra::{DMatrix, DVector, SymmetricEigen};
+use serde::{Deserialize, Serialize};
+use sprs::{CsMat, TriMat};
+
+/// Components up to this size are solved with a dense eigendecomposition.
+const DENSE_LIMIT: usize = 256;
+
+/// Maximum subspace dimension before the sparse eigensolver restarts.
+const MAX_SUBSPACE_DIMS: usize = 120;
+
+/// Extra vectors iterated beyond those requested, to speed up convergence.
+const BLOCK_GUARD: usize = 4;
+
+/// Relative norm below which a new basis vector is treated as dependent.
+const DEPENDENT: f64 = 1e-8;
+
+/// Gap between packed components, relative to the largest component radius.
+const COMPONENT_MARGIN: f64 = 0.5;
+
+/// Spectral embedding of a graph, with its component structure.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpectralEmbedding {
+    /// Coordinates per note.
+    pub coordinates: Vec<Vec<f64>>,
+    /// Connected component id per note, largest component first.
+    pub components: Vec<usize>,
+    /// Number of connected components.
+    pub component_count: usize,
+    /// Whether every eigenvector reached the solver tolerance.
+    pub converged: bool,
+}
+
+/// Laplacian eigenmaps reducer.
+///
+/// Expects a symmetric Laplacian, normally the symmetric normalized one from
+/// `AdjacencyMatrixBuilder::build_laplacian` on an undirected graph.
+pub struct SpectralReducer {
+    /// Residual tolerance for the sparse eigensolver.
+    tolerance: f64,
+    /// Maximum number of eigensolver iterations per component.
+    max_iterations: usize,
+    /// Seed for the eigensolver start vectors.
+    seed: u64,
+}
+
+impl SpectralReducer {
+    /// Create a new spectral reducer with default settings.
+    ///
+    /// Default: tolerance=1e-8, `max_iterations`=1000, seed=42
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { tolerance: 1e-8, max_iterations: 1000, seed: 42 }
+    }
+
+    /// Create a new spectral reducer with custom settings.
+    ///
+    /// # Arguments
+    /// * `tolerance` - Residual tolerance for the eigensolver
+    /// * `max_iterations` - Maximum number of eigensolver iterations per component
+    /// * `seed` - Seed for the eigensolver start vectors
+    #[must_use]
+    pub const fn with_options(tolerance: f64, max_iterations: usize, seed: u64) -> Self {
+        Self { tolerance, max_iterations, seed }
+    }
+
+    /// Embed a graph from its Laplacian.
+    ///
+    /// Each component is embedded with its own eigenvectors, scaled so its
+    /// extent grows with its size, and components are packed in rows along
+    /// the first two axes. Isolated notes sit at their slot's centre.
+    ///
+    /// # Arguments
+    /// * `laplacian` - Symmetric sparse Laplacian (N×N)
+    /// * `target_dims` - Target dimensionality (2 or 3)
+    ///
+    /// # Returns
+    /// Coordinates per note plus the component assignment
+    ///
+    /// # Errors
+    /// Returns error if the matrix is empty, not square or not symmetric, or
+    /// `target_dims` is not 2 or 3
+    pub fn embed(
+        &self,
+        laplacian: &CsMat<f64>,
+        target_dims: usize,
+    ) -> Result<SpectralEmbedding, PluginError> {
+        let (n, cols) = laplacian.shape();
+        if n == 0 {
+            return Err(PluginError::InsufficientData { required: 1, provided: 0 });
+        }
+        if n != cols {
+            return Err(PluginError::ValidationError {
+                field: "laplacian".to_string(),
+                value: format!("{n}x{cols}"),
+                reason: "Laplacian must be square".to_string(),
+            });
+        }
+        if !(2..=3).contains(&target_dims) {
+            return Err(PluginError::DimensionalityReductionError {
+                method: self.method_name().to_string(),
+                reason: format!("Target dimensions must be 2 or 3, got {target_dims}"),
+            });
+        }
+        let csr = laplacian.to_csr();
+        if !is_symmetric(&csr) {
+            return Err(PluginError::ValidationError {
+                field: "laplacian".to_string(),
+                value: format!("{n}x{n}"),
+                reason: "Laplacian must be symmetric (build it from an undirected graph)"
+                    .to_string(),
+            });
+        }
+
+        let components = connected_components(&csr);
+        let component_count = components.iter().max().map_or(0, |&c| c + 1);
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); component_count];
+        for (node, &component) in components.iter().enumerate() {
+            members[component].push(node);
+        }
+
+        let mut rng = SeededRng::new(self.seed);
+        let mut converged = true;
+        let mut local_layouts = Vec::with_capacity(component_count);
+        for nodes in &members {
+            let (layout, component_converged) =
+                self.embed_component(&csr, nodes, target_dims, &mut rng);
+            converged &= component_converged;
+            local_layouts.push(layout);
+        }
+
+        let coordinates = pack_components(n, &members, &local_layouts, target_dims);
+        Ok(SpectralEmbedding { coordinates, components, component_count, converged })
+    }
+
+    /// Embed one component, returning coordinates scaled to its radius.
+    fn embed_component(
+        &self,
+        laplacian: &CsMat<f64>,
+        nodes: &[usize],
+        dims: usize,
+        rng: &mut SeededRng,
+    ) -> (Vec<Vec<f64>>, bool) {
+        let size = nodes.len();
+        if size == 1 {
+            return (vec![vec![0.0; dims]], true);
+        }
+
+        let sub = submatrix(laplacian, nodes);
+        // The smallest eigenvalue (0) belongs to the trivial eigenvector
+        let wanted = (dims + 1).min(size);
+        let (vectors, converged) = if size <= DENSE_LIMIT {
+            (dense_smallest_eigenvectors(&sub, wanted), true)
+        } else {
+            sparse_smallest_eigenvectors(&sub, wanted, self.tolerance, self.max_iterations, rng)
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let radius = (size as f64).powf(1.0 / dims as f64);
+        let mut layout = vec![vec![0.0; dims]; size];
+        for (axis, column) in vectors.column_iter().skip(1).enumerate() {
+            let values: Vec<f64> = column.iter().copied().collect();
+            let max_abs = values.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+            if max_abs <= 0.0 {
+                continue;
+            }
+            let scale = canonical_sign(&values) * radius / max_abs;
+            for (row, value) in layout.iter_mut().zip(&values) {
+                row[axis] = value * scale;
+            }
+        }
+        (layout, converged)
+    }
+}
+
+impl Default for SpectralReducer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DimensionalityReducer for SpectralReducer {
+    /// Treats `vectors` as the dense rows of a symmetric Laplacian.
+    fn reduce(
+        &self,
+        vectors: &[Vec<f64>],
+        target_dims: usize,
+    ) -> Result<Vec<Vec<f64>>, PluginError> {
+        let n = vectors.len();
+        let mut triplets = TriMat::new((n, n));
+        for (i, row) in vectors.iter().enumerate() {
+            if row.len() != n {
+                return Err(PluginError::InvalidVectorDimensions {
+                    expected: n,
+                    got: row.len(),
+                    vector_index: i,
+                });
+            }
+            for (j, &val) in row.iter().enumerate() {
+                if val != 0.0 {
+                    triplets.add_triplet(i, j, val);
+                }
+            }
+        }
+
+        Ok(self.embed(&triplets.to_csr(), target_dims)?.coordinates)
+    }
+
+    fn method_name(&self) -> &'static str {
+        "Spectral"
+    }
+}
+
+/// Label the connected components of a graph.
+///
+/// Any non-zero off-diagonal entry connects its row and column, regardless of
+/// direction, so this gives weakly connected components of directed graphs.
+///
+/// # Arguments
+/// * `matrix` - Square sparse adjacency or Laplacian matrix
+///
+/// # Returns
+/// Component id per node, numbered from 0 by decreasing size (ties by first node)
+#[must_use]
+pub fn connected_components(matrix: &CsMat<f64>) -> Vec<usize> {
+    let n = matrix.rows();
+    let mut parent: Vec<usize> = (0..n).collect();
+    for (i, row) in matrix.outer_iterator().enumerate() {
+        for (j, &val) in row.iter() {
+            if i != j && val != 0.0 {
+                let (a, b) = (find_root(&mut parent, i), find_root(&mut parent, j));
+                if a != b {
+                    parent[a.max(b)] = a.min(b);
+                }
+            }
+        }
+    }
+
+    let roots: Vec<usize> = (0..n).map(|node| find_root(&mut parent, node)).collect();
+    let mut sizes = vec![0_usize; n];
+    for &root in &roots {
+        sizes[root] += 1;
+    }
+    // Roots are the smallest node of each component, so sorting by size and
+    // then root orders ties by first node
+    let mut order: Vec<usize> = (0..n).filter(|&node| roots[node] == node).collect();
+    order.sort_by_key(|&root| (std::cmp::Reverse(sizes[root]), root));
+    let mut labels = vec![0; n];
+    for (label, &root) in order.iter().enumerate() {
+        labels[root] = label;
+    }
+    roots.iter().map(|&root| labels[root]).collect()
+}
+
+/// Check that a CSR matrix equals its transpose up to rounding.
+fn is_symmetric(matrix: &CsMat<f64>) -> bool {
+    let transpose = matrix.transpose_view().to_csr();
+    let difference = matrix - &transpose;
+    let scale = matrix.data().iter().fold(1.0_f64, |m, v| m.max(v.abs()));
+    difference.data().iter().all(|v| v.abs() <= 1e-9 * scale)
+}
+
+/// Extract the square submatrix for the given (sorted) nodes.
+fn submatrix(matrix: &CsMat<f64>, nodes: &[usize]) -> CsMat<f64> {
+    let mut local = vec![usize::MAX; matrix.rows()];
+    for (index, &node) in nodes.iter().enumerate() {
+        local[node] = index;
+    }
+    let mut triplets = TriMat::new((nodes.len(), nodes.len()));
+    for (index, &node) in nodes.iter().enumerate() {
+        if let Some(row) = matrix.outer_view(node) {
+            for (col, &val) in row.iter() {
+                if local[col] != usize::MAX {
+                    triplets.add_triplet(index, local[col], val);
+                }
+            }
+        }
+    }
+    triplets.to_csr()
+}
+
+/// Eigenvectors for the `count` smallest eigenvalues, via a dense solver.
+fn dense_smallest_eigenvectors(matrix: &CsMat<f64>, count: usize) -> DMatrix<f64> {
+    let n = matrix.rows();
+    let mut dense = DMatrix::<f64>::zeros(n, n);
+    for (i, row) in matrix.outer_iterator().enumerate() {
+        for (j, &val) in row.iter() {
+            dense[(i, j)] = val;
+        }
+    }
+    let eigen = SymmetricEigen::new(dense);
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| eigen.eigenvalues[a].total_cmp(&eigen.eigenvalues[b]));
+    DMatrix::from_fn(n, count, |r, c| eigen.eigenvectors[(r, order[c])])
+}
+
+/// Eigenvectors for the `count` smallest eigenvalues of a sparse symmetric matrix.
+///
+/// Block Krylov subspace iteration with Rayleigh-Ritz and thick restarts:
+/// the basis grows by the residuals of the current Ritz block, and when it
+/// is full it shrinks back to the best Ritz vectors. Working with a block
+/// (rather than a single Lanczos vector) also finds repeated eigenvalues.
+/// Returns the best available vectors and `false` if `max_iterations` runs
+/// out before every residual is below `tolerance`.
+fn sparse_smallest_eigenvectors(
+    matrix: &CsMat<f64>,
+    count: usize,
+    tolerance: f64,
+    max_iterations: usize,
+    rng: &mut SeededRng,
+) -> (DMatrix<f64>, bool) {
+    let n = matrix.rows();
+    let block = (count + BLOCK_GUARD).min(n);
+    let max_dims = MAX_SUBSPACE_DIMS.min(n).max(2 * block);
+    let keep = (max_dims / 3).max(block);
+
+    let mut basis = DMatrix::<f64>::zeros(n, 0);
+    let mut images = DMatrix::<f64>::zeros(n, 0);
+    let mut projected = DMatrix::<f64>::zeros(0, 0);
+    let mut candidates = DMatrix::from_fn(n, block, |_, _| rng.next_normal());
+
+    let mut ritz_vectors = DMatrix::zeros(n, count);
+    for _ in 0..max_iterations {
+        // Thick restart: keep the best Ritz vectors once the basis is full
+        if basis.ncols() + candidates.ncols() > max_dims {
+            let (values, coefficients) = rayleigh_ritz(&projected);
+            let kept = coefficients.columns(0, keep.min(values.len()));
+            basis = &basis * kept;
+            images = &images * kept;
+            projected = DMatrix::from_diagonal(&DVector::from_iterator(
+                kept.ncols(),
+                values.iter().copied().take(kept.ncols()),
+            ));
+        }
+
+        let added = extend_basis(matrix, &mut basis, &mut images, &mut projected, &candidates);
+        if added == 0 && basis.ncols() < count {
+            // Residuals vanished before spanning enough vectors; add fresh noise
+            candidates = DMatrix::from_fn(n, block, |_, _| rng.next_normal());
+            continue;
+        }
+
+        let (values, coefficients) = rayleigh_ritz(&projected);
+        let width = block.min(values.len());
+        let top = coefficients.columns(0, width);
+        let vectors = &basis * top;
+        let mut residuals = &images * top;
+        for (c, &value) in values.iter().take(width).enumerate() {
+            residuals
+                .column_mut(c)
+                .axpy(-value, &vectors.column(c), 1.0);
+        }
+
+        let scale = values.iter().fold(1.0_f64, |m, v| m.max(v.abs()));
+        let wanted = count.min(width);
+        ritz_vectors = vectors.columns(0, wanted).into_owned();
+        let done = (0..wanted).all(|c| residuals.column(c).norm() <= tolerance * scale);
+        if (done && wanted == count) || added == 0 {
+            return (ritz_vectors, done && wanted == count);
+        }
+        candidates = residuals;
+    }
+    (ritz_vectors, false)
+}
+
+/// Orthonormalize `candidates` against `basis` and append them.
+///
+/// Updates the images (matrix × basis) and the projected matrix
+/// basisᵀ × matrix × basis. Returns the number of columns added.
+fn extend_basis(
+    matrix: &CsMat<f64>,
+    basis: &mut DMatrix<f64>,
+    images: &mut DMatrix<f64>,
+    projected: &mut DMatrix<f64>,
+    candidates: &DMatrix<f64>,
+) -> usize {
+    let mut block = candidates.clone();
+    for mut column in block.column_iter_mut() {
+        let norm = column.norm();
+        if norm > 0.0 {
+            column /= norm;
+        }
+    }
+    // Two passes of block Gram-Schmidt against the basis, then within the block
+    for _ in 0..2 {
+        block -= &*basis * basis.tr_mul(&block);
+    }
+    let mut accepted: Vec<DVector<f64>> = Vec::new();
+    for column in block.column_iter() {
+        let mut v = column.into_owned();
+        for _ in 0..2 {
+            for u in &accepted {
+                let projection = u.dot(&v);
+                v.axpy(-projection, u, 1.0);
+            }
+        }
+        let norm = v.norm();
+        if norm > DEPENDENT {
+            accepted.push(v / norm);
+        }
+    }
+    if accepted.is_empty() {
+        return 0;
+    }
+
+    let new_basis = DMatrix::from_columns(&accepted);
+    let new_images = DMatrix::from_columns(
+        &accepted
+            .iter()
+            .map(|v| multiply(matrix, v))
+            .collect::<Vec<_>>(),
+    );
+    let old = basis.ncols();
+    let total = old + accepted.len();
+    let cross = basis.tr_mul(&new_images);
+    let corner = new_basis.tr_mul(&new_images);
+    let mut grown = DMatrix::zeros(total, total);
+    grown.view_mut((0, 0), (old, old)).copy_from(projected);
+    grown
+        .view_mut((0, old), (old, accepted.len()))
+        .copy_from(&cross);
+    grown
+        .view_mut((old, 0), (accepted.len(), old))
+        .copy_from(&cross.transpose());
+    // Symmetrise to absorb rounding
+    grown
+        .view_mut((old, old), (accepted.len(), accepted.len()))
+        .copy_from(&((&corner + corner.transpose()) * 0.5));
+
+    let added = accepted.len();
+    *basis = std::mem::take(basis).resize_horizontally(total, 0.0);
+    basis.columns_mut(old, added).copy_from(&new_basis);
+    *images = std::mem::take(images).resize_horizontally(total, 0.0);
+    images.columns_mut(old, added).copy_from(&new_images);
+    *projected = grown;
+    added
+}
+
+/// Eigenvalues (ascending) and eigenvectors of a small symmetric matrix.
+fn rayleigh_ritz(projected: &DMatrix<f64>) -> (Vec<f64>, DMatrix<f64>) {
+    let k = projected.nrows();
+    let eigen = SymmetricEigen::new(projected.clone());
+    let mut order: Vec<usize> = (0..k).collect();
+    order.sort_by(|&a, &b| eigen.eigenvalues[a].total_cmp(&eigen.eigenvalues[b]));
+    let values = order.iter().map(|&i| eigen.eigenvalues[i]).collect();
+    let vectors = DMatrix::from_fn(k, k, |r, c| eigen.eigenvectors[(r, order[c])]);
+    (values, vectors)
+}
+
+/// Sparse matrix-vector product.
+fn multiply(matrix: &CsMat<f64>, v: &DVector<f64>) -> DVector<f64> {
+    DVector::from_iterator(
+        matrix.rows(),
+        matrix
+            .outer_iterator()
+            .map(|row| row.iter().map(|(j, &val)| val * v[j]).sum()),
+    )
+}
+
+/// Place component layouts in rows along the first two axes.
+///
+/// Components are already ordered largest first. Rows are filled up to a
+/// width that makes the overall arrangement roughly square.
+fn pack_components(
+    n: usize,
+    members: &[Vec<usize>],
+    layouts: &[Vec<Vec<f64>>],
+    dims: usize,
+) -> Vec<Vec<f64>> {
+    let radii: Vec<f64> = layouts
+        .iter()
+        .map(|layout| {
+            layout
+                .iter()
+                .flatten()
+                .fold(0.0_f64, |m, v| m.max(v.abs()))
+                .max(0.5)
+        })
+        .collect();
+    let margin = COMPONENT_MARGIN * radii.first().copied().unwrap_or(1.0);
+    let total_area: f64 = radii.iter().map(|r| (2.0 * r + margin).powi(2)).sum();
+    let row_width = total_area.sqrt().max(2.0f64.mul_add(radii[0], margin));
+
+    let mut coordinates = vec![vec![0.0; dims]; n];
+    let (mut cursor_x, mut cursor_y, mut row_height) = (0.0_f64, 0.0_f64, 0.0_f64);
+    for ((nodes, layout), &radius) in members.iter().zip(layouts).zip(&radii) {
+        let extent = 2.0f64.mul_add(radius, margin);
+        if cursor_x > 0.0 && cursor_x + extent > row_width {
+            cursor_x = 0.0;
+            cursor_y -= row_height;
+            row_height = 0.0;
+        }
+        let center_x = cursor_x + extent / 2.0;
+        let center_y = cursor_y - extent / 2.0;
+        for (&node, local) in nodes.iter().zip(layout) {
+            coordinates[node].clone_from(local);
+            coordinates[node][0] += center_x;
+            coordinates[node][1] += center_y;
+        }
+        cursor_x += extent;
+        row_height = row_height.max(extent);
+    }
+
+    // Centre the whole arrangement on the origin
+    #[allow(clippy::cast_precision_loss)]
+    let count = n as f64;
+    for axis in 0..dims {
+        let mean = coordinates.iter().map(|c| c[axis]).sum::<f64>() / count;
+        for c in &mut coordinates {
+            c[axis] -= mean;
+        }
+    }
+    coordinates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdjacencyMatrixBuilder, GraphOptions, LaplacianKind, NoteLink};
+
+    fn normalized_laplacian(n: usize, edges: &[(usize, usize)]) -> CsMat<f64> {
+        let options = GraphOptions {
+            directed: false,
+            laplacian: LaplacianKind::SymmetricNormalized,
+            self_loops: false,
+            binarize: true,
+        };
+        let builder =
+            AdjacencyMatrixBuilder::with_options((0..n).map(|i| i.to_string()).collect(), options);
+        let links = edges
+            .iter()
+            .map(|&(from_id, to_id)| NoteLink { from_id, to_id })
+            .collect();
+        builder
+            .build_laplacian(links)
+            .expect("Failed to build Laplacian")
+    }
+
+    fn ring(n: usize, offset: usize) -> Vec<(usize, usize)> {
+        (0..n).map(|i| (offset + i, offset + (i + 1) % n)).collect()
+    }
+
+    #[test]
+    fn test_ring_embeds_as_circle() {
+        let laplacian = normalized_laplacian(12, &ring(12, 0));
+        let result = SpectralReducer::new()
+            .embed(&laplacian, 2)
+            .expect("Spectral embedding failed");
+
+        assert_eq!(result.component_count, 1);
+        let radii: Vec<f64> = result
+            .coordinates
+            .iter()
+            .map(|c| c[0].hypot(c[1]))
+            .collect();
+        assert!(radii.iter().all(|r| (r - radii[0]).abs() < 1e-6 * radii[0]));
+    }
+
+    #[test]
+    fn test_sparse_solver_matches_dense_solver() {
+        // A long ring has closely spaced eigenvalues, each non-trivial one repeated
+        let laplacian = normalized_laplacian(150, &ring(150, 0));
+        let mut rng = SeededRng::new(1);
+
+        let (sparse, converged) = sparse_smallest_eigenvectors(&laplacian, 3, 1e-9, 1000, &mut rng);
+        let dense = dense_smallest_eigenvectors(&laplacian, 3);
+
+        assert!(converged);
+        // Compare the spanned subspaces, which is robust to repeated eigenvalues
+        let overlap = sparse.transpose() * &dense;
+        let singular_values = overlap.singular_values();
+        assert!(singular_values.iter().all(|s| (s - 1.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_disconnected_components_are_separated() {
+        let mut edges = ring(10, 0);
+        edges.extend(ring(6, 10));
+        let laplacian = normalized_laplacian(17, &edges);
+        let result = SpectralReducer::new()
+            .embed(&laplacian, 3)
+            .expect("Spectral embedding failed");
+
+        assert_eq!(result.component_count, 3);
+        assert_eq!(result.components[0], 0);
+        assert_eq!(result.components[10], 1);
+        assert_eq!(result.components[16], 2);
+        assert!(result.coordinates.iter().flatten().all(|x| x.is_finite()));
+
+        // Bounding boxes of the two rings do not overlap
+        let bounds = |range: std::ops::Range<usize>, axis: usize| {
+            range.fold((f64::MAX, f64::MIN), |(lo, hi), i| {
+                let x = result.coordinates[i][axis];
+                (lo.min(x), hi.max(x))
+            })
+        };
+        let separated = (0..2).any(|axis| {
+            let (first, second) = (bounds(0..10, axis), bounds(10..16, axis));
+            first.1 < second.0 || second.1 < first.0
+        });
+        assert!(separated);
+    }
+
+    #[test]
+    fn test_connected_components_by_size() {
+        let mut triplets = TriMat::new((5, 5));
+        triplets.add_triplet(0, 1, 1.0);
+        triplets.add_triplet(3, 2, 1.0);
+        triplets.add_triplet(4, 3, 1.0);
+
+        assert_eq!(connected_components(&triplets.to_csr()), vec![1, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_rejects_directed_laplacian() {
+        let builder = AdjacencyMatrixBuilder::new(vec!["a".to_string(), "b".to_string()]);
+        let laplacian = builder
+            .build_laplacian(vec![NoteLink { from_id: 0, to_id: 1 }])
+            .expect("Failed to build Laplacian");
+
+        let result = SpectralReducer::new().embed(&laplacian, 2);
+
+        assert!(matches!(result, Err(PluginError::ValidationError { .. })));
+    }
+}
@@ -0,0 +1,12 @@
+//! Fixtures shared by unit tests.
+
+use sprs::{CsMat, TriMat};
+
+/// Directed graph on `n` nodes with a unit-weight link per `(from, to)` pair.
+pub fn graph(n: usize, edges: &[(usize, usize)]) -> CsMat<f64> {
+    let mut triplets = TriMat::new((n, n));
+    for &(from, to) in edges {
+        triplets.add_triplet(from, to, 1.0);
+    }
+    triplets.to_csr()
+}
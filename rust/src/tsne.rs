@@ -0,0 +1,444 @@
+//! Barnes-Hut t-SNE dimensionality reduction.
+//!
+//! Input affinities are computed over a sparse nearest-neighbour graph and
+//! repulsive forces are approximated with a space-partitioning tree, giving
+//! O(N log N) work per iteration instead of O(N²).
+
+use crate::rng::SeededRng;
+use crate::space_tree::SpaceTree;
+use crate::{
+    DimensionalityReducer, DistanceMetric, Euclidean, PluginError, k_nearest_neighbor_graph,
+};
+use std::collections::BTreeMap;
+
+/// Barnes-Hut accuracy trade-off; cells whose side is below `THETA` × distance are summarised.
+const THETA: f64 = 0.5;
+/// Iterations run with early exaggeration and low momentum.
+const EXAGGERATION_ITERATIONS: usize = 250;
+/// Momentum during the early exaggeration phase.
+const INITIAL_MOMENTUM: f64 = 0.5;
+/// Momentum after the early exaggeration phase.
+const FINAL_MOMENTUM: f64 = 0.8;
+/// Lower bound for the adaptive per-parameter gains.
+const MIN_GAIN: f64 = 0.01;
+
+/// Barnes-Hut t-SNE dimensionality reduction.
+///
+/// Emphasises local cluster structure; distances between clusters in the
+/// output are not meaningful.
+pub struct TSNEReducer {
+    /// Effective number of neighbours each point considers.
+    perplexity: f64,
+    /// Factor applied to input affinities during the early iterations.
+    early_exaggeration: f64,
+    /// Gradient descent step size.
+    learning_rate: f64,
+    /// Distance metric in the input space.
+    metric: Box<dyn DistanceMetric>,
+    /// Total number of gradient descent iterations.
+    n_iter: usize,
+    /// Seed for the initial layout.
+    seed: u64,
+}
+
+impl TSNEReducer {
+    /// Create a new t-SNE reducer with default settings.
+    ///
+    /// Default: perplexity=30, `early_exaggeration`=12, `learning_rate`=200,
+    /// metric=euclidean, `n_iter`=1000, seed=42
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_options(30.0, 12.0, 200.0, Box::new(Euclidean), 1000, 42)
+    }
+
+    /// Create a new t-SNE reducer with custom settings.
+    ///
+    /// # Arguments
+    /// * `perplexity` - Effective number of neighbours (typically 5-50)
+    /// * `early_exaggeration` - Affinity multiplier for the first 250 iterations
+    /// * `learning_rate` - Gradient descent step size
+    /// * `metric` - Distance metric in the input space; distances are squared in
+    ///   the Gaussian kernel, so Euclidean gives standard t-SNE
+    /// * `n_iter` - Total number of iterations
+    /// * `seed` - Random seed
+    #[must_use]
+    pub fn with_options(
+        perplexity: f64,
+        early_exaggeration: f64,
+        learning_rate: f64,
+        metric: Box<dyn DistanceMetric>,
+        n_iter: usize,
+        seed: u64,
+    ) -> Self {
+        Self { perplexity, early_exaggeration, learning_rate, metric, n_iter, seed }
+    }
+
+    /// Compute the symmetric sparse input affinities P.
+    ///
+    /// # Returns
+    /// For each point, its `(neighbour, p_ij)` pairs; rows are symmetric and sum to 1 overall
+    fn input_affinities(
+        &self,
+        vectors: &[Vec<f64>],
+    ) -> Result<Vec<Vec<(usize, f64)>>, PluginError> {
+        let n = vectors.len();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let k = ((3.0 * self.perplexity) as usize).clamp(1, n - 1);
+        let knn = k_nearest_neighbor_graph(vectors, k, self.metric.as_ref())?;
+
+        let mut pairs: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+        for (i, neighbors) in knn.iter().enumerate() {
+            // A negative (inner product) distance would square to a large one and
+            // turn the nearest neighbours into the least affine; clamp it first
+            let distances: Vec<f64> = neighbors
+                .iter()
+                .map(|c| c.distance.max(0.0).powi(2))
+                .collect();
+            let conditional = calibrate_perplexity(&distances, self.perplexity);
+            for (neighbor, p) in neighbors.iter().zip(conditional) {
+                let j = neighbor.index;
+                *pairs.entry((i.min(j), i.max(j))).or_insert(0.0) += p;
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let norm = 2.0 * n as f64;
+        let mut rows = vec![Vec::new(); n];
+        for ((i, j), p) in pairs {
+            rows[i].push((j, p / norm));
+            rows[j].push((i, p / norm));
+        }
+        Ok(rows)
+    }
+
+    /// Run gradient descent from a small random layout.
+    fn optimize(&self, affinities: &[Vec<(usize, f64)>], dims: usize) -> Vec<Vec<f64>> {
+        let n = affinities.len();
+        let mut rng = SeededRng::new(self.seed);
+        let mut y: Vec<Vec<f64>> = (0..n)
+            .map(|_| (0..dims).map(|_| 1e-4 * rng.next_normal()).collect())
+            .collect();
+        let mut update = vec![vec![0.0; dims]; n];
+        let mut gains = vec![vec![1.0_f64; dims]; n];
+
+        for iter in 0..self.n_iter {
+            let early = iter < EXAGGERATION_ITERATIONS;
+            let exaggeration = if early { self.early_exaggeration } else { 1.0 };
+            let momentum = if early {
+                INITIAL_MOMENTUM
+            } else {
+                FINAL_MOMENTUM
+            };
+
+            let gradient = compute_gradient(&y, affinities, exaggeration);
+            for i in 0..n {
+                for d in 0..dims {
+                    let g = gradient[i][d];
+                    gains[i][d] = if (g > 0.0) == (update[i][d] > 0.0) {
+                        (gains[i][d] * 0.8).max(MIN_GAIN)
+                    } else {
+                        gains[i][d] + 0.2
+                    };
+                    update[i][d] =
+                        momentum.mul_add(update[i][d], -self.learning_rate * gains[i][d] * g);
+                    y[i][d] += update[i][d];
+                }
+            }
+            center(&mut y);
+        }
+
+        y
+    }
+}
+
+impl Default for TSNEReducer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DimensionalityReducer for TSNEReducer {
+    fn reduce(
+        &self,
+        vectors: &[Vec<f64>],
+        target_dims: usize,
+    ) -> Result<Vec<Vec<f64>>, PluginError> {
+        if vectors.is_empty() {
+            return Err(PluginError::InsufficientData { required: 1, provided: 0 });
+        }
+
+        // Validate all vectors have same dimensionality
+        let dim = vectors[0].len();
+        for (i, vec) in vectors.iter().enumerate() {
+            if vec.len() != dim {
+                return Err(PluginError::InvalidVectorDimensions {
+                    expected: dim,
+                    got: vec.len(),
+                    vector_index: i,
+                });
+            }
+        }
+
+        if !(2..=3).contains(&target_dims) {
+            return Err(PluginError::DimensionalityReductionError {
+                method: self.method_name().to_string(),
+                reason: format!(
+                    "Barnes-Hut t-SNE supports 2 or 3 output dimensions, got {target_dims}"
+                ),
+            });
+        }
+
+        let positive = |x: f64| x.is_finite() && x > 0.0;
+        if !(positive(self.perplexity)
+            && positive(self.learning_rate)
+            && positive(self.early_exaggeration))
+        {
+            return Err(PluginError::DimensionalityReductionError {
+                method: self.method_name().to_string(),
+                reason: format!(
+                    "Invalid parameters: perplexity={}, learning_rate={}, early_exaggeration={}",
+                    self.perplexity, self.learning_rate, self.early_exaggeration
+                ),
+            });
+        }
+
+        if vectors.len() == 1 {
+            return Ok(vec![vec![0.0; target_dims]]);
+        }
+
+        let affinities = self.input_affinities(vectors)?;
+        Ok(self.optimize(&affinities, target_dims))
+    }
+
+    fn method_name(&self) -> &'static str {
+        "t-SNE"
+    }
+}
+
+/// Binary-search the Gaussian precision so the conditional distribution over
+/// `squared_distances` has the requested perplexity.
+///
+/// # Returns
+/// Conditional probabilities p(j|i), summing to 1
+fn calibrate_perplexity(squared_distances: &[f64], perplexity: f64) -> Vec<f64> {
+    let target_entropy = perplexity.ln();
+    // Shifting by the minimum distance avoids underflow and leaves the entropy unchanged
+    let min = squared_distances
+        .iter()
+        .copied()
+        .fold(f64::INFINITY, f64::min);
+    let shifted: Vec<f64> = squared_distances.iter().map(|d| d - min).collect();
+
+    let (mut beta, mut lo, mut hi) = (1.0, 0.0, f64::INFINITY);
+    let mut probabilities = vec![0.0; shifted.len()];
+    for _ in 0..100 {
+        let mut sum = 0.0;
+        let mut weighted = 0.0;
+        for (p, &d) in probabilities.iter_mut().zip(&shifted) {
+            *p = (-beta * d).exp();
+            sum += *p;
+            weighted += d * *p;
+        }
+        let entropy = sum.ln() + beta * weighted / sum;
+        for p in &mut probabilities {
+            *p /= sum;
+        }
+
+        if (entropy - target_entropy).abs() < 1e-5 {
+            break;
+        }
+        // Higher entropy than wanted means the Gaussian is too wide
+        if entropy > target_entropy {
+            lo = beta;
+            beta = if hi.is_finite() {
+                f64::midpoint(lo, hi)
+            } else {
+                beta * 2.0
+            };
+        } else {
+            hi = beta;
+            beta = f64::midpoint(lo, hi);
+        }
+    }
+
+    probabilities
+}
+
+/// Gradient of the KL divergence with Barnes-Hut repulsion.
+fn compute_gradient(
+    y: &[Vec<f64>],
+    affinities: &[Vec<(usize, f64)>],
+    exaggeration: f64,
+) -> Vec<Vec<f64>> {
+    let dims = y.first().map_or(0, Vec::len);
+    let tree = SpaceTree::build(y, &vec![1.0; y.len()]);
+
+    let mut repulsive = vec![vec![0.0; dims]; y.len()];
+    let mut normalization = 0.0;
+    for (i, force) in repulsive.iter_mut().enumerate() {
+        normalization += repulsion(&tree, i, y, force);
+    }
+
+    y.iter()
+        .enumerate()
+        .map(|(i, yi)| {
+            let mut gradient = vec![0.0; dims];
+            for &(j, p) in &affinities[i] {
+                let q = 1.0 / (1.0 + squared_distance(yi, &y[j]));
+                for d in 0..dims {
+                    gradient[d] += exaggeration * p * q * (yi[d] - y[j][d]);
+                }
+            }
+            for d in 0..dims {
+                gradient[d] = 4.0 * (gradient[d] - repulsive[i][d] / normalization);
+            }
+            gradient
+        })
+        .collect()
+}
+
+/// Accumulate the unnormalised repulsive force on point `i` into `force`.
+///
+/// # Returns
+/// The point's contribution to the normalisation term Z
+fn repulsion(tree: &SpaceTree, i: usize, y: &[Vec<f64>], force: &mut [f64]) -> f64 {
+    let mut z = 0.0;
+    tree.for_each_interaction(i, &y[i], 1.0, THETA, |count, offset, dist_sq| {
+        let q = 1.0 / (1.0 + dist_sq);
+        z += count * q;
+        for (f, o) in force.iter_mut().zip(offset) {
+            *f += count * q * q * o;
+        }
+    });
+    z
+}
+
+/// Shift points so their mean is at the origin.
+fn center(y: &mut [Vec<f64>]) {
+    let dims = y.first().map_or(0, Vec::len);
+    #[allow(clippy::cast_precision_loss)]
+    let n = y.len() as f64;
+    for d in 0..dims {
+        let mean = y.iter().map(|p| p[d]).sum::<f64>() / n;
+        for point in y.iter_mut() {
+            point[d] -= mean;
+        }
+    }
+}
+
+/// Squared Euclidean distance in the embedding space.
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn three_blobs(per_blob: usize) -> Vec<Vec<f64>> {
+        let mut rng = SeededRng::new(11);
+        (0..3 * per_blob)
+            .map(|i| {
+                #[allow(clippy::cast_precision_loss)]
+                let offset = (i / per_blob) as f64 * 20.0;
+                (0..4)
+                    .map(|_| rng.next_normal().mul_add(0.5, offset))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_calibrate_perplexity_hits_target() {
+        let distances: Vec<f64> = (1..=30).map(f64::from).collect();
+        let p = calibrate_perplexity(&distances, 5.0);
+
+        let entropy: f64 = -p
+            .iter()
+            .filter(|&&x| x > 0.0)
+            .map(|x| x * x.ln())
+            .sum::<f64>();
+        assert!((entropy.exp() - 5.0).abs() < 1e-3);
+        assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_barnes_hut_matches_exact_repulsion() {
+        // Spread the points out to the scale of a typical embedding
+        let y: Vec<Vec<f64>> = three_blobs(10)
+            .into_iter()
+            .map(|p| p[..3].iter().map(|x| x * 5.0).collect())
+            .collect();
+        let tree = SpaceTree::build(&y, &[1.0; 30]);
+
+        for i in [0, 13, 29] {
+            let mut approx = vec![0.0; 3];
+            let z_approx = repulsion(&tree, i, &y, &mut approx);
+            let z_exact: f64 = (0..y.len())
+                .filter(|&j| j != i)
+                .map(|j| 1.0 / (1.0 + squared_distance(&y[i], &y[j])))
+                .sum();
+            assert!((z_approx - z_exact).abs() / z_exact < 0.1, "{z_approx} vs {z_exact}");
+        }
+    }
+
+    #[test]
+    fn test_tsne_separates_clusters() {
+        let vectors = three_blobs(15);
+        let reducer = TSNEReducer::with_options(10.0, 12.0, 100.0, Box::new(Euclidean), 500, 3);
+        let result = reducer.reduce(&vectors, 2).expect("t-SNE failed");
+
+        assert_eq!(result.len(), 45);
+        for (i, a) in result.iter().enumerate() {
+            let nearest = (0..result.len())
+                .filter(|&j| j != i)
+                .min_by(|&x, &y| {
+                    squared_distance(a, &result[x]).total_cmp(&squared_distance(a, &result[y]))
+                })
+                .expect("No neighbours");
+            assert_eq!(nearest / 15, i / 15);
+        }
+    }
+
+    #[test]
+    fn test_tsne_is_deterministic_in_3d() {
+        let vectors = three_blobs(5);
+        let reducer = TSNEReducer::with_options(4.0, 12.0, 100.0, Box::new(Euclidean), 100, 9);
+
+        let first = reducer.reduce(&vectors, 3).expect("t-SNE failed");
+        let second = reducer.reduce(&vectors, 3).expect("t-SNE failed");
+        assert_eq!(first, second);
+        assert!(first.iter().all(|p| p.len() == 3));
+    }
+
+    #[test]
+    fn test_tsne_rejects_unsupported_dims() {
+        let result = TSNEReducer::new().reduce(&three_blobs(2), 4);
+
+        match result {
+            Err(PluginError::DimensionalityReductionError { method, reason: _ }) => {
+                assert_eq!(method, "t-SNE");
+            },
+            _ => panic!("Expected DimensionalityReductionError"),
+        }
+    }
+
+    #[test]
+    fn test_tsne_rejects_non_finite_parameters() {
+        for value in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let metric = || Box::new(Euclidean);
+            let reducers = [
+                TSNEReducer::with_options(value, 12.0, 100.0, metric(), 10, 0),
+                TSNEReducer::with_options(5.0, value, 100.0, metric(), 10, 0),
+                TSNEReducer::with_options(5.0, 12.0, value, metric(), 10, 0),
+            ];
+            for reducer in reducers {
+                let result = reducer.reduce(&three_blobs(2), 2);
+                assert!(
+                    matches!(result, Err(PluginError::DimensionalityReductionError { .. })),
+                    "{value}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,533 @@
+//! UMAP (Uniform Manifold Approximation and Projection) dimensionality reduction.
+//!
+//! Follows the original paper and reference implementation: build a
+//! k-nearest-neighbour graph, turn it into a fuzzy simplicial set, initialise
+//! the layout spectrally and refine it with negative-sampling SGD. All
+//! randomness comes from a seeded generator, so layouts are reproducible.
+
+use crate::rng::SeededRng;
+use crate::{
+    DimensionalityReducer, DistanceMetric, Euclidean, PluginError, k_nearest_neighbor_graph,
+};
+use nalgebra::{DMatrix, Matrix2, Vector2};
+use std::collections::HashMap;
+
+/// Number of negative samples drawn per positive edge sample.
+const NEGATIVE_SAMPLE_RATE: f64 = 5.0;
+/// Scale of the embedded points (fixed, as in the reference implementation).
+const SPREAD: f64 = 1.0;
+/// Gradient clipping bound for the SGD updates.
+const GRADIENT_CLIP: f64 = 4.0;
+/// Iterations used by the spectral initialisation.
+const SPECTRAL_ITERATIONS: usize = 300;
+
+/// UMAP-based dimensionality reduction.
+///
+/// Preserves local neighbourhood structure, so clusters of related notes stay
+/// visually separated instead of collapsing into one blob as with SVD.
+pub struct UMAPReducer {
+    /// Size of the local neighbourhood used to build the graph.
+    n_neighbors: usize,
+    /// Minimum distance between embedded points.
+    min_dist: f64,
+    /// Distance metric in the input space.
+    metric: Box<dyn DistanceMetric>,
+    /// Number of SGD epochs.
+    n_epochs: usize,
+    /// Seed for initialisation noise and negative sampling.
+    seed: u64,
+}
+
+impl UMAPReducer {
+    /// Create a new UMAP reducer with default settings.
+    ///
+    /// Default: `n_neighbors`=15, `min_dist`=0.1, metric=euclidean, `n_epochs`=200, seed=42
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_options(15, 0.1, Box::new(Euclidean), 200, 42)
+    }
+
+    /// Create a new UMAP reducer with custom settings.
+    ///
+    /// # Arguments
+    /// * `n_neighbors` - Size of the local neighbourhood
+    /// * `min_dist` - Minimum distance between embedded points
+    /// * `metric` - Distance metric in the input space
+    /// * `n_epochs` - Number of SGD epochs
+    /// * `seed` - Random seed
+    #[must_use]
+    pub fn with_options(
+        n_neighbors: usize,
+        min_dist: f64,
+        metric: Box<dyn DistanceMetric>,
+        n_epochs: usize,
+        seed: u64,
+    ) -> Self {
+        Self { n_neighbors, min_dist, metric, n_epochs, seed }
+    }
+
+    /// Build the symmetric fuzzy simplicial set as a list of weighted edges.
+    ///
+    /// Each directed kNN edge gets weight exp(-(d - ρᵢ) / σᵢ); the two
+    /// directions are then combined with the fuzzy union a + b - ab.
+    fn fuzzy_simplicial_set(
+        &self,
+        vectors: &[Vec<f64>],
+        k: usize,
+    ) -> Result<Vec<(usize, usize, f64)>, PluginError> {
+        let knn = k_nearest_neighbor_graph(vectors, k, self.metric.as_ref())?;
+
+        let mut pair_weights: HashMap<(usize, usize), (f64, f64)> = HashMap::new();
+        for (i, neighbors) in knn.iter().enumerate() {
+            let distances: Vec<f64> = neighbors.iter().map(|c| c.distance).collect();
+            let (rho, sigma) = smooth_knn_distance(&distances);
+            for neighbor in neighbors {
+                let weight = (-(neighbor.distance - rho).max(0.0) / sigma).exp();
+                let j = neighbor.index;
+                let entry = pair_weights
+                    .entry((i.min(j), i.max(j)))
+                    .or_insert((0.0, 0.0));
+                if i < j {
+                    entry.0 = weight;
+                } else {
+                    entry.1 = weight;
+                }
+            }
+        }
+
+        let mut edges: Vec<(usize, usize, f64)> = pair_weights
+            .into_iter()
+            .map(|((i, j), (a, b))| (i, j, a.mul_add(-b, a + b)))
+            .filter(|&(_, _, w)| w > 0.0)
+            .collect();
+        // HashMap order is random; sort so the SGD schedule is reproducible
+        edges.sort_by_key(|&(i, j, _)| (i, j));
+        Ok(edges)
+    }
+
+    /// Refine an initial layout with negative-sampling SGD.
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn optimize_layout(
+        &self,
+        embedding: &mut [Vec<f64>],
+        edges: &[(usize, usize, f64)],
+        rng: &mut SeededRng,
+    ) {
+        let (a, b) = find_ab_params(SPREAD, self.min_dist);
+        let n_epochs = self.n_epochs as f64;
+        let max_weight = edges.iter().map(|e| e.2).fold(0.0, f64::max);
+
+        // Each undirected edge is sampled in both directions
+        let samples: Vec<(usize, usize, f64)> = edges
+            .iter()
+            .filter(|e| e.2 >= max_weight / n_epochs)
+            .flat_map(|&(i, j, w)| [(i, j, max_weight / w), (j, i, max_weight / w)])
+            .collect();
+        let mut next_sample: Vec<f64> = samples.iter().map(|s| s.2).collect();
+        let mut next_negative: Vec<f64> =
+            samples.iter().map(|s| s.2 / NEGATIVE_SAMPLE_RATE).collect();
+        let n_points = embedding.len();
+
+        for epoch in 0..self.n_epochs {
+            let epoch = epoch as f64;
+            let alpha = 1.0 - epoch / n_epochs;
+
+            for (s, &(head, tail, epochs_per_sample)) in samples.iter().enumerate() {
+                if next_sample[s] > epoch {
+                    continue;
+                }
+
+                let dist_sq = squared_distance(&embedding[head], &embedding[tail]);
+                if dist_sq > 0.0 {
+                    let coeff =
+                        (-2.0 * a * b * dist_sq.powf(b - 1.0)) / (a.mul_add(dist_sq.powf(b), 1.0));
+                    for d in 0..embedding[head].len() {
+                        let diff = embedding[head][d] - embedding[tail][d];
+                        let grad = (coeff * diff).clamp(-GRADIENT_CLIP, GRADIENT_CLIP) * alpha;
+                        embedding[head][d] += grad;
+                        embedding[tail][d] -= grad;
+                    }
+                }
+                next_sample[s] += epochs_per_sample;
+
+                let epochs_per_negative = epochs_per_sample / NEGATIVE_SAMPLE_RATE;
+                let negatives =
+                    ((epoch - next_negative[s]) / epochs_per_negative).max(0.0) as usize;
+                for _ in 0..negatives {
+                    let other = rng.next_index(n_points);
+                    if other == head {
+                        continue;
+                    }
+                    let dist_sq = squared_distance(&embedding[head], &embedding[other]);
+                    let coeff = if dist_sq > 0.0 {
+                        2.0 * b / ((0.001 + dist_sq) * (a.mul_add(dist_sq.powf(b), 1.0)))
+                    } else {
+                        0.0
+                    };
+                    for d in 0..embedding[head].len() {
+                        let diff = embedding[head][d] - embedding[other][d];
+                        let grad = if coeff > 0.0 {
+                            (coeff * diff).clamp(-GRADIENT_CLIP, GRADIENT_CLIP)
+                        } else {
+                            GRADIENT_CLIP
+                        };
+                        embedding[head][d] += grad * alpha;
+                    }
+                }
+                next_negative[s] += negatives as f64 * epochs_per_negative;
+            }
+        }
+    }
+}
+
+impl Default for UMAPReducer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DimensionalityReducer for UMAPReducer {
+    fn reduce(
+        &self,
+        vectors: &[Vec<f64>],
+        target_dims: usize,
+    ) -> Result<Vec<Vec<f64>>, PluginError> {
+        if vectors.is_empty() {
+            return Err(PluginError::InsufficientData { required: 1, provided: 0 });
+        }
+
+        // Validate all vectors have same dimensionality
+        let dim = vectors[0].len();
+        for (i, vec) in vectors.iter().enumerate() {
+            if vec.len() != dim {
+                return Err(PluginError::InvalidVectorDimensions {
+                    expected: dim,
+                    got: vec.len(),
+                    vector_index: i,
+                });
+            }
+        }
+
+        // Spread and learning rate are constants; min_dist is the only float setting
+        if target_dims == 0
+            || self.n_neighbors < 2
+            || !(self.min_dist.is_finite() && self.min_dist >= 0.0)
+        {
+            return Err(PluginError::DimensionalityReductionError {
+                method: self.method_name().to_string(),
+                reason: format!(
+                    "Invalid parameters: target_dims={target_dims}, n_neighbors={}, min_dist={}",
+                    self.n_neighbors, self.min_dist
+                ),
+            });
+        }
+
+        let n = vectors.len();
+        if n == 1 {
+            return Ok(vec![vec![0.0; target_dims]]);
+        }
+
+        let k = self.n_neighbors.min(n - 1);
+        let edges = self.fuzzy_simplicial_set(vectors, k)?;
+
+        let mut rng = SeededRng::new(self.seed);
+        let mut embedding = spectral_layout(n, &edges, target_dims, &mut rng)
+            .unwrap_or_else(|| random_layout(n, target_dims, &mut rng));
+        self.optimize_layout(&mut embedding, &edges, &mut rng);
+
+        Ok(embedding)
+    }
+
+    fn method_name(&self) -> &'static str {
+        "UMAP"
+    }
+}
+
+/// Find ρ (distance to the nearest neighbour) and σ for one point.
+///
+/// σ is chosen by binary search so the neighbour weights sum to log₂(k).
+fn smooth_knn_distance(distances: &[f64]) -> (f64, f64) {
+    #[allow(clippy::cast_precision_loss)]
+    let target = (distances.len() as f64).log2();
+    let rho = distances.iter().copied().find(|&d| d > 0.0).unwrap_or(0.0);
+
+    let (mut lo, mut hi, mut sigma) = (0.0, f64::INFINITY, 1.0);
+    for _ in 0..64 {
+        let total: f64 = distances
+            .iter()
+            .map(|&d| (-(d - rho).max(0.0) / sigma).exp())
+            .sum();
+        if (total - target).abs() < 1e-5 {
+            break;
+        }
+        if total > target {
+            hi = sigma;
+            sigma = f64::midpoint(lo, hi);
+        } else {
+            lo = sigma;
+            sigma = if hi.is_finite() {
+                f64::midpoint(lo, hi)
+            } else {
+                sigma * 2.0
+            };
+        }
+    }
+
+    // Keep σ away from zero so near-duplicate points do not produce NaNs
+    #[allow(clippy::cast_precision_loss)]
+    let mean = distances.iter().sum::<f64>() / distances.len().max(1) as f64;
+    (rho, sigma.max(1e-3 * mean).max(1e-12))
+}
+
+/// Fit the `a`, `b` parameters of the low-dimensional similarity curve
+/// 1 / (1 + a·d^2b) to the target curve defined by `spread` and `min_dist`.
+///
+/// Uses Levenberg-Marquardt least squares over 300 sample points.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn find_ab_params(spread: f64, min_dist: f64) -> (f64, f64) {
+    let xs: Vec<f64> = (0..300)
+        .map(|i| 3.0 * spread * f64::from(i) / 299.0)
+        .collect();
+    let ys: Vec<f64> = xs
+        .iter()
+        .map(|&x| {
+            if x < min_dist {
+                1.0
+            } else {
+                (-(x - min_dist) / spread).exp()
+            }
+        })
+        .collect();
+
+    let residual_sum = |a: f64, b: f64| -> f64 {
+        xs.iter()
+            .zip(&ys)
+            .map(|(&x, &y)| {
+                let r = 1.0 / a.mul_add(x.powf(2.0 * b), 1.0) - y;
+                r * r
+            })
+            .sum()
+    };
+
+    let (mut a, mut b) = (1.5, 0.9);
+    let mut lambda = 1e-3;
+    let mut error = residual_sum(a, b);
+    for _ in 0..200 {
+        // Accumulate JᵀJ and Jᵀr for the 2-parameter model
+        let mut jtj = Matrix2::zeros();
+        let mut jtr = Vector2::zeros();
+        for (&x, &y) in xs.iter().zip(&ys) {
+            if x <= 0.0 {
+                continue;
+            }
+            let x2b = x.powf(2.0 * b);
+            let denom = a.mul_add(x2b, 1.0);
+            let jacobian = Vector2::new(-x2b, -a * x2b * 2.0 * x.ln()) / (denom * denom);
+            jtj += jacobian * jacobian.transpose();
+            jtr += jacobian * (1.0 / denom - y);
+        }
+
+        let damped = jtj + Matrix2::from_diagonal(&jtj.diagonal()) * lambda;
+        let Some(step) = damped.lu().solve(&(-jtr)) else {
+            break;
+        };
+        let (new_a, new_b) = ((a + step.x).max(1e-6), (b + step.y).max(1e-6));
+        let new_error = residual_sum(new_a, new_b);
+
+        if new_error < error {
+            let converged = (error - new_error) < 1e-12;
+            (a, b, error) = (new_a, new_b, new_error);
+            lambda *= 0.1;
+            if converged {
+                break;
+            }
+        } else {
+            lambda *= 10.0;
+        }
+    }
+
+    (a, b)
+}
+
+/// Spectral initialisation from the normalised graph Laplacian.
+///
+/// Computes the eigenvectors of I - D^-1/2 W D^-1/2 with the smallest
+/// non-trivial eigenvalues by subspace iteration on (I + D^-1/2 W D^-1/2) / 2,
+/// deflating the trivial D^1/2·1 direction. Returns `None` if the graph is
+/// too small or the iteration does not produce finite coordinates.
+fn spectral_layout(
+    n: usize,
+    edges: &[(usize, usize, f64)],
+    dims: usize,
+    rng: &mut SeededRng,
+) -> Option<Vec<Vec<f64>>> {
+    if n <= dims + 1 {
+        return None;
+    }
+
+    let mut degree = vec![0.0; n];
+    for &(i, j, w) in edges {
+        degree[i] += w;
+        degree[j] += w;
+    }
+    let inv_sqrt: Vec<f64> = degree
+        .iter()
+        .map(|&d| if d > 0.0 { 1.0 / d.sqrt() } else { 0.0 })
+        .collect();
+
+    let trivial_norm = degree.iter().sum::<f64>().sqrt();
+    if trivial_norm <= 0.0 {
+        return None;
+    }
+    let trivial: Vec<f64> = degree.iter().map(|d| d.sqrt() / trivial_norm).collect();
+
+    let mut basis = DMatrix::from_fn(n, dims, |_, _| rng.next_normal());
+    for _ in 0..SPECTRAL_ITERATIONS {
+        let mut next = basis.scale(0.5);
+        for &(i, j, w) in edges {
+            let weight = 0.5 * w * inv_sqrt[i] * inv_sqrt[j];
+            for c in 0..dims {
+                next[(i, c)] += weight * basis[(j, c)];
+                next[(j, c)] += weight * basis[(i, c)];
+            }
+        }
+        for c in 0..dims {
+            let projection: f64 = (0..n).map(|r| next[(r, c)] * trivial[r]).sum();
+            for r in 0..n {
+                next[(r, c)] -= projection * trivial[r];
+            }
+        }
+        basis = next.qr().q();
+    }
+
+    // Scale into a [-10, 10] box and add a little noise, as the reference does
+    let max_abs = basis.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+    if !max_abs.is_finite() || max_abs <= 0.0 {
+        return None;
+    }
+    let expansion = 10.0 / max_abs;
+    Some(
+        (0..n)
+            .map(|r| {
+                (0..dims)
+                    .map(|c| basis[(r, c)].mul_add(expansion, 1e-4 * rng.next_normal()))
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+/// Uniform random initialisation in a [-10, 10] box.
+fn random_layout(n: usize, dims: usize, rng: &mut SeededRng) -> Vec<Vec<f64>> {
+    (0..n)
+        .map(|_| {
+            (0..dims)
+                .map(|_| 20.0_f64.mul_add(rng.next_f64(), -10.0))
+                .collect()
+        })
+        .collect()
+}
+
+/// Squared Euclidean distance in the embedding space.
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_blobs() -> Vec<Vec<f64>> {
+        let mut rng = SeededRng::new(7);
+        (0..40)
+            .map(|i| {
+                let offset = if i < 20 { 0.0 } else { 10.0 };
+                (0..5)
+                    .map(|_| rng.next_normal().mul_add(0.5, offset))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn centroid(points: &[Vec<f64>]) -> Vec<f64> {
+        let dims = points[0].len();
+        #[allow(clippy::cast_precision_loss)]
+        let n = points.len() as f64;
+        (0..dims)
+            .map(|d| points.iter().map(|p| p[d]).sum::<f64>() / n)
+            .collect()
+    }
+
+    #[test]
+    fn test_find_ab_params_matches_reference() {
+        // Reference values from umap-learn for spread=1, min_dist=0.1
+        let (a, b) = find_ab_params(1.0, 0.1);
+        assert!((a - 1.577).abs() < 0.05, "a = {a}");
+        assert!((b - 0.895).abs() < 0.02, "b = {b}");
+    }
+
+    #[test]
+    fn test_umap_separates_clusters() {
+        let vectors = two_blobs();
+        let reducer = UMAPReducer::with_options(10, 0.1, Box::new(Euclidean), 100, 1);
+        let result = reducer.reduce(&vectors, 2).expect("UMAP failed");
+
+        assert_eq!(result.len(), 40);
+        let (left, right) = result.split_at(20);
+        let gap = squared_distance(&centroid(left), &centroid(right)).sqrt();
+        let spread = left
+            .iter()
+            .map(|p| squared_distance(p, &centroid(left)).sqrt())
+            .fold(0.0, f64::max);
+        assert!(gap > spread, "gap {gap} should exceed cluster radius {spread}");
+    }
+
+    #[test]
+    fn test_umap_is_deterministic() {
+        let vectors = two_blobs();
+        let reducer = UMAPReducer::with_options(8, 0.2, Box::new(Euclidean), 50, 3);
+
+        let first = reducer.reduce(&vectors, 3).expect("UMAP failed");
+        let second = reducer.reduce(&vectors, 3).expect("UMAP failed");
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_umap_tiny_input() {
+        let reducer = UMAPReducer::new();
+        let single = reducer.reduce(&[vec![1.0, 2.0]], 2).expect("UMAP failed");
+        assert_eq!(single, vec![vec![0.0, 0.0]]);
+
+        let pair = reducer
+            .reduce(&[vec![1.0, 2.0], vec![3.0, 4.0]], 2)
+            .expect("UMAP failed");
+        assert!(pair.iter().flatten().all(|x| x.is_finite()));
+    }
+
+    #[test]
+    fn test_umap_invalid_parameters() {
+        let reducer = UMAPReducer::with_options(1, 0.1, Box::new(Euclidean), 10, 0);
+        let result = reducer.reduce(&two_blobs(), 2);
+
+        match result {
+            Err(PluginError::DimensionalityReductionError { method, reason: _ }) => {
+                assert_eq!(method, "UMAP");
+            },
+            _ => panic!("Expected DimensionalityReductionError"),
+        }
+    }
+
+    #[test]
+    fn test_umap_rejects_invalid_min_dist() {
+        for min_dist in [f64::NAN, f64::INFINITY, -0.1] {
+            let reducer = UMAPReducer::with_options(5, min_dist, Box::new(Euclidean), 10, 0);
+            let result = reducer.reduce(&two_blobs(), 2);
+
+            assert!(
+                matches!(result, Err(PluginError::DimensionalityReductionError { .. })),
+                "min_dist={min_dist}"
+            );
+        }
+    }
+}
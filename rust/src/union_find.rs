@@ -0,0 +1,18 @@
+//! Disjoint-set forest helpers.
+//!
+//! Clustering and component code keeps its own `parent` array, since each
+//! caller links roots differently (by size, into a fresh node, or by index);
+//! they share the root lookup.
+
+/// Root of `node` in a union-find forest, halving the path on the way.
+///
+/// # Arguments
+/// * `parent` - Parent of each node; roots are their own parent
+/// * `node` - Node to look up
+pub fn find_root(parent: &mut [usize], mut node: usize) -> usize {
+    while parent[node] != node {
+        parent[node] = parent[parent[node]];
+        node = parent[node];
+    }
+    node
+}
@@ -2,7 +2,7 @@
 //!
 //! This module provides utilities for vector manipulation and analysis.
 
-use crate::PluginError;
+use crate::{KMeansConfig, PluginError, kmeans};
 
 /// Normalize vectors to unit length.
 ///
@@ -39,166 +39,291 @@ pub fn normalize_vectors(vectors: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PluginEr
 /// # Errors
 /// Returns error if vectors have different dimensions
 pub fn euclidean_distance(a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
-    if a.len() != b.len() {
-        return Err(PluginError::InvalidVectorDimensions {
-            expected: a.len(),
-            got: b.len(),
-            vector_index: 0,
-        });
-    }
+    Ok(squared_euclidean_distance(a, b)?.sqrt())
+}
 
-    let distance = a
-        .iter()
-        .zip(b.iter())
-        .map(|(x, y)| (x - y) * (x - y))
-        .sum::<f64>()
-        .sqrt();
+/// Compute squared Euclidean distance between two vectors.
+///
+/// # Arguments
+/// * `a` - First vector
+/// * `b` - Second vector
+///
+/// # Returns
+/// Squared Euclidean distance
+///
+/// # Errors
+/// Returns error if vectors have different dimensions
+pub fn squared_euclidean_distance(a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
+    check_dimensions(a, b)?;
+    Ok(a.iter().zip(b.iter()).map(|(x, y)| (x - y) * (x - y)).sum())
+}
 
-    Ok(distance)
+/// Compute the dot (inner) product of two vectors.
+///
+/// # Arguments
+/// * `a` - First vector
+/// * `b` - Second vector
+///
+/// # Returns
+/// Sum of element-wise products
+///
+/// # Errors
+/// Returns error if vectors have different dimensions
+pub fn dot_product(a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
+    check_dimensions(a, b)?;
+    Ok(a.iter().zip(b.iter()).map(|(x, y)| x * y).sum())
 }
 
-/// Simple k-means clustering for vector assignment.
+/// Compute cosine similarity between two vectors.
 ///
 /// # Arguments
-/// * `vectors` - Input vectors to cluster
-/// * `k` - Number of clusters
+/// * `a` - First vector
+/// * `b` - Second vector
 ///
 /// # Returns
-/// Cluster assignment for each vector
+/// Cosine of the angle between the vectors, in `[-1, 1]`
 ///
 /// # Errors
-/// Returns error if k is invalid or vectors have mismatched dimensions
-pub fn simple_kmeans_clustering(vectors: &[Vec<f64>], k: usize) -> Result<Vec<usize>, PluginError> {
-    if vectors.is_empty() {
-        return Err(PluginError::InsufficientData { required: 1, provided: 0 });
+/// Returns error if vectors have different dimensions or either has zero norm
+pub fn cosine_similarity(a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
+    let dot = dot_product(a, b)?;
+    let norm_a = a.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f64>().sqrt();
+    if norm_a < 1e-10 || norm_b < 1e-10 {
+        return Err(PluginError::ZeroNormVector);
     }
+    Ok((dot / (norm_a * norm_b)).clamp(-1.0, 1.0))
+}
 
-    if k == 0 {
-        return Err(PluginError::InsufficientData { required: 1, provided: 0 });
+/// Ensure two vectors have the same dimensionality.
+const fn check_dimensions(a: &[f64], b: &[f64]) -> Result<(), PluginError> {
+    if a.len() != b.len() {
+        return Err(PluginError::InvalidVectorDimensions {
+            expected: a.len(),
+            got: b.len(),
+            vector_index: 0,
+        });
     }
+    Ok(())
+}
 
-    if k > vectors.len() {
-        return Err(PluginError::InsufficientData { required: k, provided: vectors.len() });
+/// Trait for pluggable distance metrics.
+///
+/// Smaller values mean "more similar". Similarity measures such as cosine and
+/// inner product are exposed as distances so that every algorithm can minimise.
+pub trait DistanceMetric {
+    /// Compute the distance between two vectors.
+    ///
+    /// # Errors
+    /// Returns error if vectors have different dimensions
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, PluginError>;
+
+    /// Convert a distance from this metric into a similarity score (higher is more similar).
+    fn similarity(&self, distance: f64) -> f64 {
+        1.0 / (1.0 + distance)
     }
 
-    let dim = vectors[0].len();
+    /// Get the name of this metric.
+    fn name(&self) -> &'static str;
+}
 
-    // Validate all vectors have same dimensionality
-    for (i, vec) in vectors.iter().enumerate() {
-        if vec.len() != dim {
-            return Err(PluginError::InvalidVectorDimensions {
-                expected: dim,
-                got: vec.len(),
-                vector_index: i,
-            });
-        }
+impl<T: DistanceMetric + ?Sized> DistanceMetric for &T {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
+        (**self).distance(a, b)
     }
 
-    // Initialize centroids using k-means++ strategy
-    let mut centroids = initialize_centroids_kmeanspp(vectors, k)?;
+    fn similarity(&self, distance: f64) -> f64 {
+        (**self).similarity(distance)
+    }
 
-    // Run k-means iterations
-    let max_iterations = 100;
-    let mut assignments = vec![0; vectors.len()];
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+}
 
-    for _ in 0..max_iterations {
-        let mut changed = false;
+/// Euclidean (L2) distance.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Euclidean;
 
-        // Assignment step: assign each point to nearest centroid
-        for (i, vec) in vectors.iter().enumerate() {
-            let mut min_dist = f64::MAX;
-            let mut best_cluster = 0;
+impl DistanceMetric for Euclidean {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
+        euclidean_distance(a, b)
+    }
 
-            for (j, centroid) in centroids.iter().enumerate() {
-                let dist = euclidean_distance(vec, centroid)?;
-                if dist < min_dist {
-                    min_dist = dist;
-                    best_cluster = j;
-                }
-            }
+    fn name(&self) -> &'static str {
+        "euclidean"
+    }
+}
 
-            if assignments[i] != best_cluster {
-                assignments[i] = best_cluster;
-                changed = true;
-            }
-        }
+/// Squared Euclidean distance (cheaper, same ordering as Euclidean).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SquaredEuclidean;
 
-        // If no assignments changed, we've converged
-        if !changed {
-            break;
+impl DistanceMetric for SquaredEuclidean {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
+        squared_euclidean_distance(a, b)
+    }
+
+    fn name(&self) -> &'static str {
+        "squared_euclidean"
+    }
+}
+
+/// Cosine distance, `1 - cosine_similarity`.
+///
+/// Zero vectors are treated as orthogonal to everything (distance 1) so that
+/// empty link rows do not abort a whole clustering run.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cosine;
+
+impl DistanceMetric for Cosine {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
+        match cosine_similarity(a, b) {
+            Ok(similarity) => Ok(1.0 - similarity),
+            Err(PluginError::ZeroNormVector) => Ok(1.0),
+            Err(e) => Err(e),
         }
+    }
 
-        // Update step: recompute centroids
-        centroids = compute_centroids(vectors, &assignments, k, dim);
+    fn similarity(&self, distance: f64) -> f64 {
+        1.0 - distance
     }
 
-    Ok(assignments)
+    fn name(&self) -> &'static str {
+        "cosine"
+    }
 }
 
-/// Initialize centroids using k-means++ strategy.
-fn initialize_centroids_kmeanspp(
-    vectors: &[Vec<f64>],
-    k: usize,
-) -> Result<Vec<Vec<f64>>, PluginError> {
-    let mut centroids = Vec::with_capacity(k);
+/// Inner-product distance, `1 - a·b`, for embeddings trained for dot-product search.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InnerProduct;
 
-    // Choose first centroid randomly (use first point for determinism)
-    centroids.push(vectors[0].clone());
+impl DistanceMetric for InnerProduct {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
+        Ok(1.0 - dot_product(a, b)?)
+    }
 
-    // Choose remaining centroids with probability proportional to distance squared
-    for _ in 1..k {
-        let mut distances = vec![0.0; vectors.len()];
+    fn similarity(&self, distance: f64) -> f64 {
+        1.0 - distance
+    }
 
-        for (i, vec) in vectors.iter().enumerate() {
-            let mut min_dist = f64::MAX;
-            for centroid in &centroids {
-                let dist = euclidean_distance(vec, centroid)?;
-                min_dist = min_dist.min(dist);
-            }
-            distances[i] = min_dist * min_dist;
-        }
+    fn name(&self) -> &'static str {
+        "inner_product"
+    }
+}
 
-        // Select point with highest distance (deterministic, simpler than random)
-        let max_idx = distances
-            .iter()
-            .enumerate()
-            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .map_or(0, |(idx, _)| idx);
+/// Manhattan (L1) distance.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Manhattan;
 
-        centroids.push(vectors[max_idx].clone());
+impl DistanceMetric for Manhattan {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
+        check_dimensions(a, b)?;
+        Ok(a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).sum())
     }
 
-    Ok(centroids)
+    fn name(&self) -> &'static str {
+        "manhattan"
+    }
 }
 
-/// Compute new centroids from current assignments.
-fn compute_centroids(
-    vectors: &[Vec<f64>],
-    assignments: &[usize],
-    k: usize,
-    dim: usize,
-) -> Vec<Vec<f64>> {
-    let mut centroids = vec![vec![0.0; dim]; k];
-    let mut counts = vec![0; k];
-
-    // Sum all vectors in each cluster
-    for (vec, &cluster) in vectors.iter().zip(assignments.iter()) {
-        for (i, &val) in vec.iter().enumerate() {
-            centroids[cluster][i] += val;
-        }
-        counts[cluster] += 1;
+/// Chebyshev (L∞) distance.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Chebyshev;
+
+impl DistanceMetric for Chebyshev {
+    fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, PluginError> {
+        check_dimensions(a, b)?;
+        Ok(a.iter()
+            .zip(b.iter())
+            .map(|(x, y)| (x - y).abs())
+            .fold(0.0, f64::max))
     }
 
-    // Compute means (avoid division by zero)
-    for (cluster, count) in counts.iter().enumerate() {
-        if *count > 0 {
-            for val in &mut centroids[cluster] {
-                *val /= f64::from(*count);
-            }
-        }
+    fn name(&self) -> &'static str {
+        "chebyshev"
+    }
+}
+
+/// Look up a distance metric by name.
+///
+/// # Arguments
+/// * `name` - One of `euclidean`, `squared_euclidean`, `cosine`, `inner_product`
+///   (or `dot`), `manhattan`, `chebyshev`
+///
+/// # Returns
+/// The matching metric
+///
+/// # Errors
+/// Returns error if the name is not recognised
+pub fn distance_metric_from_name(name: &str) -> Result<Box<dyn DistanceMetric>, PluginError> {
+    match name.to_lowercase().as_str() {
+        "euclidean" | "l2" => Ok(Box::new(Euclidean)),
+        "squared_euclidean" | "sqeuclidean" => Ok(Box::new(SquaredEuclidean)),
+        "cosine" => Ok(Box::new(Cosine)),
+        "inner_product" | "dot" => Ok(Box::new(InnerProduct)),
+        "manhattan" | "l1" => Ok(Box::new(Manhattan)),
+        "chebyshev" => Ok(Box::new(Chebyshev)),
+        _ => Err(PluginError::ValidationError {
+            field: "metric".to_string(),
+            value: name.to_string(),
+            reason: "Unknown distance metric".to_string(),
+        }),
     }
+}
+
+/// Distance between two vectors, clamped at zero.
+///
+/// Similarity-derived distances such as inner product can dip below zero.
+/// Clustering code that sums, squares or thresholds distances treats those
+/// pairs as coincident rather than closer than identical.
+///
+/// # Errors
+/// Returns error if vectors have different dimensions
+pub fn non_negative_distance(
+    metric: &dyn DistanceMetric,
+    a: &[f64],
+    b: &[f64],
+) -> Result<f64, PluginError> {
+    Ok(metric.distance(a, b)?.max(0.0))
+}
+
+/// Simple k-means clustering for vector assignment.
+///
+/// # Arguments
+/// * `vectors` - Input vectors to cluster
+/// * `k` - Number of clusters
+///
+/// # Returns
+/// Cluster assignment for each vector
+///
+/// # Errors
+/// Returns error if k is invalid or vectors have mismatched dimensions
+pub fn simple_kmeans_clustering(vectors: &[Vec<f64>], k: usize) -> Result<Vec<usize>, PluginError> {
+    kmeans_clustering_with_metric(vectors, k, &Euclidean)
+}
 
-    centroids
+/// K-means clustering using a custom distance metric for assignment.
+///
+/// Runs [`kmeans`] with the default [`KMeansConfig`] and keeps only the
+/// assignments.
+///
+/// # Arguments
+/// * `vectors` - Input vectors to cluster
+/// * `k` - Number of clusters
+/// * `metric` - Distance metric used to assign points to centroids
+///
+/// # Returns
+/// Cluster assignment for each vector
+///
+/// # Errors
+/// Returns error if k is invalid or vectors have mismatched dimensions
+pub fn kmeans_clustering_with_metric(
+    vectors: &[Vec<f64>],
+    k: usize,
+    metric: &dyn DistanceMetric,
+) -> Result<Vec<usize>, PluginError> {
+    kmeans(vectors, k, &KMeansConfig::default(), metric).map(|result| result.assignments)
 }
 
 #[cfg(test)]
@@ -240,7 +365,70 @@ mod tests {
         let dist = euclidean_distance(&a, &b).expect("Distance calculation failed");
 
         // sqrt((4-1)^2 + (5-2)^2 + (6-3)^2) = sqrt(9+9+9) = sqrt(27) ≈ 5.196
-        assert!((dist - 5.196152422706632).abs() < 1e-10);
+        assert!((dist - 5.196_152_422_706_632).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_cosine_similarity_and_dot_product() {
+        let a = vec![1.0, 0.0];
+        let b = vec![1.0, 1.0];
+
+        let dot = dot_product(&a, &b).expect("Dot product failed");
+        let cos = cosine_similarity(&a, &b).expect("Cosine similarity failed");
+
+        assert!((dot - 1.0).abs() < 1e-10);
+        assert!((cos - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-10);
+        assert!(matches!(cosine_similarity(&a, &[0.0, 0.0]), Err(PluginError::ZeroNormVector)));
+    }
+
+    #[test]
+    fn test_distance_metrics() {
+        let a = vec![1.0, 2.0, 3.0];
+        let b = vec![4.0, 0.0, 3.0];
+
+        let expected = [
+            ("euclidean", 13.0_f64.sqrt()),
+            ("squared_euclidean", 13.0),
+            ("manhattan", 5.0),
+            ("chebyshev", 3.0),
+            ("inner_product", 1.0 - 13.0),
+            ("cosine", 1.0 - 13.0 / (14.0_f64.sqrt() * 5.0)),
+        ];
+        for (name, value) in expected {
+            let metric = distance_metric_from_name(name).expect("Unknown metric");
+            assert_eq!(metric.name(), name);
+            let dist = metric
+                .distance(&a, &b)
+                .expect("Distance calculation failed");
+            assert!((dist - value).abs() < 1e-10, "{name}: {dist} != {value}");
+        }
+    }
+
+    #[test]
+    fn test_distance_metric_unknown_name() {
+        let result = distance_metric_from_name("hamming");
+        assert!(matches!(result, Err(PluginError::ValidationError { .. })));
+    }
+
+    #[test]
+    fn test_distance_metric_dimension_mismatch() {
+        let result = Manhattan.distance(&[1.0, 2.0], &[1.0]);
+        assert!(matches!(result, Err(PluginError::InvalidVectorDimensions { .. })));
+    }
+
+    #[test]
+    fn test_kmeans_with_cosine_metric() {
+        // Two directions with very different magnitudes
+        let vectors =
+            vec![vec![1.0, 0.1], vec![10.0, 0.5], vec![0.1, 1.0], vec![0.4, 9.0], vec![5.0, 0.2]];
+
+        let assignments =
+            kmeans_clustering_with_metric(&vectors, 2, &Cosine).expect("Clustering failed");
+
+        assert_eq!(assignments[0], assignments[1]);
+        assert_eq!(assignments[0], assignments[4]);
+        assert_eq!(assignments[2], assignments[3]);
+        assert_ne!(assignments[0], assignments[2]);
     }
 
     #[test]
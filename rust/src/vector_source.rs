@@ -80,7 +80,7 @@ impl VectorWithMetadata {
 
     /// Get the dimensionality of this vector.
     #[must_use]
-    pub fn dimensionality(&self) -> usize {
+    pub const fn dimensionality(&self) -> usize {
         self.vector.len()
     }
 }
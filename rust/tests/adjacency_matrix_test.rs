@@ -1,4 +1,6 @@
-use rust::{AdjacencyMatrixBuilder, NoteLink};
+//! Integration tests for adjacency matrix.
+
+use rust::{AdjacencyMatrixBuilder, GraphOptions, LaplacianKind, NoteLink};
 
 #[test]
 fn test_adjacency_matrix_basic() {
@@ -60,3 +62,15 @@ fn test_adjacency_matrix_multiple_links() {
     let snapshot = serde_json::to_string_pretty(&vectors).expect("Failed to serialize");
     insta::assert_snapshot!(snapshot);
 }
+
+#[test]
+fn test_graph_options_from_json() {
+    let options: GraphOptions =
+        serde_json::from_str(r#"{"directed": false, "laplacian": "symmetricNormalized"}"#)
+            .expect("Failed to parse options");
+
+    assert!(!options.directed);
+    assert_eq!(options.laplacian, LaplacianKind::SymmetricNormalized);
+    assert!(options.self_loops);
+    assert!(!options.binarize);
+}
@@ -0,0 +1,45 @@
+//! Integration tests for alignment.
+
+use rust::{DimensionalityReducer, NotePosition, SVDReducer, procrustes_align};
+
+fn layout(ids: &[&str], coords: Vec<Vec<f64>>) -> Vec<NotePosition> {
+    ids.iter()
+        .zip(coords)
+        .map(|(id, coordinates)| NotePosition { id: (*id).to_string(), coordinates })
+        .collect()
+}
+
+#[test]
+fn test_refresh_with_new_note_stays_close() {
+    let vectors = vec![
+        vec![1.0, 0.0, 0.2, 0.0],
+        vec![0.9, 0.1, 0.0, 0.1],
+        vec![0.0, 1.0, 0.1, 0.0],
+        vec![0.1, 0.9, 0.0, 0.3],
+        vec![0.0, 0.0, 1.0, 0.9],
+    ];
+    let ids = ["a", "b", "c", "d", "e", "f"];
+    let reducer = SVDReducer::new();
+    let before = layout(&ids[..5], reducer.reduce(&vectors, 2).expect("SVD failed"));
+
+    let mut grown = vectors;
+    grown.push(vec![0.5, 0.5, 0.5, 0.5]);
+    let after = layout(&ids, reducer.reduce(&grown, 2).expect("SVD failed"));
+    let aligned = procrustes_align(&after, &before).expect("Alignment failed");
+
+    let drift = |positions: &[NotePosition]| -> f64 {
+        positions
+            .iter()
+            .zip(&before)
+            .map(|(p, q)| {
+                p.coordinates
+                    .iter()
+                    .zip(&q.coordinates)
+                    .map(|(x, y)| (x - y).powi(2))
+                    .sum::<f64>()
+            })
+            .sum()
+    };
+    assert_eq!(aligned.len(), 6);
+    assert!(drift(&aligned) <= drift(&after) + 1e-12);
+}
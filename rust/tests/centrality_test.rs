@@ -0,0 +1,77 @@
+//! Integration tests for centrality.
+
+use rust::{
+    AdjacencyMatrixBuilder, BetweennessConfig, HitsConfig, NoteLink, PageRankConfig,
+    critical_notes, hits, pagerank, personalized_pagerank,
+};
+
+fn moc_vault() -> (AdjacencyMatrixBuilder, Vec<NoteLink>) {
+    // A map of content (MOC) links out to three topic notes; a separate
+    // cluster of notes only links among itself
+    let paths = ["moc.md", "a.md", "b.md", "c.md", "x.md", "y.md"];
+    let builder = AdjacencyMatrixBuilder::new(paths.iter().map(ToString::to_string).collect());
+    let links = [(0, 1), (0, 2), (0, 3), (1, 2), (2, 1), (4, 5), (5, 4)]
+        .into_iter()
+        .map(|(from_id, to_id)| NoteLink { from_id, to_id })
+        .collect();
+    (builder, links)
+}
+
+#[test]
+fn test_pagerank_scores_sum_to_one() {
+    let (builder, links) = moc_vault();
+    let matrix = builder.build(links).expect("Failed to build matrix");
+    let result = pagerank(&matrix, &PageRankConfig::default()).expect("PageRank failed");
+
+    assert_eq!(result.scores.len(), 6);
+    assert!(result.converged);
+    assert!((result.scores.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_notes_most_relevant_to_moc() {
+    let (builder, links) = moc_vault();
+    let matrix = builder.build(links).expect("Failed to build matrix");
+    let moc = builder.get_note_index("moc.md").expect("Missing MOC");
+    let result = personalized_pagerank(&matrix, &[moc], &PageRankConfig::default())
+        .expect("Personalized PageRank failed");
+
+    let mut ranked: Vec<usize> = (0..6).filter(|&i| i != moc).collect();
+    ranked.sort_by(|&a, &b| result.scores[b].total_cmp(&result.scores[a]));
+
+    // Notes linked from the MOC outrank the separate cluster
+    assert!(ranked[..3].iter().all(|&i| (1..=3).contains(&i)));
+}
+
+#[test]
+fn test_moc_is_the_top_hub() {
+    let (builder, links) = moc_vault();
+    let matrix = builder.build(links).expect("Failed to build matrix");
+    let result = hits(&matrix, &HitsConfig::default()).expect("HITS failed");
+
+    let moc = builder.get_note_index("moc.md").expect("Missing MOC");
+    assert!(result.converged);
+    assert!(
+        (0..6)
+            .filter(|&i| i != moc)
+            .all(|i| result.hubs[moc] > result.hubs[i])
+    );
+    assert!(result.authorities[moc].abs() < 1e-12);
+}
+
+#[test]
+fn test_moc_holds_its_topics_together() {
+    let (builder, links) = moc_vault();
+    let matrix = builder.build(links).expect("Failed to build matrix");
+    let result = critical_notes(&matrix, &BetweennessConfig::default()).expect("Analysis failed");
+
+    let moc = builder.get_note_index("moc.md").expect("Missing MOC");
+    let c = builder.get_note_index("c.md").expect("Missing note");
+    assert_eq!(result.articulation_points, vec![moc]);
+    assert_eq!(result.bridges, vec![[moc, c], [4, 5]]);
+    assert!(
+        (0..6)
+            .filter(|&i| i != moc)
+            .all(|i| result.betweenness[moc] > result.betweenness[i])
+    );
+}
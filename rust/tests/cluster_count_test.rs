@@ -0,0 +1,48 @@
+//! Integration tests for cluster count.
+
+use rust::{ClusterCountConfig, Cosine, KMeansConfig, choose_cluster_count};
+
+#[test]
+fn test_auto_count_finds_topics_in_embeddings() {
+    // Four topics along different axes of a 4-dimensional embedding space
+    let mut vectors = Vec::new();
+    for note in 0..24 {
+        let mut vector = vec![0.05; 4];
+        #[allow(clippy::cast_precision_loss)]
+        let wobble = (note % 6) as f64 * 0.02;
+        vector[note % 4] = 1.0 - wobble;
+        vector[(note + 1) % 4] += wobble;
+        vectors.push(vector);
+    }
+
+    let config = ClusterCountConfig { max_k: 8, ..ClusterCountConfig::default() };
+    let report = choose_cluster_count(&vectors, &config, &Cosine).expect("Sweep failed");
+
+    assert_eq!(report.recommended_k, 4);
+    assert_eq!(report.scores.len(), 7);
+    for topic in 0..4 {
+        let label = report.assignments[topic];
+        assert!(
+            (topic..24)
+                .step_by(4)
+                .all(|note| report.assignments[note] == label)
+        );
+    }
+    assert!(
+        report
+            .scores
+            .windows(2)
+            .all(|pair| pair[1].inertia <= pair[0].inertia + 1e-9)
+    );
+}
+
+#[test]
+fn test_config_accepts_partial_json() {
+    let config: ClusterCountConfig =
+        serde_json::from_str(r#"{"maxK": 6, "kmeans": {"restarts": 2}}"#)
+            .expect("Failed to parse config");
+
+    assert_eq!(config.max_k, 6);
+    assert_eq!(config.min_k, 2);
+    assert_eq!(config.kmeans, KMeansConfig { restarts: 2, ..KMeansConfig::default() });
+}
@@ -0,0 +1,46 @@
+//! Integration tests for cluster labels.
+
+use rust::{ClusterLabelConfig, NOISE_LABEL, label_clusters};
+
+#[test]
+fn test_labels_for_clustered_note_titles() {
+    let titles: Vec<String> = [
+        "Weekly review 2024-03",
+        "Weekly review 2024-04",
+        "Review template for the weekly planning",
+        "Garden planting schedule",
+        "Tomato planting in the garden",
+        "Garden compost and soil",
+        "Random thought about trains",
+    ]
+    .map(String::from)
+    .to_vec();
+    let assignments = [0, 0, 0, 1, 1, 1, NOISE_LABEL];
+
+    let labels = label_clusters(&assignments, &titles, &ClusterLabelConfig::default())
+        .expect("Labelling failed");
+
+    assert_eq!(labels.len(), 2);
+    assert_eq!((labels[0].cluster, labels[0].size), (0, 3));
+    assert_eq!(labels[0].label, "review, weekly");
+    assert!(labels[1].label.starts_with("garden"));
+    assert!(
+        labels
+            .iter()
+            .all(|l| l.keywords.iter().all(|k| k.term != "trains"))
+    );
+
+    let json = serde_json::to_value(&labels).expect("Failed to serialize");
+    assert_eq!(json[1]["keywords"][0]["term"], "garden");
+}
+
+#[test]
+fn test_config_accepts_partial_json() {
+    let config: ClusterLabelConfig =
+        serde_json::from_str(r#"{"minDocumentFrequency": 1, "stopwords": ["weekly"]}"#)
+            .expect("Failed to parse config");
+
+    assert_eq!(config.min_document_frequency, 1);
+    assert_eq!(config.stopwords, vec!["weekly"]);
+    assert!(config.default_stopwords);
+}
@@ -1,3 +1,5 @@
+//! Integration tests for commands.
+
 use rust::{calculate_fibonacci, generate_demo_message, generate_greeting, process_editor_text};
 
 #[test]
@@ -0,0 +1,27 @@
+//! Integration tests for community.
+
+use rust::{
+    AdjacencyMatrixBuilder, CommunityAlgorithm, CommunityConfig, NoteLink, detect_communities,
+};
+
+#[test]
+fn test_topic_clusters_become_communities() {
+    // Two topic clusters of notes with one cross-link, plus an orphan note
+    let paths: Vec<String> = (0..9).map(|i| format!("note{i}.md")).collect();
+    let builder = AdjacencyMatrixBuilder::new(paths);
+    let links =
+        [(0, 1), (1, 2), (2, 0), (0, 3), (3, 1), (4, 5), (5, 6), (6, 7), (7, 4), (4, 6), (3, 4)]
+            .into_iter()
+            .map(|(from_id, to_id)| NoteLink { from_id, to_id })
+            .collect();
+    let matrix = builder.build(links).expect("Failed to build matrix");
+
+    for algorithm in [CommunityAlgorithm::Louvain, CommunityAlgorithm::Leiden] {
+        let config = CommunityConfig { algorithm, ..CommunityConfig::default() };
+        let result = detect_communities(&matrix, &config).expect("Detection failed");
+
+        assert_eq!(result.assignments, vec![0, 0, 0, 0, 1, 1, 1, 1, 2]);
+        assert_eq!(result.community_count, 3);
+        assert!(result.modularity > 0.3);
+    }
+}
@@ -0,0 +1,43 @@
+//! Integration tests for dag.
+
+use rust::{AdjacencyMatrixBuilder, DagConfig, NoteLink, analyze_dag};
+
+fn course_outline() -> (AdjacencyMatrixBuilder, Vec<NoteLink>) {
+    // Each lesson links to the lessons it depends on
+    let paths = ["course.md", "lesson-3.md", "lesson-2.md", "lesson-1.md"].map(String::from);
+    let links = [(0, 1), (0, 2), (0, 3), (1, 2), (2, 3)]
+        .into_iter()
+        .map(|(from_id, to_id)| NoteLink { from_id, to_id })
+        .collect();
+    (AdjacencyMatrixBuilder::new(paths.to_vec()), links)
+}
+
+#[test]
+fn test_course_outline_is_ordered() {
+    let (builder, links) = course_outline();
+    let report = analyze_dag(&builder, links, &DagConfig::default()).expect("Analysis failed");
+
+    assert!(report.is_dag);
+    assert!(report.cycles.is_empty());
+    assert_eq!(
+        report.topological_order,
+        Some(vec![
+            "course.md".to_string(),
+            "lesson-3.md".to_string(),
+            "lesson-2.md".to_string(),
+            "lesson-1.md".to_string(),
+        ])
+    );
+}
+
+#[test]
+fn test_back_link_creates_cycle() {
+    let (builder, mut links) = course_outline();
+    links.push(NoteLink { from_id: 3, to_id: 1 });
+    let report = analyze_dag(&builder, links, &DagConfig::default()).expect("Analysis failed");
+
+    assert!(!report.is_dag);
+    assert_eq!(report.topological_order, None);
+    assert_eq!(report.cyclic_components, vec![vec!["lesson-3.md", "lesson-2.md", "lesson-1.md"]]);
+    assert_eq!(report.cycles, vec![vec!["lesson-3.md", "lesson-2.md", "lesson-1.md"]]);
+}
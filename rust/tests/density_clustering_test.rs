@@ -0,0 +1,39 @@
+//! Integration tests for density clustering.
+
+use rust::{Cosine, DbscanConfig, DensityAlgorithm, HdbscanConfig, NOISE_LABEL, dbscan, hdbscan};
+
+fn embeddings_with_strays() -> Vec<Vec<f64>> {
+    // Two topics of eight notes each, plus two notes on unrelated subjects
+    let mut vectors = Vec::new();
+    for topic in [[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0]] {
+        for note in 0..8 {
+            let drift = f64::from(note) * 0.01;
+            let mut vector = topic.to_vec();
+            vector[2] = drift;
+            vector[3] = 0.05 - drift / 2.0;
+            vectors.push(vector);
+        }
+    }
+    vectors.push(vec![-1.0, -1.0, 1.0, 0.0]);
+    vectors.push(vec![-1.0, -1.0, 0.0, 1.0]);
+    vectors
+}
+
+#[test]
+fn test_stray_notes_are_left_unclustered() {
+    let vectors = embeddings_with_strays();
+    let config = DbscanConfig { eps: 0.05, min_points: 3 };
+    let labels = dbscan(&vectors, &config, &Cosine).expect("DBSCAN failed");
+    assert_eq!(labels, [vec![0; 8], vec![1; 8], vec![NOISE_LABEL; 2]].concat());
+
+    let config = HdbscanConfig { min_cluster_size: 4, ..HdbscanConfig::default() };
+    let result = hdbscan(&vectors, &config, &Cosine).expect("HDBSCAN failed");
+    assert_eq!(result.labels, [vec![0; 8], vec![1; 8], vec![NOISE_LABEL; 2]].concat());
+    assert_eq!(result.stability.len(), 2);
+}
+
+#[test]
+fn test_algorithm_names() {
+    assert_eq!(DensityAlgorithm::from_name("HDBSCAN").ok(), Some(DensityAlgorithm::Hdbscan));
+    assert!(DensityAlgorithm::from_name("optics").is_err());
+}
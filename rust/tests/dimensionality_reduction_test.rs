@@ -1,4 +1,6 @@
-use rust::{DimensionalityReducer, SVDReducer};
+//! Integration tests for dimensionality reduction.
+
+use rust::{DimensionalityReducer, ProjectionModel, SVDReducer};
 
 #[test]
 fn test_svd_reduction_3d_to_2d() {
@@ -8,10 +10,16 @@ fn test_svd_reduction_3d_to_2d() {
     let reducer = SVDReducer::new();
     let result = reducer.reduce(&vectors, 2).expect("SVD reduction failed");
 
-    // Normalize results for deterministic snapshots (scale-invariant)
+    // Normalize results for deterministic snapshots; the second component is
+    // rounding noise (the input is rank one), so flush it to zero
     let normalized: Vec<Vec<String>> = result
         .iter()
-        .map(|v| v.iter().map(|x| format!("{x:.6}")).collect())
+        .map(|v| {
+            v.iter()
+                .map(|&x| if x.abs() < 1e-9 { 0.0 } else { x })
+                .map(|x| format!("{x:.6}"))
+                .collect()
+        })
         .collect();
 
     let snapshot = serde_json::to_string_pretty(&normalized).expect("Failed to serialize");
@@ -29,3 +37,44 @@ fn test_svd_reduction_preserves_count() {
     assert_eq!(result[0].len(), 2);
     assert_eq!(result[1].len(), 2);
 }
+
+#[test]
+fn test_pca_result_json_shape() {
+    let vectors =
+        vec![vec![2.0, 0.0, 1.0], vec![-2.0, 0.0, 1.0], vec![0.0, 1.0, 1.0], vec![0.0, -1.0, 1.0]];
+
+    let result = SVDReducer::new().pca(&vectors, 2).expect("PCA failed");
+    let json = serde_json::to_value(&result).expect("Failed to serialize");
+
+    let first = json["explainedVarianceRatio"][0]
+        .as_f64()
+        .expect("Missing ratio");
+    let total = json["cumulativeVariance"][1]
+        .as_f64()
+        .expect("Missing cumulative variance");
+    assert!((first - 0.8).abs() < 1e-12);
+    assert!((total - 1.0).abs() < 1e-12);
+    assert_eq!(json["loadings"][0].as_array().map(Vec::len), Some(3));
+    assert_eq!(json["coordinates"].as_array().map(Vec::len), Some(4));
+}
+
+#[test]
+fn test_new_note_keeps_existing_layout() {
+    let vectors = vec![
+        vec![1.0, 0.0, 0.0, 2.0],
+        vec![0.0, 1.0, 0.0, 1.0],
+        vec![0.0, 0.0, 1.0, 0.0],
+        vec![1.0, 1.0, 0.0, 3.0],
+    ];
+    let model = SVDReducer::new().fit(&vectors, 2).expect("Fit failed");
+    let layout = model.transform(&vectors).expect("Transform failed");
+
+    // Reload the model as a later session would, then add a note
+    let reloaded = ProjectionModel::from_bytes(&model.to_bytes()).expect("Reload failed");
+    let mut grown = vectors;
+    grown.push(vec![0.5, 0.5, 0.5, 1.5]);
+    let new_layout = reloaded.transform(&grown).expect("Transform failed");
+
+    assert_eq!(&new_layout[..4], layout.as_slice());
+    assert_eq!(new_layout[4].len(), 2);
+}
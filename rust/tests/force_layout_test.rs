@@ -0,0 +1,78 @@
+//! Integration tests for force layout.
+
+use rust::{AdjacencyMatrixBuilder, ForceLayout, ForceLayoutConfig, NoteLink};
+
+fn topic_links() -> Vec<NoteLink> {
+    // Two topic clusters of five notes each, joined by one cross-link
+    let mut links = Vec::new();
+    for cluster in [0, 5] {
+        for a in cluster..cluster + 5 {
+            for b in a + 1..cluster + 5 {
+                links.push(NoteLink { from_id: a, to_id: b });
+            }
+        }
+    }
+    links.push(NoteLink { from_id: 4, to_id: 5 });
+    links
+}
+
+fn centroid(points: &[Vec<f64>]) -> Vec<f64> {
+    (0..points[0].len())
+        .map(|d| points.iter().map(|p| p[d]).sum::<f64>() / 5.0)
+        .collect()
+}
+
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[test]
+fn test_topic_clusters_are_laid_out_apart() {
+    let paths: Vec<String> = (0..10).map(|i| format!("note{i}.md")).collect();
+    let matrix = AdjacencyMatrixBuilder::new(paths)
+        .build(topic_links())
+        .expect("Failed to build matrix");
+
+    for dims in [2, 3] {
+        let config = ForceLayoutConfig { dims, ..ForceLayoutConfig::default() };
+        let mut layout = ForceLayout::new(&matrix, config).expect("Layout failed");
+        layout.step(500);
+
+        let positions = layout.positions();
+        let (first, second) = positions.split_at(5);
+        let (a, b) = (centroid(first), centroid(second));
+        let gap = distance(&a, &b);
+        for p in first {
+            assert!(distance(p, &a) < gap, "dims {dims}");
+        }
+        for p in second {
+            assert!(distance(p, &b) < gap, "dims {dims}");
+        }
+    }
+}
+
+#[test]
+fn test_layout_resumes_from_saved_positions() {
+    let paths: Vec<String> = (0..10).map(|i| format!("note{i}.md")).collect();
+    let matrix = AdjacencyMatrixBuilder::new(paths)
+        .build(topic_links())
+        .expect("Failed to build matrix");
+
+    let config = ForceLayoutConfig::default();
+    let mut original = ForceLayout::new(&matrix, config).expect("Layout failed");
+    original.step(100);
+    let saved = original.flat_positions();
+    assert_eq!(saved.len(), 20);
+
+    let mut resumed =
+        ForceLayout::new(&matrix, ForceLayoutConfig { seed: 7, ..config }).expect("Layout failed");
+    resumed
+        .set_positions(&saved)
+        .expect("Failed to restore positions");
+    assert_eq!(resumed.positions(), original.positions());
+    assert!(resumed.set_positions(&saved[1..]).is_err());
+}
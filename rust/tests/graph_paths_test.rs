@@ -0,0 +1,51 @@
+//! Integration tests for graph paths.
+
+use rust::{
+    AdjacencyMatrixBuilder, GraphOptions, NoteDistance, NoteLink, connecting_paths,
+    notes_within_hops,
+};
+
+fn vault() -> (Vec<String>, Vec<NoteLink>) {
+    // inbox -> projects -> rust, inbox -> reading -> rust, and archive linking to inbox
+    let paths = ["inbox.md", "projects.md", "reading.md", "rust.md", "archive.md"]
+        .map(String::from)
+        .to_vec();
+    let links = [(0, 1), (1, 3), (0, 2), (2, 3), (4, 0)]
+        .into_iter()
+        .map(|(from_id, to_id)| NoteLink { from_id, to_id })
+        .collect();
+    (paths, links)
+}
+
+#[test]
+fn test_paths_between_notes() {
+    let (paths, links) = vault();
+    let builder = AdjacencyMatrixBuilder::new(paths);
+    let found = connecting_paths(&builder, links.clone(), "inbox.md", "rust.md", 5)
+        .expect("Path search failed");
+
+    assert_eq!(
+        found,
+        vec![vec!["inbox.md", "projects.md", "rust.md"], vec!["inbox.md", "reading.md", "rust.md"]]
+    );
+    assert!(
+        connecting_paths(&builder, links, "rust.md", "archive.md", 1)
+            .expect("Path search failed")
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_undirected_paths_and_hops() {
+    let (paths, links) = vault();
+    let options = GraphOptions { directed: false, ..GraphOptions::default() };
+    let builder = AdjacencyMatrixBuilder::with_options(paths, options);
+
+    let found = connecting_paths(&builder, links.clone(), "rust.md", "archive.md", 1)
+        .expect("Path search failed");
+    assert_eq!(found, vec![vec!["rust.md", "projects.md", "inbox.md", "archive.md"]]);
+
+    let nearby = notes_within_hops(&builder, links, "inbox.md", 1).expect("Search failed");
+    let hop = |path: &str| NoteDistance { path: path.to_string(), hops: 1 };
+    assert_eq!(nearby, vec![hop("projects.md"), hop("reading.md"), hop("archive.md")]);
+}
@@ -0,0 +1,29 @@
+//! Integration tests for graph stats.
+
+use rust::{NoteLink, compute_graph_stats};
+
+#[test]
+fn test_vault_health_report() {
+    // An index note linking out to two topic notes that link back, a reading
+    // list that links nowhere, and an orphaned draft
+    let note_paths = vec![
+        "index.md".to_string(),
+        "topics/rust.md".to_string(),
+        "topics/wasm.md".to_string(),
+        "reading-list.md".to_string(),
+        "drafts/untitled.md".to_string(),
+    ];
+    let links = vec![
+        NoteLink { from_id: 0, to_id: 1 },
+        NoteLink { from_id: 0, to_id: 2 },
+        NoteLink { from_id: 1, to_id: 0 },
+        NoteLink { from_id: 2, to_id: 0 },
+        NoteLink { from_id: 1, to_id: 2 },
+        NoteLink { from_id: 2, to_id: 3 },
+    ];
+
+    let stats = compute_graph_stats(&note_paths, links).expect("Failed to compute stats");
+
+    let snapshot = serde_json::to_string_pretty(&stats).expect("Failed to serialize");
+    insta::assert_snapshot!(snapshot);
+}
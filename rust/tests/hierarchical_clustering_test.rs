@@ -0,0 +1,52 @@
+//! Integration tests for hierarchical clustering.
+
+use rust::{Cosine, Dendrogram, DendrogramCut, Linkage, agglomerative_clustering};
+
+fn topic_embeddings() -> Vec<Vec<f64>> {
+    // Two topics (axes 0 and 1), each with two subtopics leaning towards axis 2 or 3
+    let mut vectors = Vec::new();
+    for topic in 0..2 {
+        for subtopic in 2..4 {
+            for note in 0..3 {
+                let mut vector = vec![0.0; 4];
+                vector[topic] = 1.0;
+                vector[subtopic] = f64::from(note).mul_add(0.01, 0.3);
+                vectors.push(vector);
+            }
+        }
+    }
+    vectors
+}
+
+#[test]
+fn test_topics_and_subtopics_at_different_cuts() {
+    for linkage in [Linkage::Complete, Linkage::Average, Linkage::Ward] {
+        let dendrogram = agglomerative_clustering(&topic_embeddings(), linkage, &Cosine)
+            .expect("Clustering failed");
+
+        let topics = dendrogram
+            .cut(DendrogramCut::Clusters(2))
+            .expect("Cut failed");
+        assert_eq!(topics, [vec![0; 6], vec![1; 6]].concat(), "{linkage:?}");
+
+        let subtopics = dendrogram
+            .cut(DendrogramCut::Clusters(4))
+            .expect("Cut failed");
+        let expected: Vec<usize> = (0..4).flat_map(|s| vec![s; 3]).collect();
+        assert_eq!(subtopics, expected, "{linkage:?}");
+    }
+}
+
+#[test]
+fn test_dendrogram_json_round_trip() {
+    let dendrogram = agglomerative_clustering(&topic_embeddings(), Linkage::Average, &Cosine)
+        .expect("Clustering failed");
+    let json = serde_json::to_string(&dendrogram).expect("Failed to serialize");
+    assert!(json.contains("\"leafCount\":12"));
+
+    let restored: Dendrogram = serde_json::from_str(&json).expect("Failed to parse");
+    assert_eq!(restored, dendrogram);
+    let cut: DendrogramCut =
+        serde_json::from_str(r#"{"clusters": 2}"#).expect("Failed to parse cut");
+    assert_eq!(restored.cut(cut).expect("Cut failed").iter().max(), Some(&1));
+}
@@ -0,0 +1,90 @@
+//! Integration tests for hnsw.
+
+use rust::{Cosine, HnswConfig, HnswIndex, NeighborQuery, VectorWithMetadata, find_related_notes};
+
+/// Deterministic pseudo-random vectors (xorshift) so the test needs no RNG crate.
+#[allow(clippy::cast_precision_loss)]
+fn random_records(count: usize, dim: usize, mut state: u64) -> Vec<VectorWithMetadata> {
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+    };
+    (0..count)
+        .map(|i| {
+            let vector = (0..dim).map(|_| next()).collect();
+            VectorWithMetadata::new(
+                format!("note{i}.md"),
+                format!("Note {i}"),
+                vector,
+                "test".into(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn test_hnsw_recall_against_exact_search() {
+    let records = random_records(1000, 24, 0x1234_5678);
+    let queries = random_records(50, 24, 0x9abc_def0);
+    let k = 10;
+
+    let index = HnswIndex::build(
+        records.clone(),
+        HnswConfig { ef_construction: 100, ..HnswConfig::default() },
+        Box::new(Cosine),
+    )
+    .expect("Build failed");
+
+    let mut hits = 0;
+    for query in &queries {
+        let exact = find_related_notes(
+            &records,
+            &NeighborQuery::Vector(query.vector.clone()),
+            k,
+            &Cosine,
+            None,
+        )
+        .expect("Exact search failed");
+        let approx = index
+            .search(&query.vector, k, 64)
+            .expect("HNSW search failed");
+
+        hits += approx
+            .iter()
+            .filter(|a| exact.iter().any(|e| e.id == a.id))
+            .count();
+    }
+
+    let recall = hits as f64 / (queries.len() * k) as f64;
+    assert!(recall >= 0.95, "recall@{k} too low: {recall:.3}");
+}
+
+#[test]
+fn test_hnsw_incremental_updates_survive_round_trip() {
+    let records = random_records(200, 8, 42);
+    let mut index =
+        HnswIndex::build(records[..150].to_vec(), HnswConfig::default(), Box::new(Cosine))
+            .expect("Build failed");
+
+    for record in &records[150..] {
+        index.insert(record.clone()).expect("Insert failed");
+    }
+    index.remove("note3.md");
+
+    let restored = HnswIndex::from_bytes(&index.to_bytes()).expect("Decode failed");
+    assert_eq!(restored.len(), 199);
+    assert!(!restored.contains("note3.md"));
+
+    let results = restored
+        .search_by_id("note180.md", 5, 50)
+        .expect("Search failed");
+    assert_eq!(results.len(), 5);
+    assert!(
+        results
+            .iter()
+            .all(|r| r.id != "note180.md" && r.id != "note3.md")
+    );
+}
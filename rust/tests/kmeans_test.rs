@@ -0,0 +1,51 @@
+//! Integration tests for kmeans.
+
+use rust::{Cosine, KMeansConfig, kmeans};
+
+fn topic_embeddings() -> Vec<Vec<f64>> {
+    // Three topics pointing along different axes, each listed out of order
+    vec![
+        vec![0.9, 0.1, 0.0],
+        vec![0.0, 1.0, 0.1],
+        vec![0.1, 0.0, 0.9],
+        vec![1.0, 0.0, 0.1],
+        vec![0.1, 0.9, 0.0],
+        vec![0.0, 0.1, 1.0],
+        vec![0.8, 0.2, 0.1],
+        vec![0.2, 0.8, 0.1],
+        vec![0.1, 0.1, 0.8],
+    ]
+}
+
+#[test]
+fn test_cosine_kmeans_groups_topics() {
+    let result = kmeans(&topic_embeddings(), 3, &KMeansConfig::default(), &Cosine)
+        .expect("Clustering failed");
+
+    for topic in 0..3 {
+        let members: Vec<usize> = (topic..9)
+            .step_by(3)
+            .map(|i| result.assignments[i])
+            .collect();
+        assert!(members.iter().all(|&c| c == members[0]), "topic {topic}: {members:?}");
+    }
+    assert_eq!(result.centroids.len(), 3);
+    assert!(result.converged);
+    assert!(result.iterations >= 1);
+    assert!(result.inertia < 0.1, "{}", result.inertia);
+}
+
+#[test]
+fn test_result_serializes_in_camel_case() {
+    let result = kmeans(&topic_embeddings(), 2, &KMeansConfig::default(), &Cosine)
+        .expect("Clustering failed");
+    let json = serde_json::to_value(&result).expect("Failed to serialize");
+
+    for key in ["assignments", "centroids", "inertia", "iterations", "converged"] {
+        assert!(json.get(key).is_some(), "missing {key}");
+    }
+    let config: KMeansConfig =
+        serde_json::from_str(r#"{"maxIterations": 5}"#).expect("Failed to parse config");
+    assert_eq!(config.max_iterations, 5);
+    assert_eq!(config.restarts, KMeansConfig::default().restarts);
+}
@@ -0,0 +1,47 @@
+//! Integration tests for link extractor.
+
+use rust::{AdjacencyMatrixBuilder, NoteContent, extract_links};
+
+#[test]
+fn test_extract_links_vault() {
+    let vault: Vec<String> = ["Index.md", "Topics/Rust.md", "Topics/Wasm.md", "img/diagram.png"]
+        .iter()
+        .map(|s| (*s).to_string())
+        .collect();
+
+    let notes = vec![
+        NoteContent {
+            path: "Index.md".to_string(),
+            content: "# Index\n- [[Rust|The Rust note]]\n- [[topics/wasm#Setup]]\n- [[Missing]]"
+                .to_string(),
+        },
+        NoteContent {
+            path: "Topics/Rust.md".to_string(),
+            content: "See [wasm](Wasm.md) and ![[diagram.png]]\n```\n[[Index]]\n```".to_string(),
+        },
+        NoteContent {
+            path: "Topics/Wasm.md".to_string(),
+            content: "Back to [[../Index^intro]] or [docs](https://example.com)".to_string(),
+        },
+    ];
+
+    let result = extract_links(&notes, &vault).expect("Extraction failed");
+
+    let snapshot = serde_json::to_string_pretty(&result).expect("Failed to serialize");
+    insta::assert_snapshot!(snapshot);
+}
+
+#[test]
+fn test_extracted_links_feed_adjacency_builder() {
+    let vault: Vec<String> = vec!["A.md".to_string(), "B.md".to_string()];
+    let notes = vec![
+        NoteContent { path: "A.md".to_string(), content: "[[B]] [[b]]".to_string() },
+        NoteContent { path: "B.md".to_string(), content: "[[A]]".to_string() },
+    ];
+
+    let result = extract_links(&notes, &vault).expect("Extraction failed");
+    let builder = AdjacencyMatrixBuilder::new(vault);
+    let matrix = builder.build(result.links).expect("Failed to build matrix");
+
+    assert_eq!(builder.matrix_to_vectors(&matrix), vec![vec![0.0, 2.0], vec![1.0, 0.0]]);
+}
@@ -0,0 +1,29 @@
+//! Integration tests for nearest neighbors.
+
+use rust::{Cosine, NeighborQuery, VectorWithMetadata, find_related_notes};
+
+#[test]
+fn test_related_notes_snapshot() {
+    let notes: Vec<VectorWithMetadata> = [
+        ("rust.md", vec![1.0, 0.2, 0.0]),
+        ("wasm.md", vec![0.8, 0.4, 0.1]),
+        ("cooking.md", vec![0.0, 0.1, 1.0]),
+        ("cargo.md", vec![0.9, 0.1, 0.05]),
+    ]
+    .into_iter()
+    .map(|(id, vector)| {
+        VectorWithMetadata::new(id.to_string(), id.replace(".md", ""), vector, "test".to_string())
+    })
+    .collect();
+
+    let results =
+        find_related_notes(&notes, &NeighborQuery::Id("rust.md".to_string()), 3, &Cosine, None)
+            .expect("Query failed");
+
+    let formatted: Vec<(String, String)> = results
+        .iter()
+        .map(|r| (r.id.clone(), format!("{:.6}", r.score)))
+        .collect();
+    let snapshot = serde_json::to_string_pretty(&formatted).expect("Failed to serialize");
+    insta::assert_snapshot!(snapshot);
+}
@@ -1,5 +1,7 @@
+//! Integration tests for settings.
+
 use rust::{
-    PluginError, PluginSettings, deserialize_settings, serialize_settings,
+    PluginError, PluginSettings, deserialize_settings, merge_settings, serialize_settings,
     validate_setting_internal,
 };
 
@@ -7,7 +9,7 @@ use rust::{
 fn test_default_settings_serialization() {
     let settings = PluginSettings::default();
     let json = serialize_settings(&settings).expect("Should serialize default settings");
-    insta::assert_snapshot!(json, @r#"{"mySetting":"default"}"#);
+    insta::assert_snapshot!(json, @r#"{"mySetting":"default","autoClusterCount":false}"#);
 }
 
 #[test]
@@ -15,6 +17,14 @@ fn test_deserialize_valid_settings() {
     let json = r#"{"mySetting":"custom_value"}"#;
     let settings = deserialize_settings(json).expect("Should deserialize valid JSON");
     insta::assert_snapshot!(settings.my_setting, @"custom_value");
+    assert!(!settings.auto_cluster_count);
+}
+
+#[test]
+fn test_merge_settings_keeps_auto_cluster_count() {
+    let defaults = serialize_settings(&PluginSettings::default()).expect("Should serialize");
+    let merged = merge_settings(&defaults, r#"{"autoClusterCount":true}"#);
+    insta::assert_snapshot!(merged, @r#"{"mySetting":"default","autoClusterCount":true}"#);
 }
 
 #[test]
@@ -52,6 +62,21 @@ fn test_validate_setting_my_setting_empty() {
     }
 }
 
+#[test]
+fn test_validate_setting_auto_cluster_count() {
+    assert!(validate_setting_internal("autoClusterCount", "true").is_ok());
+    assert!(validate_setting_internal("autoClusterCount", "false").is_ok());
+
+    if let Err(PluginError::ValidationError { field, reason, .. }) =
+        validate_setting_internal("autoClusterCount", "yes")
+    {
+        insta::assert_snapshot!(field, @"autoClusterCount");
+        insta::assert_snapshot!(reason, @"Setting value must be true or false");
+    } else {
+        panic!("Expected ValidationError");
+    }
+}
+
 #[test]
 fn test_validate_setting_unknown_key() {
     let result = validate_setting_internal("unknownKey", "value");
@@ -0,0 +1,54 @@
+//! Integration tests for sparse svd.
+
+use rust::{AdjacencyMatrixBuilder, NoteLink, TruncatedSVDReducer};
+
+#[test]
+fn test_link_graph_to_3d_coordinates() {
+    let note_paths: Vec<String> = (0..8).map(|i| format!("note{i}.md")).collect();
+    let links: Vec<NoteLink> = (0..8)
+        .flat_map(|i| {
+            [
+                NoteLink { from_id: i, to_id: (i + 1) % 8 },
+                NoteLink { from_id: i, to_id: (i + 3) % 8 },
+            ]
+        })
+        .collect();
+
+    let builder = AdjacencyMatrixBuilder::new(note_paths);
+    let matrix = builder.build(links).expect("Failed to build matrix");
+    let coords = TruncatedSVDReducer::new()
+        .reduce_sparse(&matrix, 3)
+        .expect("Truncated SVD failed");
+
+    assert_eq!(coords.len(), 8);
+    assert!(
+        coords
+            .iter()
+            .all(|c| c.len() == 3 && c.iter().all(|x| x.is_finite()))
+    );
+}
+
+#[test]
+fn test_laplacian_reduction_is_centered() {
+    let note_paths: Vec<String> = (0..5).map(|i| format!("note{i}.md")).collect();
+    let links = vec![
+        NoteLink { from_id: 0, to_id: 1 },
+        NoteLink { from_id: 1, to_id: 2 },
+        NoteLink { from_id: 2, to_id: 0 },
+        NoteLink { from_id: 3, to_id: 4 },
+    ];
+
+    let builder = AdjacencyMatrixBuilder::new(note_paths);
+    let matrix = builder
+        .build_laplacian(links)
+        .expect("Failed to build Laplacian");
+    let coords = TruncatedSVDReducer::new()
+        .reduce_sparse(&matrix, 2)
+        .expect("Truncated SVD failed");
+
+    // Centered projections have zero mean along every component
+    for j in 0..2 {
+        let mean: f64 = coords.iter().map(|c| c[j]).sum::<f64>() / 5.0;
+        assert!(mean.abs() < 1e-10);
+    }
+}
@@ -0,0 +1,39 @@
+//! Integration tests for spectral.
+
+use rust::{
+    AdjacencyMatrixBuilder, GraphOptions, LaplacianKind, NoteLink, SpectralReducer,
+    connected_components,
+};
+
+#[test]
+fn test_vault_with_orphans_embeds_every_note() {
+    // Two linked topic clusters, a bridge, and two orphan notes
+    let paths: Vec<String> = (0..12).map(|i| format!("note{i}.md")).collect();
+    let options = GraphOptions {
+        directed: false,
+        laplacian: LaplacianKind::SymmetricNormalized,
+        self_loops: false,
+        binarize: false,
+    };
+    let builder = AdjacencyMatrixBuilder::with_options(paths, options);
+    let links: Vec<NoteLink> =
+        [(0, 1), (1, 2), (2, 0), (2, 3), (4, 5), (5, 6), (6, 7), (7, 4), (3, 4), (8, 9)]
+            .into_iter()
+            .map(|(from_id, to_id)| NoteLink { from_id, to_id })
+            .collect();
+    let laplacian = builder
+        .build_laplacian(links)
+        .expect("Failed to build Laplacian");
+
+    let result = SpectralReducer::new()
+        .embed(&laplacian, 3)
+        .expect("Spectral embedding failed");
+
+    assert_eq!(result.component_count, 4);
+    assert_eq!(result.components, connected_components(&laplacian));
+    assert!(result.converged);
+    assert_eq!(result.coordinates.len(), 12);
+
+    // Orphans get distinct positions rather than collapsing onto one point
+    assert_ne!(result.coordinates[10], result.coordinates[11]);
+}
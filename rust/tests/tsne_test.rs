@@ -0,0 +1,79 @@
+//! Integration tests for tsne.
+
+use rust::{Cosine, DimensionalityReducer, Euclidean, TSNEReducer};
+
+fn grid_clusters() -> Vec<Vec<f64>> {
+    // Four tight clusters at the corners of a square in 5D
+    (0..60)
+        .map(|i| {
+            let corner = i % 4;
+            let jitter = f64::from(i / 4) * 0.02;
+            vec![
+                f64::from(u8::from(corner & 1 == 1)) * 10.0 + jitter,
+                f64::from(u8::from(corner & 2 == 2)) * 10.0 - jitter,
+                jitter,
+                0.0,
+                1.0,
+            ]
+        })
+        .collect()
+}
+
+#[test]
+fn test_tsne_2d_and_3d_outputs() {
+    let vectors = grid_clusters();
+    let reducer = TSNEReducer::with_options(10.0, 12.0, 100.0, Box::new(Euclidean), 300, 5);
+
+    for dims in [2, 3] {
+        let coords = reducer.reduce(&vectors, dims).expect("t-SNE failed");
+        assert_eq!(coords.len(), 60);
+        assert!(
+            coords
+                .iter()
+                .all(|c| c.len() == dims && c.iter().all(|x| x.is_finite()))
+        );
+    }
+}
+
+#[test]
+fn test_tsne_seed_changes_layout() {
+    let vectors = grid_clusters();
+    let a = TSNEReducer::with_options(10.0, 12.0, 100.0, Box::new(Euclidean), 100, 1)
+        .reduce(&vectors, 2)
+        .expect("t-SNE failed");
+    let b = TSNEReducer::with_options(10.0, 12.0, 100.0, Box::new(Euclidean), 100, 2)
+        .reduce(&vectors, 2)
+        .expect("t-SNE failed");
+    let a_again = TSNEReducer::with_options(10.0, 12.0, 100.0, Box::new(Euclidean), 100, 1)
+        .reduce(&vectors, 2)
+        .expect("t-SNE failed");
+
+    assert_ne!(a, b);
+    assert_eq!(a, a_again);
+}
+
+#[test]
+fn test_tsne_uses_metric() {
+    // Two directions at very different magnitudes: far apart in Euclidean
+    // space, but two tight groups under cosine distance
+    let vectors: Vec<Vec<f64>> = (0..40)
+        .map(|i| {
+            let scale = f64::from(i / 2 + 1);
+            if i % 2 == 0 {
+                vec![scale, 0.1]
+            } else {
+                vec![0.1, scale]
+            }
+        })
+        .collect();
+    let coords = TSNEReducer::with_options(5.0, 12.0, 100.0, Box::new(Cosine), 300, 3)
+        .reduce(&vectors, 2)
+        .expect("t-SNE failed");
+
+    let distance =
+        |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>();
+    // The smallest and largest vectors along one axis land closer together
+    // than either does to the other axis' nearest vector
+    assert!(distance(&coords[0], &coords[38]) < distance(&coords[0], &coords[1]));
+    assert!(distance(&coords[1], &coords[39]) < distance(&coords[1], &coords[0]));
+}
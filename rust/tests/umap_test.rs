@@ -0,0 +1,60 @@
+//! Integration tests for umap.
+
+use rust::{Cosine, DimensionalityReducer, UMAPReducer, distance_metric_from_name};
+
+fn topic_vectors() -> Vec<Vec<f64>> {
+    // Three "topics" pointing along different axes, with small per-note variation
+    (0..30)
+        .map(|i| {
+            let mut v = vec![0.05; 6];
+            v[i % 3] = 1.0;
+            #[allow(clippy::cast_precision_loss)]
+            let jitter = (i / 3) as f64 * 0.01;
+            v[3 + i % 3] += jitter;
+            v
+        })
+        .collect()
+}
+
+#[test]
+fn test_umap_same_seed_same_layout() {
+    let vectors = topic_vectors();
+    let make = || {
+        UMAPReducer::with_options(
+            5,
+            0.1,
+            distance_metric_from_name("cosine").expect("Unknown metric"),
+            100,
+            1234,
+        )
+    };
+
+    let first = make().reduce(&vectors, 2).expect("UMAP failed");
+    let second = make().reduce(&vectors, 2).expect("UMAP failed");
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_umap_keeps_topics_apart() {
+    let vectors = topic_vectors();
+    let coords = UMAPReducer::with_options(5, 0.1, Box::new(Cosine), 200, 7)
+        .reduce(&vectors, 3)
+        .expect("UMAP failed");
+
+    assert_eq!(coords.len(), 30);
+    // Every point's nearest embedded neighbour should share its topic
+    for (i, a) in coords.iter().enumerate() {
+        let nearest = coords
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(j, b)| {
+                let d: f64 = a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum();
+                (d, j)
+            })
+            .min_by(|x, y| x.0.total_cmp(&y.0))
+            .map(|(_, j)| j)
+            .expect("No neighbours");
+        assert_eq!(nearest % 3, i % 3, "point {i} landed next to point {nearest}");
+    }
+}
@@ -1,3 +1,5 @@
+//! Integration tests for utils.
+
 use rust::{add, multiply, reverse_string, to_title_case, word_count};
 
 #[test]
@@ -1,3 +1,5 @@
+//! Integration tests for vector ops.
+
 use rust::{normalize_vectors, simple_kmeans_clustering};
 
 #[test]
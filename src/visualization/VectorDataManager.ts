@@ -3,7 +3,12 @@ import { VectorSourceType as VectorSourceTypeEnum } from "./types";
 import type { VectorSourceProvider } from "./providers/VectorSourceProvider";
 import { EmbeddingSourceProvider } from "./providers/EmbeddingSourceProvider";
 import { AdjacencyMatrixProvider } from "./providers/AdjacencyMatrixProvider";
-import { reduce_dimensions_svd, cluster_vectors } from "../../pkg/rust";
+import {
+	reduce_dimensions_svd,
+	reduce_dimensions_umap,
+	cluster_vectors,
+	cluster_vectors_auto,
+} from "../../pkg/rust";
 import type HelloWorldPlugin from "../../main";
 
 export class VectorDataManager {
@@ -77,7 +82,9 @@ export class VectorDataManager {
 			console.log("[VectorDataManager] Reduction complete, result count:", reduced3d.length);
 
 			// Compute clusters
-			const clusterCount = Math.min(10, Math.max(3, Math.floor(vectors.length / 50)));
+			const clusterCount = this.plugin.settings.autoClusterCount
+				? "auto"
+				: Math.min(10, Math.max(3, Math.floor(vectors.length / 50)));
 			console.log("[VectorDataManager] Computing", clusterCount, "clusters...");
 			const clusters = this.computeClusters(vectors, clusterCount);
 			console.log("[VectorDataManager] Clustering complete");
@@ -125,6 +132,18 @@ export class VectorDataManager {
 				);
 				return parsed;
 			}
+			if (method === "umap") {
+				const result = reduce_dimensions_umap(
+					JSON.stringify(vectors),
+					3,
+					15,
+					0.1,
+					"cosine",
+					200,
+					42
+				);
+				return JSON.parse(result) as number[][];
+			}
 			throw new Error(`Unsupported reduction method: ${method}`);
 		} catch (error) {
 			console.error("[VectorDataManager] Error in computeReduction:", error);
@@ -133,9 +152,9 @@ export class VectorDataManager {
 	}
 
 	/**
-	 * Compute cluster assignments.
+	 * Compute cluster assignments, choosing the count by silhouette for "auto".
 	 */
-	private computeClusters(vectors: number[][], numClusters: number): number[] {
+	private computeClusters(vectors: number[][], numClusters: number | "auto"): number[] {
 		try {
 			console.log(
 				"[VectorDataManager] Calling WASM clustering with",
@@ -144,7 +163,11 @@ export class VectorDataManager {
 				numClusters,
 				"clusters"
 			);
-			const result = cluster_vectors(JSON.stringify(vectors), numClusters);
+			const vectorsJson = JSON.stringify(vectors);
+			const result =
+				numClusters === "auto"
+					? cluster_vectors_auto(vectorsJson, "euclidean")
+					: cluster_vectors(vectorsJson, numClusters, "euclidean");
 			const parsed = JSON.parse(result) as number[];
 			console.log("[VectorDataManager] Clustering returned", parsed.length, "assignments");
 			return parsed;
@@ -159,7 +182,8 @@ export class VectorDataManager {
 	 */
 	private getCacheKey(sourceConfig: VectorSourceConfig): string {
 		const vaultName = this.plugin.app.vault.getName();
-		return `${sourceConfig.type}:${sourceConfig.id}:${vaultName}`;
+		const clustering = this.plugin.settings.autoClusterCount ? "auto" : "fixed";
+		return `${sourceConfig.type}:${sourceConfig.id}:${vaultName}:${clustering}`;
 	}
 
 	/**